@@ -0,0 +1,53 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Decoding of fuzz inputs and a naive reference matcher to compare the BNDM search with.
+
+/// A pattern, an optional wildcard and a source decoded from a fuzz input.
+pub struct Input {
+    pub pattern: Vec<u8>,
+    pub wildcard: Option<u8>,
+    pub source: Vec<u8>
+}
+
+impl Input {
+    /// Decodes a fuzz input, which consists of a flags byte, a wildcard byte, a pattern length
+    /// byte, the pattern and the source.
+    ///
+    /// Bit 0 of the flags enables the wildcard. Bit 1 reduces all bytes of the pattern and the
+    /// source to a small alphabet, so that patterns longer than the word size match regularly.
+    pub fn decode(data: &[u8]) -> Option<Input> {
+        let (&[flags, wildcard, pattern_len], rest) = data.split_first_chunk::<3>()?;
+        let pattern_len = (pattern_len as usize).min(rest.len());
+        let small_alphabet = flags & 2 != 0;
+        let reduce = |bytes: &[u8]| -> Vec<u8> {
+            bytes.iter().map(|&byte| if small_alphabet { byte % 3 } else { byte }).collect()
+        };
+
+        Some(Input {
+            pattern: reduce(&rest[..pattern_len]),
+            wildcard: (flags & 1 != 0).then_some(if small_alphabet { wildcard % 3 } else { wildcard }),
+            source: reduce(&rest[pattern_len..])
+        })
+    }
+}
+
+/// Returns whether the pattern occurs at the given index of the source.
+pub fn matches_at(source: &[u8], pattern: &[u8], wildcard: Option<u8>, index: usize) -> bool {
+    source.get(index..index + pattern.len()).is_some_and(|window| {
+        window.iter().zip(pattern).all(|(&byte, &pattern_byte)| byte == pattern_byte || wildcard == Some(pattern_byte))
+    })
+}
+
+/// Returns the index of the first occurrence of the pattern by comparing the pattern at every
+/// index of the source.
+pub fn naive_find(source: &[u8], pattern: &[u8], wildcard: Option<u8>, start_index: usize) -> Option<usize> {
+    if pattern.len() == 1 && wildcard == Some(pattern[0]) && start_index == 0 {
+        // A single wildcard matches at the start, even in an empty source.
+        return Some(0);
+    }
+    if pattern.is_empty() {
+        return None;
+    }
+    (start_index..source.len()).find(|&index| matches_at(source, pattern, wildcard, index))
+}
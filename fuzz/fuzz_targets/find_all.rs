@@ -0,0 +1,32 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+#![no_main]
+
+#[path = "common/mod.rs"]
+mod common;
+
+use bndm::{BndmConfig, find_all, find_all_cb};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Some(input) = common::Input::decode(data) else {
+        return;
+    };
+    if input.pattern.len() == 1 && input.wildcard == Some(input.pattern[0]) {
+        // A single wildcard matches at every index; this is covered by the find_pattern target.
+        return;
+    }
+
+    let config = BndmConfig::new(&input.pattern, input.wildcard);
+    let mut indexes = Vec::new();
+    find_all_cb(&input.source, &config, |index| indexes.push(index));
+
+    let expected: Vec<usize> = std::iter::successors(
+        common::naive_find(&input.source, &input.pattern, input.wildcard, 0),
+        |&index| common::naive_find(&input.source, &input.pattern, input.wildcard, index + input.pattern.len()))
+        .collect();
+    assert_eq!(indexes, expected);
+    assert_eq!(find_all(&input.source, &config), expected);
+    assert_eq!(config.find_iter(&input.source).collect::<Vec<_>>(), expected);
+});
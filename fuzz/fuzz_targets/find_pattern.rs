@@ -0,0 +1,27 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+#![no_main]
+
+#[path = "common/mod.rs"]
+mod common;
+
+use bndm::{BndmConfig, find_pattern, rfind_pattern};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Some(input) = common::Input::decode(data) else {
+        return;
+    };
+
+    let config = BndmConfig::new(&input.pattern, input.wildcard);
+    let expected = common::naive_find(&input.source, &input.pattern, input.wildcard, 0);
+    assert_eq!(find_pattern(&input.source, &config), expected);
+
+    let expected_last = if input.pattern.is_empty() {
+        None
+    } else {
+        (0..input.source.len()).rev().find(|&index| common::matches_at(&input.source, &input.pattern, input.wildcard, index))
+    };
+    assert_eq!(rfind_pattern(&input.source, &config), expected_last);
+});
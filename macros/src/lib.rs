@@ -0,0 +1,101 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Procedural macros for the bndm crate.
+
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+mod signature;
+
+use signature::{Position, parse_signature};
+
+/// Checks a hexadecimal signature at build time and expands to a `&'static BndmConfig`.
+///
+/// The signature has the syntax of `BndmConfig::from_hex`, e.g. `"4D 5A ?? ??"`. Syntax
+/// errors are reported at compile time. The bitmasks are not computed by the compiler, as
+/// they depend on the word size of the target. Instead, the configuration is built from the
+/// parsed positions on first use, which costs the same as building it with `PatternBuilder`,
+/// and is shared afterwards, so later uses only load the reference.
+///
+/// # Usage
+///
+/// ```rust,ignore
+/// use bndm::{find_pattern, pattern};
+///
+/// let config = pattern!("4D 5A ?? ??");
+/// assert_eq!(find_pattern(b"xxMZ\x90\x00", config), Some(2));
+/// ```
+#[proc_macro]
+pub fn pattern(input: TokenStream) -> TokenStream {
+    let (literal, span) = match string_literal(input) {
+        Ok(literal) => literal,
+        Err((message, span)) => return compile_error(message, span)
+    };
+
+    match parse_signature(&literal) {
+        Ok(positions) => expand(&positions),
+        Err(error) => compile_error(&error, span)
+    }
+}
+
+fn string_literal(input: TokenStream) -> Result<(String, Span), (&'static str, Span)> {
+    let mut tokens = input.into_iter();
+    let token = match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Group(group)), None) if group.delimiter() == Delimiter::None => return string_literal(group.stream()),
+        (Some(token), None) => token,
+        (Some(token), Some(_)) => return Err(("expected a single string literal", token.span())),
+        (None, _) => return Err(("expected a string literal", Span::call_site()))
+    };
+
+    let TokenTree::Literal(literal) = token else {
+        return Err(("expected a string literal", token.span()));
+    };
+    let (literal, span) = (literal.to_string(), literal.span());
+    if !literal.starts_with(['"', 'r']) {
+        return Err(("expected a string literal", span));
+    }
+    unquote(&literal).map(|value| (value, span)).ok_or(("expected a string literal without escapes", span))
+}
+
+fn unquote(literal: &str) -> Option<String> {
+    match literal.strip_prefix('r') {
+        Some(raw) => {
+            let hashes = raw.len() - raw.trim_start_matches('#').len();
+            raw.get(hashes + 1..raw.len() - hashes - 1).map(str::to_owned)
+        }
+        None => literal.strip_prefix('"')?.strip_suffix('"').filter(|value| !value.contains('\\')).map(str::to_owned)
+    }
+}
+
+fn expand(positions: &[Position]) -> TokenStream {
+    let calls: String = positions.iter().map(|position| match position {
+        Position::Masked(value, mask) => format!(".masked({value:#04x}, {mask:#04x})"),
+        Position::Range(start, end) => format!(".range({start:#04x}..={end:#04x})"),
+        Position::Any => ".any()".to_owned(),
+        Position::Skip(count) => format!(".skip({count})")
+    }).collect();
+
+    format!("{{
+        static CONFIG: ::std::sync::OnceLock<::bndm::BndmConfig> = ::std::sync::OnceLock::new();
+        CONFIG.get_or_init(|| ::bndm::PatternBuilder::new(){calls}.build())
+    }}").parse().expect("generated code is valid")
+}
+
+fn compile_error(message: &str, span: Span) -> TokenStream {
+    let mut literal = Literal::string(message);
+    literal.set_span(span);
+
+    [
+        TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+        TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+        TokenTree::Ident(Ident::new("core", span)),
+        TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+        TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+        TokenTree::Ident(Ident::new("compile_error", span)),
+        TokenTree::Punct(Punct::new('!', Spacing::Alone)),
+        TokenTree::Group(Group::new(Delimiter::Parenthesis, TokenTree::Literal(literal).into()))
+    ].into_iter().map(|mut token| {
+        token.set_span(span);
+        token
+    }).collect()
+}
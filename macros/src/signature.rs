@@ -0,0 +1,80 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Parsing of hexadecimal signatures, with the same syntax as `BndmConfig::from_hex`.
+
+const MAX_SKIP: usize = 0x10000;
+
+/// A position or a number of positions of a signature.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Position {
+    /// Matches every byte whose bits selected by the mask equal those of the value.
+    Masked(u8, u8),
+
+    /// Matches any byte of the range.
+    Range(u8, u8),
+
+    /// Matches any byte.
+    Any,
+
+    /// Skips the given number of positions that match any byte.
+    Skip(usize)
+}
+
+pub(crate) fn parse_signature(signature: &str) -> Result<Vec<Position>, String> {
+    let positions = signature.split_whitespace().enumerate()
+        .map(|(index, token)| parse_token(token).ok_or_else(|| format!("invalid token at index {index}")))
+        .collect::<Result<Vec<Position>, String>>()?;
+
+    let is_empty = positions.iter().all(|position| matches!(position, Position::Skip(0)));
+    if is_empty {
+        return Err("signature is empty".to_owned());
+    }
+    Ok(positions)
+}
+
+fn parse_token(token: &str) -> Option<Position> {
+    if token == "?" {
+        return Some(Position::Any);
+    }
+
+    match token.strip_prefix('[').and_then(|range| range.strip_suffix(']')) {
+        Some(range) => match range.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (parse_hex_digits(start)?, parse_hex_digits(end)?);
+                (start <= end).then_some(Position::Range(start, end))
+            }
+            None => range.bytes().all(|digit| digit.is_ascii_digit())
+                .then(|| range.parse().ok().filter(|&count| count <= MAX_SKIP).map(Position::Skip))
+                .flatten()
+        },
+        None => parse_hex_byte(token).map(|(value, mask)| Position::Masked(value, mask))
+    }
+}
+
+fn parse_hex_digits(digits: &str) -> Option<u8> {
+    match parse_hex_byte(digits)? {
+        (value, 0xff) => Some(value),
+        _ => None
+    }
+}
+
+fn parse_hex_byte(token: &str) -> Option<(u8, u8)> {
+    let &[high, low] = token.as_bytes() else {
+        return None;
+    };
+    let (high_value, high_mask) = parse_nibble(high)?;
+    let (low_value, low_mask) = parse_nibble(low)?;
+    Some(((high_value << 4) | low_value, (high_mask << 4) | low_mask))
+}
+
+fn parse_nibble(digit: u8) -> Option<(u8, u8)> {
+    match digit {
+        b'?' => Some((0, 0)),
+        _ => (digit as char).to_digit(16).map(|value| (value as u8, 0xf))
+    }
+}
+
+#[cfg(test)]
+#[path = "./signature_test.rs"]
+mod signature_test;
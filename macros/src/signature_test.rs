@@ -0,0 +1,28 @@
+use super::*;
+
+#[test]
+fn parse_positions() {
+    let positions = parse_signature("4D ?? 4? ? [30-39] [3] [65536]").unwrap();
+
+    assert_eq!(positions, vec![
+        Position::Masked(0x4d, 0xff),
+        Position::Masked(0x00, 0x00),
+        Position::Masked(0x40, 0xf0),
+        Position::Any,
+        Position::Range(0x30, 0x39),
+        Position::Skip(3),
+        Position::Skip(65536)
+    ]);
+}
+
+#[test]
+fn parse_invalid_signatures() {
+    assert_eq!(parse_signature(""), Err("signature is empty".to_owned()));
+    assert_eq!(parse_signature("[0]"), Err("signature is empty".to_owned()));
+    assert_eq!(parse_signature("4D 5"), Err("invalid token at index 1".to_owned()));
+    assert_eq!(parse_signature("[39-30]"), Err("invalid token at index 0".to_owned()));
+    assert_eq!(parse_signature("[+2]"), Err("invalid token at index 0".to_owned()));
+    assert_eq!(parse_signature("[3?-39]"), Err("invalid token at index 0".to_owned()));
+    assert_eq!(parse_signature("4D [65537] 5A"), Err("invalid token at index 1".to_owned()));
+    assert_eq!(parse_signature("4D [18446744073709551616] 5A"), Err("invalid token at index 1".to_owned()));
+}
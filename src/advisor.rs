@@ -0,0 +1,206 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Recommendations of a search strategy for a pattern, based on the pattern and a profile of
+//! the text to search in.
+
+use crate::WORD_SIZE_IN_BITS;
+
+const INDEX_MIN_QUERIES: usize = 8;
+const PREFILTER_MIN_SIZE: usize = 1 << 20;
+const PREFILTER_CHUNK_SIZE: usize = 1 << 16;
+const COMMON_BYTE_FREQUENCY: f64 = 0.05;
+
+/// The `HaystackProfile` struct describes the text that will be searched.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HaystackProfile {
+    /// The size of the text in bytes.
+    pub size: usize,
+
+    /// The number of patterns that will be searched for in the same text.
+    pub queries: usize,
+
+    /// The relative frequency of every byte value in the text, if known.
+    pub byte_frequencies: Option<[f64; 256]>
+}
+
+/// The search strategy that is recommended for a pattern.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Strategy {
+    /// Scan for the single byte of the pattern, as done by `find_pattern` for patterns of one
+    /// byte.
+    ByteScan,
+
+    /// Scan the text with BNDM.
+    Bndm,
+
+    /// Build a `HaystackIndex` of the text once and query it for every pattern.
+    HaystackIndex
+}
+
+/// A property of a pattern that affects the performance of the search.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Warning {
+    /// The pattern is empty and never matches.
+    EmptyPattern,
+
+    /// The pattern consists of wildcards only and matches at every position.
+    AllWildcards,
+
+    /// The pattern starts with wildcards. Every shift of the window is limited to the given
+    /// number of bytes, which is the length of the part of the pattern that is used to skip
+    /// through the text minus the number of leading wildcards, and at least one.
+    WildcardPrefix(usize),
+
+    /// More than half of the pattern consists of wildcards.
+    HighWildcardDensity,
+
+    /// All literal bytes of the pattern are common in the text.
+    CommonBytes,
+
+    /// The pattern is longer than the word size; only the first part of the pattern is used
+    /// to skip through the text.
+    PatternExceedsWord
+}
+
+/// The `Recommendation` struct holds the recommended search configuration for a pattern.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Recommendation {
+    /// The recommended search strategy.
+    pub strategy: Strategy,
+
+    /// The chunk size to use with a `ChunkFilter`, if prefiltering the text is recommended.
+    pub prefilter_chunk_size: Option<usize>,
+
+    /// The properties of the pattern that affect the performance of the search.
+    pub warnings: Vec<Warning>
+}
+
+impl HaystackProfile {
+    /// Creates a new `HaystackProfile` instance for a single query over a text of unknown
+    /// byte distribution.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The size of the text in bytes.
+    ///
+    /// # Returns
+    ///
+    /// * `HaystackProfile` - A new `HaystackProfile` instance.
+    pub fn new(size: usize) -> HaystackProfile {
+        HaystackProfile {
+            size,
+            queries: 1,
+            byte_frequencies: None
+        }
+    }
+
+    /// Sets the number of patterns that will be searched for in the same text.
+    pub fn with_queries(mut self, queries: usize) -> HaystackProfile {
+        self.queries = queries;
+        self
+    }
+
+    /// Sets the byte frequencies from a representative sample of the text.
+    pub fn with_sample(mut self, sample: &[u8]) -> HaystackProfile {
+        if !sample.is_empty() {
+            let mut counts = [0usize; 256];
+            sample.iter().for_each(|&byte| counts[byte as usize] += 1);
+            self.byte_frequencies = Some(counts.map(|count| count as f64 / sample.len() as f64));
+        }
+        self
+    }
+
+    fn byte_frequency(&self, byte: u8) -> f64 {
+        match &self.byte_frequencies {
+            Some(frequencies) => frequencies[byte as usize],
+            None => typical_byte_frequency(byte)
+        }
+    }
+}
+
+// The frequency of a byte in typical text and binary data, used when no sample is available.
+pub(crate) fn typical_byte_frequency(byte: u8) -> f64 {
+    match byte {
+        0x00 | 0xff | b' ' => 0.1,
+        b'a'..=b'z' => 0.03,
+        b'0'..=b'9' | b'A'..=b'Z' | b'\t' | b'\n' | b'\r' | b'.' | b',' => 0.01,
+        _ => 0.002
+    }
+}
+
+/// Recommends a search strategy for the pattern, based on the length of the pattern, the
+/// density of wildcards and the rarity of its bytes in the text.
+///
+/// # Arguments
+///
+/// * `pattern` - The pattern to search for in the text.
+/// * `wildcard` - An optional wildcard character. If provided, this character in the pattern
+///   can match any character in the text.
+/// * `profile` - The profile of the text to search in.
+///
+/// # Returns
+///
+/// * `Recommendation` - The recommended search configuration.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{HaystackProfile, Strategy, Warning, advise};
+///
+/// let profile = HaystackProfile::new(1 << 30);
+/// let recommendation = advise(b"\xca\xfe\xba\xbe", None, &profile);
+/// assert_eq!(recommendation.strategy, Strategy::Bndm);
+/// assert_eq!(recommendation.prefilter_chunk_size, Some(65536));
+///
+/// let recommendation = advise(b"??ll? w?rld", Some(b'?'), &profile.with_queries(100));
+/// assert_eq!(recommendation.strategy, Strategy::HaystackIndex);
+/// assert_eq!(recommendation.warnings, vec![Warning::WildcardPrefix(9)]);
+/// ```
+pub fn advise(pattern: &[u8], wildcard: Option<u8>, profile: &HaystackProfile) -> Recommendation {
+    let is_literal = |byte: &u8| wildcard != Some(*byte);
+    let literal_count = pattern.iter().filter(|byte| is_literal(byte)).count();
+    let mut warnings = Vec::new();
+
+    if pattern.is_empty() {
+        warnings.push(Warning::EmptyPattern);
+    } else if literal_count == 0 {
+        warnings.push(Warning::AllWildcards);
+    } else {
+        let prefix_len = pattern.iter().take_while(|byte| !is_literal(byte)).count();
+        if prefix_len > 0 {
+            let max_shift = pattern.len().min(WORD_SIZE_IN_BITS).saturating_sub(prefix_len).max(1);
+            warnings.push(Warning::WildcardPrefix(max_shift));
+        }
+        if literal_count * 2 < pattern.len() {
+            warnings.push(Warning::HighWildcardDensity);
+        }
+        if pattern.iter().filter(|byte| is_literal(byte)).all(|&byte| profile.byte_frequency(byte) >= COMMON_BYTE_FREQUENCY) {
+            warnings.push(Warning::CommonBytes);
+        }
+    }
+    if pattern.len() > WORD_SIZE_IN_BITS {
+        warnings.push(Warning::PatternExceedsWord);
+    }
+
+    let has_literal_trigram = pattern.windows(3).any(|gram| gram.iter().all(is_literal));
+    let strategy = match pattern.len() {
+        1 if literal_count == 1 => Strategy::ByteScan,
+        _ if has_literal_trigram && profile.queries >= INDEX_MIN_QUERIES => Strategy::HaystackIndex,
+        _ => Strategy::Bndm
+    };
+
+    let rarest_bigram_frequency = pattern.windows(2)
+        .filter(|gram| gram.iter().all(is_literal))
+        .map(|gram| profile.byte_frequency(gram[0]) * profile.byte_frequency(gram[1]))
+        .reduce(f64::min);
+    let is_rare_in_chunk = rarest_bigram_frequency.is_some_and(|frequency| frequency * (PREFILTER_CHUNK_SIZE as f64) < 1.0);
+    let prefilter_chunk_size = (strategy == Strategy::Bndm && profile.size >= PREFILTER_MIN_SIZE && is_rare_in_chunk)
+        .then_some(PREFILTER_CHUNK_SIZE);
+
+    Recommendation { strategy, prefilter_chunk_size, warnings }
+}
+
+#[cfg(test)]
+#[path = "./advisor_test.rs"]
+mod advisor_test;
@@ -0,0 +1,86 @@
+use super::*;
+
+#[test]
+fn advise_empty_pattern() {
+    let recommendation = advise(b"", None, &HaystackProfile::new(100));
+
+    assert_eq!(recommendation.strategy, Strategy::Bndm);
+    assert_eq!(recommendation.warnings, vec![Warning::EmptyPattern]);
+}
+
+#[test]
+fn advise_single_byte() {
+    let recommendation = advise(b"x", None, &HaystackProfile::new(100));
+
+    assert_eq!(recommendation.strategy, Strategy::ByteScan);
+    assert_eq!(recommendation.prefilter_chunk_size, None);
+    assert!(recommendation.warnings.is_empty());
+}
+
+#[test]
+fn advise_all_wildcards() {
+    let recommendation = advise(b"???", Some(b'?'), &HaystackProfile::new(100));
+
+    assert_eq!(recommendation.strategy, Strategy::Bndm);
+    assert_eq!(recommendation.warnings, vec![Warning::AllWildcards]);
+}
+
+#[test]
+fn advise_wildcard_density() {
+    let recommendation = advise(b"a??b?", Some(b'?'), &HaystackProfile::new(100));
+
+    assert_eq!(recommendation.warnings, vec![Warning::HighWildcardDensity]);
+}
+
+#[test]
+fn advise_common_bytes_from_sample() {
+    let profile = HaystackProfile::new(1 << 30).with_sample(b"aaaabbbbccccdddd");
+    let recommendation = advise(b"abcd", None, &profile);
+
+    assert_eq!(recommendation.warnings, vec![Warning::CommonBytes]);
+    assert_eq!(recommendation.prefilter_chunk_size, None);
+}
+
+#[test]
+fn advise_rare_bytes_from_sample() {
+    let profile = HaystackProfile::new(1 << 30).with_sample(b"aaaabbbbccccdddd");
+    let recommendation = advise(b"xyz", None, &profile);
+
+    assert!(recommendation.warnings.is_empty());
+    assert_eq!(recommendation.prefilter_chunk_size, Some(PREFILTER_CHUNK_SIZE));
+}
+
+#[test]
+fn advise_no_prefilter_for_small_haystack() {
+    let recommendation = advise(b"\x4d\x5a\x90\x00", None, &HaystackProfile::new(1000));
+
+    assert_eq!(recommendation.prefilter_chunk_size, None);
+}
+
+#[test]
+fn advise_index_requires_literal_trigram() {
+    let profile = HaystackProfile::new(1000).with_queries(100);
+
+    assert_eq!(advise(b"abc", None, &profile).strategy, Strategy::HaystackIndex);
+    assert_eq!(advise(b"ab?cd", Some(b'?'), &profile).strategy, Strategy::Bndm);
+}
+
+#[test]
+fn advise_long_pattern() {
+    let recommendation = advise(&[b'x'; 65], None, &HaystackProfile::new(100));
+
+    assert_eq!(recommendation.warnings, vec![Warning::PatternExceedsWord]);
+}
+
+#[test]
+fn advise_wildcard_prefix() {
+    let profile = HaystackProfile::new(100);
+
+    assert_eq!(advise(b"?abcdef", Some(b'?'), &profile).warnings, vec![Warning::WildcardPrefix(6)]);
+    assert_eq!(advise(b"??abcd", Some(b'?'), &profile).warnings, vec![Warning::WildcardPrefix(4)]);
+
+    let mut pattern = [b'?'; 70];
+    pattern[69] = b'x';
+    assert_eq!(advise(&pattern, Some(b'?'), &profile).warnings,
+        vec![Warning::WildcardPrefix(1), Warning::HighWildcardDensity, Warning::PatternExceedsWord]);
+}
@@ -0,0 +1,314 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! A zero-copy archive of compiled configurations, for loading large signature sets from
+//! memory-mapped files without deserializing them.
+
+use std::error::Error;
+use std::fmt;
+use std::mem::{align_of, size_of};
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::masks::validate_view;
+use crate::{BndmConfig, ByteClass, Equivalence, MASKS_TABLE_SIZE, MaskTableError, PatternView, find_first_in_view};
+
+const MAGIC: &[u8; 8] = b"BNDMARC\0";
+const HEADER_SIZE: usize = 24;
+const RECORD_HEADER_SIZE: usize = 16;
+const ALIGNMENT: usize = 8;
+const MASKS_SIZE: usize = MASKS_TABLE_SIZE * size_of::<usize>();
+const CLASS_SIZE: usize = size_of::<ByteClass>();
+const EQUIVALENCE_SIZE: usize = size_of::<Equivalence>();
+
+const FLAG_WILDCARD: u64 = 1;
+const FLAG_EQUIVALENCE: u64 = 2;
+const FLAG_CLASSES: u64 = 4;
+
+/// The error type for opening archives.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ArchiveError {
+    /// The bytes do not start with the header of an archive.
+    InvalidHeader,
+
+    /// The archive was written on a target with a different word size or byte order.
+    IncompatibleTarget,
+
+    /// The bytes are not aligned to 8 bytes.
+    Misaligned,
+
+    /// The archive is truncated or contains a record that is out of bounds.
+    Truncated,
+
+    /// The bitmasks of the configuration at the given index can't be used to scan a text, or
+    /// do not agree with its pattern.
+    InvalidMasks(usize, MaskTableError)
+}
+
+impl fmt::Display for ArchiveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArchiveError::InvalidHeader => write!(f, "invalid archive header"),
+            ArchiveError::IncompatibleTarget => write!(f, "archive was written for a different word size or byte order"),
+            ArchiveError::Misaligned => write!(f, "archive is not aligned to {ALIGNMENT} bytes"),
+            ArchiveError::Truncated => write!(f, "archive is truncated"),
+            ArchiveError::InvalidMasks(index, error) => write!(f, "invalid bitmasks of configuration {index}: {error}")
+        }
+    }
+}
+
+impl Error for ArchiveError {}
+
+/// Writes the configurations into an archive that can be opened with `ConfigArchive::new`.
+///
+/// The archive stores the bitmasks in the native word size and byte order, so it can only be
+/// opened on a target with the same word size and byte order.
+///
+/// # Arguments
+///
+/// * `configs` - The configurations to write into the archive.
+///
+/// # Returns
+///
+/// * `ArchiveBuffer` - The bytes of the archive, aligned to 8 bytes.
+pub fn write_archive(configs: &[BndmConfig]) -> ArchiveBuffer {
+    let mut archive = Vec::new();
+    archive.extend_from_slice(MAGIC);
+    archive.extend_from_slice(&[size_of::<usize>() as u8, cfg!(target_endian = "little") as u8, 0, 0, 0, 0, 0, 0]);
+    archive.extend_from_slice(&(configs.len() as u64).to_ne_bytes());
+
+    let offsets_start = archive.len();
+    archive.resize(offsets_start + configs.len() * size_of::<u64>(), 0);
+
+    for (index, config) in configs.iter().enumerate() {
+        let offset = archive.len() as u64;
+        archive[offsets_start + index * 8..offsets_start + index * 8 + 8].copy_from_slice(&offset.to_ne_bytes());
+
+        config.masks.iter().for_each(|mask| archive.extend_from_slice(&mask.to_ne_bytes()));
+        let flags = config.wildcard.map_or(0, |wildcard| FLAG_WILDCARD | (wildcard as u64) << 8)
+            | config.equivalence.as_ref().map_or(0, |_| FLAG_EQUIVALENCE)
+            | config.classes.as_ref().map_or(0, |_| FLAG_CLASSES);
+        archive.extend_from_slice(&(config.pattern.len() as u64).to_ne_bytes());
+        archive.extend_from_slice(&flags.to_ne_bytes());
+
+        if let Some(classes) = &config.classes {
+            classes.iter().for_each(|class| archive.extend(class.bits().iter().flat_map(|bits| bits.to_ne_bytes())));
+        }
+        if let Some(equivalence) = &config.equivalence {
+            archive.extend((0..=u8::MAX).map(|byte| equivalence.fold(byte)));
+        }
+        archive.extend_from_slice(&config.pattern);
+        archive.resize(archive.len().next_multiple_of(ALIGNMENT), 0);
+    }
+
+    ArchiveBuffer {
+        words: archive.chunks(ALIGNMENT).map(|word| u64::from_ne_bytes(word.try_into().unwrap())).collect()
+    }
+}
+
+/// The bytes of an archive written by `write_archive`, in a buffer that is aligned to 8 bytes
+/// so that it can be opened with `ConfigArchive::new` directly.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArchiveBuffer {
+    words: Vec<u64>
+}
+
+impl Deref for ArchiveBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // SAFETY: the words are initialized and every byte of a u64 is a valid u8.
+        unsafe { std::slice::from_raw_parts(self.words.as_ptr().cast::<u8>(), self.words.len() * size_of::<u64>()) }
+    }
+}
+
+/// The `ConfigArchive` struct gives access to the configurations of an archive without
+/// copying them.
+///
+/// The archive is validated once when it is opened. Every configuration refers to the
+/// bitmasks and pattern in the bytes of the archive, which is typically a memory-mapped file.
+#[derive(Clone, Copy, Debug)]
+pub struct ConfigArchive<'a> {
+    bytes: &'a [u8],
+    count: usize
+}
+
+impl<'a> ConfigArchive<'a> {
+    /// Opens an archive that was written by `write_archive`.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes of the archive. They should be aligned to 8 bytes, which holds
+    ///   for memory-mapped files and for the buffer returned by `write_archive`.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<ConfigArchive, ArchiveError>` - Returns the archive, or an error if the bytes
+    ///   are not a valid archive for this target, or contain bitmasks that do not fit the
+    ///   pattern of their configuration.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{BndmConfig, ConfigArchive, write_archive};
+    ///
+    /// let archive = write_archive(&[BndmConfig::new(b"fox", None), BndmConfig::new(b"ju??s", Some(b'?'))]);
+    ///
+    /// let archive = ConfigArchive::new(&archive).unwrap();
+    /// assert_eq!(archive.len(), 2);
+    /// assert_eq!(archive.get(1).unwrap().find(b"The quick brown fox jumps"), Some(20));
+    /// ```
+    pub fn new(bytes: &'a [u8]) -> Result<ConfigArchive<'a>, ArchiveError> {
+        if bytes.as_ptr().align_offset(ALIGNMENT) != 0 {
+            return Err(ArchiveError::Misaligned);
+        }
+        if bytes.len() < HEADER_SIZE || &bytes[..8] != MAGIC {
+            return Err(ArchiveError::InvalidHeader);
+        }
+        if bytes[8] as usize != size_of::<usize>() || bytes[9] != cfg!(target_endian = "little") as u8 {
+            return Err(ArchiveError::IncompatibleTarget);
+        }
+
+        let count = usize::try_from(read_u64(bytes, 16).ok_or(ArchiveError::Truncated)?).map_err(|_| ArchiveError::Truncated)?;
+        let offsets_end = count.checked_mul(8).and_then(|size| size.checked_add(HEADER_SIZE)).ok_or(ArchiveError::Truncated)?;
+        if offsets_end > bytes.len() {
+            return Err(ArchiveError::Truncated);
+        }
+
+        let archive = ConfigArchive { bytes, count };
+        for index in 0..count {
+            let config = archive.record(index).ok_or(ArchiveError::Truncated)?;
+            validate_view(&config.view()).map_err(|error| ArchiveError::InvalidMasks(index, error))?;
+        }
+        Ok(archive)
+    }
+
+    /// Returns the number of configurations in the archive.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Returns whether the archive contains no configurations.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Returns the configuration at the given index, or `None` if the index is out of bounds.
+    pub fn get(&self, index: usize) -> Option<ArchivedConfig<'a>> {
+        (index < self.count).then(|| self.record(index)).flatten()
+    }
+
+    /// Returns an iterator over the configurations of the archive.
+    pub fn iter(&self) -> impl Iterator<Item = ArchivedConfig<'a>> + '_ {
+        (0..self.count).filter_map(|index| self.record(index))
+    }
+
+    fn record(&self, index: usize) -> Option<ArchivedConfig<'a>> {
+        let bytes = self.bytes;
+        let offset = usize::try_from(read_u64(bytes, HEADER_SIZE + index * 8)?).ok()?;
+        if !offset.is_multiple_of(ALIGNMENT) {
+            return None;
+        }
+
+        let masks = bytes.get(offset..offset.checked_add(MASKS_SIZE)?)?;
+        let header = offset + MASKS_SIZE;
+        let pattern_len = usize::try_from(read_u64(bytes, header)?).ok()?;
+        let flags = read_u64(bytes, header + 8)?;
+
+        let mut position = header + RECORD_HEADER_SIZE;
+        let classes = match flags & FLAG_CLASSES {
+            0 => None,
+            _ => {
+                let classes = bytes.get(position..position.checked_add(pattern_len.checked_mul(CLASS_SIZE)?)?)?;
+                position += classes.len();
+                Some(cast_slice::<ByteClass>(classes))
+            }
+        };
+        let equivalence = match flags & FLAG_EQUIVALENCE {
+            0 => None,
+            _ => {
+                let equivalence = bytes.get(position..position + EQUIVALENCE_SIZE)?;
+                position += EQUIVALENCE_SIZE;
+                cast_slice::<Equivalence>(equivalence).first()
+            }
+        };
+        let pattern = bytes.get(position..position.checked_add(pattern_len)?)?;
+
+        Some(ArchivedConfig {
+            masks: cast_slice::<[usize; MASKS_TABLE_SIZE]>(masks).first()?,
+            wildcard: (flags & FLAG_WILDCARD != 0).then_some((flags >> 8) as u8),
+            pattern,
+            equivalence,
+            classes
+        })
+    }
+}
+
+/// A configuration in a `ConfigArchive`, which refers to the bytes of the archive.
+#[derive(Clone, Copy, Debug)]
+pub struct ArchivedConfig<'a> {
+    masks: &'a [usize; MASKS_TABLE_SIZE],
+    wildcard: Option<u8>,
+    pattern: &'a [u8],
+    equivalence: Option<&'a Equivalence>,
+    classes: Option<&'a [ByteClass]>
+}
+
+impl ArchivedConfig<'_> {
+    /// Returns the pattern.
+    pub fn pattern(&self) -> &[u8] {
+        self.pattern
+    }
+
+    /// Returns the wildcard character.
+    pub fn wildcard(&self) -> Option<u8> {
+        self.wildcard
+    }
+
+    /// Returns the index of the first occurrence of the pattern in the source, or `None` if
+    /// the pattern is not found.
+    pub fn find(&self, source: &[u8]) -> Option<usize> {
+        find_first_in_view(source, &self.view())
+    }
+
+    /// Returns a `BndmConfig` instance with a copy of the configuration, for use with the
+    /// other search functions of the crate.
+    pub fn to_config(&self) -> BndmConfig {
+        BndmConfig {
+            masks: Arc::new(*self.masks),
+            wildcard: self.wildcard,
+            pattern: self.pattern.to_vec(),
+            equivalence: self.equivalence.cloned(),
+            classes: self.classes.map(<[ByteClass]>::to_vec)
+        }
+    }
+
+    fn view(&self) -> PatternView<'_> {
+        PatternView {
+            masks: self.masks,
+            wildcard: self.wildcard,
+            pattern: self.pattern,
+            equivalence: self.equivalence,
+            classes: self.classes
+        }
+    }
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> Option<u64> {
+    bytes.get(offset..offset.checked_add(8)?).map(|bytes| u64::from_ne_bytes(bytes.try_into().unwrap()))
+}
+
+/// Reinterprets the bytes as a slice of `T`, which must be a type for which every bit pattern
+/// is valid. Returns an empty slice if the bytes are not aligned for `T`.
+pub(crate) fn cast_slice<T>(bytes: &[u8]) -> &[T] {
+    if bytes.as_ptr().align_offset(align_of::<T>()) != 0 {
+        return &[];
+    }
+    // SAFETY: the bytes are aligned for `T`, the length is rounded down to whole elements, and
+    // the types this is used with consist of integers only, for which every bit pattern is valid.
+    unsafe { std::slice::from_raw_parts(bytes.as_ptr().cast::<T>(), bytes.len() / size_of::<T>()) }
+}
+
+#[cfg(test)]
+#[path = "./archive_test.rs"]
+mod archive_test;
@@ -0,0 +1,83 @@
+use super::*;
+use crate::{PatternBuilder, find_pattern};
+
+fn aligned(bytes: &[u8]) -> Vec<u64> {
+    bytes.chunks(8).map(|chunk| {
+        let mut word = [0; 8];
+        word[..chunk.len()].copy_from_slice(chunk);
+        u64::from_ne_bytes(word)
+    }).collect()
+}
+
+fn as_bytes(words: &[u64], len: usize) -> &[u8] {
+    // SAFETY: every bit pattern of a u64 is a valid sequence of bytes.
+    unsafe { std::slice::from_raw_parts(words.as_ptr().cast::<u8>(), len) }
+}
+
+#[test]
+fn archive_round_trip() {
+    let configs = vec![
+        BndmConfig::new(b"fox", None),
+        BndmConfig::new(b"ju??s", Some(b'?')),
+        BndmConfig::new_nocase(b"LAZY", None),
+        PatternBuilder::new().literal(b"d").range(b'n'..=b'o').any().build(),
+        BndmConfig::new(&[b'a'; 70], None)
+    ];
+    let buffer = write_archive(&configs);
+    let archive = ConfigArchive::new(&buffer).unwrap();
+
+    assert_eq!(archive.len(), configs.len());
+    for (archived, config) in archive.iter().zip(&configs) {
+        assert_eq!(archived.to_config(), *config);
+        assert_eq!(archived.pattern(), config.pattern);
+        assert_eq!(archived.wildcard(), config.wildcard);
+    }
+
+    let source = b"The quick brown fox jumps over the lazy dog";
+    for (index, config) in configs.iter().enumerate() {
+        assert_eq!(archive.get(index).unwrap().find(source), find_pattern(source, config));
+    }
+    assert!(archive.get(configs.len()).is_none());
+}
+
+#[test]
+fn empty_archive() {
+    let buffer = write_archive(&[]);
+    let archive = ConfigArchive::new(&buffer).unwrap();
+
+    assert!(archive.is_empty());
+    assert_eq!(archive.iter().count(), 0);
+}
+
+#[test]
+fn invalid_archives() {
+    let bytes = write_archive(&[BndmConfig::new(b"fox", None)]);
+    let words = aligned(&bytes);
+    let archive = as_bytes(&words, bytes.len());
+
+    assert_eq!(ConfigArchive::new(&archive[1..]).err(), Some(ArchiveError::Misaligned));
+    assert_eq!(ConfigArchive::new(&archive[..16]).err(), Some(ArchiveError::InvalidHeader));
+    assert_eq!(ConfigArchive::new(&archive[..archive.len() - 8]).err(), Some(ArchiveError::Truncated));
+    assert_eq!(ConfigArchive::new(&archive[..HEADER_SIZE]).err(), Some(ArchiveError::Truncated));
+
+    let mut words = words.clone();
+    words[1] ^= 0xff;
+    assert_eq!(ConfigArchive::new(as_bytes(&words, bytes.len())).err(), Some(ArchiveError::IncompatibleTarget));
+
+    let mut words = aligned(&bytes);
+    words[0] ^= 1;
+    assert_eq!(ConfigArchive::new(as_bytes(&words, bytes.len())).err(), Some(ArchiveError::InvalidHeader));
+    assert_eq!(ArchiveError::Truncated.to_string(), "archive is truncated");
+}
+
+#[test]
+fn invalid_masks() {
+    let mut bytes = write_archive(&[BndmConfig::new(b"fox", None), BndmConfig::new(b"ab", None)]).to_vec();
+    let offset = usize::try_from(read_u64(&bytes, HEADER_SIZE + 8).unwrap()).unwrap();
+    bytes[offset..offset + MASKS_SIZE].chunks_mut(size_of::<usize>())
+        .for_each(|mask| mask.copy_from_slice(&(!2usize).to_ne_bytes()));
+    let words = aligned(&bytes);
+
+    assert_eq!(ConfigArchive::new(as_bytes(&words, bytes.len())).err(),
+        Some(ArchiveError::InvalidMasks(1, MaskTableError::UnusedBits(0))));
+}
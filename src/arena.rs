@@ -0,0 +1,159 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Bulk compilation of many patterns into shared buffers, for loading large signature sets.
+
+use std::ops::Range;
+
+use crate::{MASKS_TABLE_SIZE, PatternView, find_first_in_view, generate_masks, get_pattern_length_within_cpu_word};
+
+/// The `ConfigArena` struct stores many compiled patterns in two contiguous buffers, one for
+/// the mask tables and one for the pattern bytes.
+///
+/// Compiling a pattern into the arena does not allocate per pattern, which reduces the
+/// pressure on the allocator and improves the locality when loading hundreds of thousands of
+/// signatures. Patterns are referred to by lightweight `ConfigHandle` values.
+#[derive(Clone, Debug, Default)]
+pub struct ConfigArena {
+    masks: Vec<[usize; MASKS_TABLE_SIZE]>,
+    patterns: Vec<u8>,
+    entries: Vec<ArenaEntry>
+}
+
+/// A handle to a pattern compiled into a `ConfigArena`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ConfigHandle(usize);
+
+#[derive(Clone, Debug)]
+struct ArenaEntry {
+    pattern: Range<usize>,
+    wildcard: Option<u8>
+}
+
+impl ConfigArena {
+    /// Creates a new, empty `ConfigArena` instance.
+    pub fn new() -> ConfigArena {
+        ConfigArena::default()
+    }
+
+    /// Creates a new, empty `ConfigArena` instance with room for the given number of patterns
+    /// and pattern bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `patterns` - The number of patterns to reserve room for.
+    /// * `pattern_bytes` - The total length of the patterns to reserve room for.
+    ///
+    /// # Returns
+    ///
+    /// * `ConfigArena` - A new `ConfigArena` instance.
+    pub fn with_capacity(patterns: usize, pattern_bytes: usize) -> ConfigArena {
+        ConfigArena {
+            masks: Vec::with_capacity(patterns),
+            patterns: Vec::with_capacity(pattern_bytes),
+            entries: Vec::with_capacity(patterns)
+        }
+    }
+
+    /// Compiles the pattern into the arena.
+    ///
+    /// # Arguments
+    ///
+    /// * `search_pattern` - The pattern to search for in the text.
+    /// * `wildcard` - An optional wildcard character. If provided, this character in the pattern
+    ///   can match any character in the text.
+    ///
+    /// # Returns
+    ///
+    /// * `ConfigHandle` - The handle to refer to the compiled pattern.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::ConfigArena;
+    ///
+    /// let mut arena = ConfigArena::with_capacity(2, 10);
+    /// let fox = arena.push(b"fox", None);
+    /// let jumps = arena.push(b"ju??s", Some(b'?'));
+    ///
+    /// let source = b"The quick brown fox jumps over the lazy dog";
+    /// assert_eq!(arena.find(jumps, source), Some(20));
+    /// assert_eq!(arena.find_first_of_all(source), vec![(fox, 16), (jumps, 20)]);
+    /// ```
+    pub fn push(&mut self, search_pattern: &[u8], wildcard: Option<u8>) -> ConfigHandle {
+        let len = get_pattern_length_within_cpu_word(search_pattern.len());
+        let start = self.patterns.len();
+
+        self.masks.push(generate_masks(&search_pattern[..len], wildcard));
+        self.patterns.extend_from_slice(search_pattern);
+        self.entries.push(ArenaEntry { pattern: start..self.patterns.len(), wildcard });
+
+        ConfigHandle(self.entries.len() - 1)
+    }
+
+    /// Returns the number of patterns in the arena.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether the arena contains no patterns.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the handles of all patterns in the arena, in the order they were added.
+    pub fn handles(&self) -> impl Iterator<Item = ConfigHandle> {
+        (0..self.entries.len()).map(ConfigHandle)
+    }
+
+    /// Returns the pattern of the handle.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the handle does not belong to this arena.
+    pub fn pattern(&self, handle: ConfigHandle) -> &[u8] {
+        &self.patterns[self.entries[handle.0].pattern.clone()]
+    }
+
+    /// Returns the wildcard character of the pattern of the handle.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the handle does not belong to this arena.
+    pub fn wildcard(&self, handle: ConfigHandle) -> Option<u8> {
+        self.entries[handle.0].wildcard
+    }
+
+    /// Returns the index of the first occurrence of the pattern of the handle in the source
+    /// string, or `None` if the pattern is not found.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the handle does not belong to this arena.
+    pub fn find(&self, handle: ConfigHandle, source: &[u8]) -> Option<usize> {
+        find_first_in_view(source, &self.view(handle))
+    }
+
+    /// Returns, for every pattern that occurs in the source string, the handle of the pattern
+    /// and the index of its first occurrence, in the order the patterns were added.
+    pub fn find_first_of_all(&self, source: &[u8]) -> Vec<(ConfigHandle, usize)> {
+        self.handles()
+            .filter_map(|handle| self.find(handle, source).map(|index| (handle, index)))
+            .collect()
+    }
+
+    fn view(&self, handle: ConfigHandle) -> PatternView<'_> {
+        let entry = &self.entries[handle.0];
+        PatternView {
+            masks: &self.masks[handle.0],
+            wildcard: entry.wildcard,
+            pattern: &self.patterns[entry.pattern.clone()],
+            equivalence: None,
+            classes: None
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "./arena_test.rs"]
+mod arena_test;
@@ -0,0 +1,64 @@
+use super::*;
+use crate::{BndmConfig, find_pattern};
+
+#[test]
+fn empty_arena() {
+    let arena = ConfigArena::new();
+
+    assert!(arena.is_empty());
+    assert_eq!(arena.len(), 0);
+    assert_eq!(arena.find_first_of_all(b"abc"), vec![]);
+}
+
+#[test]
+fn push_stores_patterns() {
+    let mut arena = ConfigArena::new();
+    let first = arena.push(b"abc", None);
+    let second = arena.push(b"", None);
+    let third = arena.push(b"d?f", Some(b'?'));
+
+    assert_eq!(arena.len(), 3);
+    assert_eq!(arena.handles().collect::<Vec<_>>(), vec![first, second, third]);
+    assert_eq!(arena.pattern(first), b"abc");
+    assert_eq!(arena.pattern(second), b"");
+    assert_eq!(arena.pattern(third), b"d?f");
+    assert_eq!(arena.wildcard(third), Some(b'?'));
+}
+
+#[test]
+fn find_matches_config() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let patterns: [(&[u8], Option<u8>); 6] = [
+        (b"fox", None), (b"?he", Some(b'?')), (b"cat", None), (b"", None), (b"o", None), (b"?", Some(b'?'))
+    ];
+    let mut arena = ConfigArena::new();
+    let handles: Vec<ConfigHandle> = patterns.iter().map(|&(pattern, wildcard)| arena.push(pattern, wildcard)).collect();
+
+    for (&(pattern, wildcard), &handle) in patterns.iter().zip(&handles) {
+        assert_eq!(arena.find(handle, source), find_pattern(source, &BndmConfig::new(pattern, wildcard)));
+    }
+}
+
+#[test]
+fn find_long_pattern() {
+    let pattern: Vec<u8> = (0..100).collect();
+    let mut source = vec![0xff; 10];
+    source.extend(&pattern);
+    let mut arena = ConfigArena::new();
+    let handle = arena.push(&pattern, None);
+
+    assert_eq!(arena.find(handle, &source), Some(10));
+
+    source[105] = 0xff;
+    assert_eq!(arena.find(handle, &source), None);
+}
+
+#[test]
+fn find_first_of_all_skips_missing_patterns() {
+    let mut arena = ConfigArena::new();
+    let dog = arena.push(b"dog", None);
+    arena.push(b"cat", None);
+    let the = arena.push(b"the", None);
+
+    assert_eq!(arena.find_first_of_all(b"The quick brown fox jumps over the lazy dog"), vec![(dog, 40), (the, 31)]);
+}
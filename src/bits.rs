@@ -0,0 +1,230 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Search for bit patterns at any bit offset, e.g. in radio captures, compressed streams and
+//! FPGA bitstreams.
+
+use std::sync::Arc;
+
+use crate::{BndmConfig, MASKS_TABLE_SIZE, WORD_SIZE_IN_BITS, find_overlapping_indexes};
+
+/// The order in which the bits of a byte are numbered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BitOrder {
+    /// Bits are numbered from the most significant bit of a byte, so bit offset `n` of a
+    /// buffer is bit `7 - n % 8` of byte `n / 8`.
+    MsbFirst,
+
+    /// Bits are numbered from the least significant bit of a byte, so bit offset `n` of a
+    /// buffer is bit `n % 8` of byte `n / 8`, as in DEFLATE and GIF streams.
+    LsbFirst
+}
+
+impl BitOrder {
+    fn bit_mask(self, position: usize) -> u8 {
+        match self {
+            BitOrder::MsbFirst => 0x80 >> (position % 8),
+            BitOrder::LsbFirst => 1 << (position % 8)
+        }
+    }
+}
+
+/// The `BitPattern` struct holds a sequence of bits to search for at any bit offset.
+///
+/// By default, bits are numbered from the most significant bit of the first byte, see
+/// `BitOrder::MsbFirst`. The pattern is searched for as 8 byte patterns,
+/// one for every bit offset within a byte, in which the partial first and last bytes only
+/// match on the bits that belong to the pattern.
+pub struct BitPattern {
+    bit_len: usize,
+    shifts: Vec<ShiftedPattern>
+}
+
+struct ShiftedPattern {
+    shift: usize,
+    config: BndmConfig,
+    bit_masks: Vec<u8>,
+    values: Vec<u8>
+}
+
+impl BitPattern {
+    /// Creates a new `BitPattern` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes that hold the bits of the pattern, starting at the most
+    ///   significant bit of the first byte.
+    /// * `bit_len` - The number of bits of the pattern; it is limited to the number of bits in
+    ///   `bytes`.
+    ///
+    /// # Returns
+    ///
+    /// * `BitPattern` - A new `BitPattern` instance.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::BitPattern;
+    ///
+    /// // The bits 1010 1011 1100 1101 shifted right by 3 bits.
+    /// let source = [0x15, 0x79, 0xa0];
+    /// let pattern = BitPattern::new(&[0xab, 0xcd], 16);
+    /// assert_eq!(pattern.find(&source), Some(3));
+    /// ```
+    pub fn new(bytes: &[u8], bit_len: usize) -> BitPattern {
+        BitPattern::with_bit_order(bytes, bit_len, BitOrder::MsbFirst)
+    }
+
+    /// Creates a new `BitPattern` instance in which the bits of the pattern and of the
+    /// searched buffers are numbered in the given order.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes that hold the bits of the pattern, starting at the first bit of
+    ///   the first byte in the given bit order.
+    /// * `bit_len` - The number of bits of the pattern; it is limited to the number of bits in
+    ///   `bytes`.
+    /// * `bit_order` - The order in which the bits of a byte are numbered.
+    ///
+    /// # Returns
+    ///
+    /// * `BitPattern` - A new `BitPattern` instance.
+    pub fn with_bit_order(bytes: &[u8], bit_len: usize, bit_order: BitOrder) -> BitPattern {
+        let bit_len = bit_len.min(bytes.len() * 8);
+        let shifts = if bit_len == 0 {
+            Vec::new()
+        } else {
+            (0..8).map(|shift| ShiftedPattern::new(bytes, bit_len, shift, bit_order)).collect()
+        };
+
+        BitPattern { bit_len, shifts }
+    }
+
+    /// Creates a new `BitPattern` instance for a sequence of symbols that are packed into a
+    /// stream of bits, e.g. 6-bit telemetry samples or 12-bit LZW codes.
+    ///
+    /// With `BitOrder::MsbFirst`, every symbol is packed starting at its most significant bit.
+    /// With `BitOrder::LsbFirst`, every symbol is packed starting at its least significant
+    /// bit, as done by DEFLATE and GIF. The offsets of the occurrences are bit offsets; an
+    /// occurrence is aligned to the symbols of the stream if the offset, relative to the start
+    /// of the stream, is a multiple of the symbol width.
+    ///
+    /// # Arguments
+    ///
+    /// * `symbols` - The symbols of the pattern.
+    /// * `symbol_bits` - The width of every symbol in bits; it is limited to 32 bits.
+    /// * `bit_order` - The order in which the symbols are packed.
+    ///
+    /// # Returns
+    ///
+    /// * `BitPattern` - A new `BitPattern` instance.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{BitOrder, BitPattern};
+    ///
+    /// // The 6-bit symbols 1, 2, 3, 4 packed most significant bit first.
+    /// let source = [0x04, 0x20, 0xc4];
+    /// let pattern = BitPattern::from_symbols(&[2, 3], 6, BitOrder::MsbFirst);
+    /// assert_eq!(pattern.find(&source), Some(6));
+    /// ```
+    pub fn from_symbols(symbols: &[u32], symbol_bits: usize, bit_order: BitOrder) -> BitPattern {
+        let symbol_bits = symbol_bits.min(32);
+        let bit_len = symbols.len() * symbol_bits;
+        let mut bytes = vec![0u8; bit_len.div_ceil(8)];
+
+        for (index, &symbol) in symbols.iter().enumerate() {
+            for bit in 0..symbol_bits {
+                let symbol_bit = match bit_order {
+                    BitOrder::MsbFirst => symbol_bits - 1 - bit,
+                    BitOrder::LsbFirst => bit
+                };
+                if symbol & (1 << symbol_bit) != 0 {
+                    let position = index * symbol_bits + bit;
+                    bytes[position / 8] |= bit_order.bit_mask(position);
+                }
+            }
+        }
+
+        BitPattern::with_bit_order(&bytes, bit_len, bit_order)
+    }
+
+    /// Returns the number of bits of the pattern.
+    pub fn bit_len(&self) -> usize {
+        self.bit_len
+    }
+
+    /// Returns the bit offset of the first occurrence of the pattern in the source, or `None`
+    /// if the pattern is not found.
+    pub fn find(&self, source: &[u8]) -> Option<usize> {
+        self.shifts.iter()
+            .filter_map(|shifted| shifted.find_indexes(source).next().map(|index| index * 8 + shifted.shift))
+            .min()
+    }
+
+    /// Returns the bit offsets of all occurrences of the pattern in the source in ascending
+    /// order. Occurrences may overlap.
+    pub fn find_all(&self, source: &[u8]) -> Vec<usize> {
+        let mut offsets: Vec<usize> = self.shifts.iter()
+            .flat_map(|shifted| shifted.find_indexes(source).map(|index| index * 8 + shifted.shift))
+            .collect();
+        offsets.sort_unstable();
+        offsets
+    }
+}
+
+impl ShiftedPattern {
+    fn new(bytes: &[u8], bit_len: usize, shift: usize, bit_order: BitOrder) -> ShiftedPattern {
+        let len = (shift + bit_len).div_ceil(8);
+        let mut bit_masks = vec![0u8; len];
+        let mut values = vec![0u8; len];
+
+        for bit in 0..bit_len {
+            let position = shift + bit;
+            let bit_mask = bit_order.bit_mask(position);
+            bit_masks[position / 8] |= bit_mask;
+            if bytes[bit / 8] & bit_order.bit_mask(bit) != 0 {
+                values[position / 8] |= bit_mask;
+            }
+        }
+
+        let filter_len = len.min(WORD_SIZE_IN_BITS);
+        let mut masks = [0; MASKS_TABLE_SIZE];
+        for (index, (&bit_mask, &value)) in bit_masks.iter().zip(&values).take(filter_len).enumerate() {
+            masks.iter_mut().enumerate()
+                .filter(|&(byte, _)| byte as u8 & bit_mask == value)
+                .for_each(|(_, mask)| *mask |= 1 << (filter_len - 1 - index));
+        }
+
+        let config = BndmConfig {
+            masks: Arc::new(masks),
+            wildcard: None,
+            pattern: values[..filter_len].to_vec(),
+            equivalence: None,
+            classes: None
+        };
+
+        ShiftedPattern { shift, config, bit_masks, values }
+    }
+
+    fn matches_at(&self, source: &[u8], index: usize, from: usize) -> bool {
+        source.get(index..index + self.values.len()).is_some_and(|window| {
+            window.iter().zip(self.bit_masks.iter().zip(&self.values)).skip(from)
+                .all(|(&byte, (&bit_mask, &value))| byte & bit_mask == value)
+        })
+    }
+
+    fn find_indexes<'a>(&'a self, source: &'a [u8]) -> Box<dyn Iterator<Item = usize> + 'a> {
+        if self.values.len() == 1 {
+            Box::new((0..source.len()).filter(move |&index| self.matches_at(source, index, 0)))
+        } else {
+            Box::new(find_overlapping_indexes(source, &self.config)
+                .filter(move |&index| self.matches_at(source, index, WORD_SIZE_IN_BITS)))
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "./bits_test.rs"]
+mod bits_test;
@@ -0,0 +1,105 @@
+use super::*;
+
+fn shift_right(bytes: &[u8], shift: usize) -> Vec<u8> {
+    let mut shifted = vec![0u8; bytes.len() + 1];
+    for (index, &byte) in bytes.iter().enumerate() {
+        shifted[index] |= byte >> shift;
+        if shift > 0 {
+            shifted[index + 1] |= byte << (8 - shift);
+        }
+    }
+    shifted
+}
+
+#[test]
+fn find_empty_pattern() {
+    let pattern = BitPattern::new(b"", 8);
+
+    assert_eq!(pattern.bit_len(), 0);
+    assert_eq!(pattern.find(b"abc"), None);
+    assert_eq!(pattern.find_all(b"abc"), vec![]);
+}
+
+#[test]
+fn find_at_every_bit_offset() {
+    let pattern = BitPattern::new(&[0xde, 0xad, 0xbe, 0xef], 32);
+
+    for shift in 0..8 {
+        let mut source = vec![0u8; 3];
+        source.extend(shift_right(&[0xde, 0xad, 0xbe, 0xef], shift));
+
+        assert_eq!(pattern.find(&source), Some(24 + shift));
+        assert_eq!(pattern.find_all(&source), vec![24 + shift]);
+    }
+}
+
+#[test]
+fn find_partial_byte_pattern() {
+    let pattern = BitPattern::new(&[0b1010_0000], 3);
+
+    assert_eq!(pattern.find_all(&[0b0101_0100]), vec![1, 3]);
+    assert_eq!(pattern.find_all(&[0b0000_0001, 0b0100_0000]), vec![7]);
+}
+
+#[test]
+fn find_bit_len_limited_to_bytes() {
+    let pattern = BitPattern::new(&[0xff], 100);
+
+    assert_eq!(pattern.bit_len(), 8);
+    assert_eq!(pattern.find_all(&[0x0f, 0xf0]), vec![4]);
+}
+
+#[test]
+fn find_not_found() {
+    let pattern = BitPattern::new(&[0xab, 0xcd], 16);
+
+    assert_eq!(pattern.find(&[0xab, 0xcc, 0xab]), None);
+}
+
+#[test]
+fn find_long_pattern() {
+    let bytes: Vec<u8> = (0..100).map(|index| (index * 37 + 11) as u8).collect();
+    let pattern = BitPattern::new(&bytes, 800);
+    let mut source = vec![0x55; 10];
+    source.extend(shift_right(&bytes, 5));
+
+    assert_eq!(pattern.find(&source), Some(85));
+
+    let last = source.len() - 2;
+    source[last] ^= 0x01;
+    assert_eq!(pattern.find(&source), None);
+}
+
+#[test]
+fn find_lsb_first() {
+    let pattern = BitPattern::with_bit_order(&[0b0000_0101], 3, BitOrder::LsbFirst);
+
+    assert_eq!(pattern.find_all(&[0b0101_0000, 0b0000_0001]), vec![4, 6]);
+}
+
+#[test]
+fn find_symbols_msb_first() {
+    let symbols: Vec<u32> = vec![0x123, 0xabc, 0xfff, 0x001];
+    let pattern = BitPattern::from_symbols(&symbols[1..3], 12, BitOrder::MsbFirst);
+    let source = [0x12, 0x3a, 0xbc, 0xff, 0xf0, 0x01];
+
+    assert_eq!(pattern.bit_len(), 24);
+    assert_eq!(pattern.find_all(&source), vec![12]);
+}
+
+#[test]
+fn find_symbols_lsb_first() {
+    // The 12-bit codes 0x123, 0xabc and 0x001 packed least significant bit first.
+    let source = [0x23, 0xc1, 0xab, 0x01, 0x00];
+    let pattern = BitPattern::from_symbols(&[0xabc, 0x001], 12, BitOrder::LsbFirst);
+
+    assert_eq!(pattern.find(&source), Some(12));
+}
+
+#[test]
+fn find_symbols_width_limited() {
+    let pattern = BitPattern::from_symbols(&[1], 40, BitOrder::MsbFirst);
+
+    assert_eq!(pattern.bit_len(), 32);
+    assert_eq!(pattern.find(&[0x00, 0x00, 0x00, 0x01]), Some(0));
+}
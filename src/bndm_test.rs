@@ -569,3 +569,813 @@ fn find_small_pattern_skip_window_match_longest_suffix_long_pattern() {
 
     assert_eq!(index, Some(41));
 }
+
+#[test]
+fn find_pattern_in_ranges_skip_first_range() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let pattern = b"o";
+    let config = BndmConfig::new(pattern, None);
+    let index = find_pattern_in_ranges(source, &config, &[0..10, 20..30]);
+
+    assert_eq!(index, Some(26));
+}
+
+#[test]
+fn find_pattern_in_ranges_match_crossing_range_end() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let pattern = b"jumps";
+    let config = BndmConfig::new(pattern, None);
+    let index = find_pattern_in_ranges(source, &config, &[0..23, 22..43]);
+
+    assert_eq!(index, None);
+}
+
+#[test]
+fn find_pattern_in_ranges_with_wildcard() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let pattern = b"?he";
+    let config = BndmConfig::new(pattern, Some(b'?'));
+    let index = find_pattern_in_ranges(source, &config, &[10..20, 25..43]);
+
+    assert_eq!(index, Some(31));
+}
+
+#[test]
+fn find_pattern_in_ranges_out_of_bounds() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let pattern = b"dog";
+    let config = BndmConfig::new(pattern, None);
+    let index = find_pattern_in_ranges(source, &config, &[50..60, 35..100]);
+
+    assert_eq!(index, Some(40));
+}
+
+#[test]
+fn find_pattern_in_ranges_empty_ranges() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let pattern = b"?";
+    let config = BndmConfig::new(pattern, Some(b'?'));
+    let index = find_pattern_in_ranges(source, &config, &[5..5, 60..70]);
+
+    assert_eq!(index, None);
+}
+
+#[test]
+fn find_pattern_in_ranges_unsorted() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let pattern = b"o";
+    let config = BndmConfig::new(pattern, None);
+    let index = find_pattern_in_ranges(source, &config, &[25..43, 15..20, 0..5]);
+
+    assert_eq!(index, Some(17));
+}
+
+#[test]
+fn find_all_in_ranges_match() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let pattern = b"o";
+    let config = BndmConfig::new(pattern, None);
+    let indexes = find_all_in_ranges(source, &config, &[25..100, 0..5, 10..20, 15..27]);
+
+    assert_eq!(indexes, vec![12, 17, 26, 41]);
+}
+
+#[test]
+fn find_all_in_ranges_excludes_matches_crossing_range_end() {
+    let source = b"aaaaaaaaaa";
+    let pattern = b"aaa";
+    let config = BndmConfig::new(pattern, None);
+
+    assert_eq!(find_all_in_ranges(source, &config, &[0..4, 2..8]), vec![0, 3]);
+    assert_eq!(find_all_in_ranges(source, &config, &[5..5, 20..30]), vec![]);
+}
+
+#[test]
+fn contains_match() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let pattern = b"j?mps";
+    let config = BndmConfig::new(pattern, Some(b'?'));
+
+    assert!(contains(source, &config));
+}
+
+#[test]
+fn contains_no_match() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+
+    assert!(!contains(source, &BndmConfig::new(b"cat", None)));
+    assert!(!contains(source, &BndmConfig::new(b"", None)));
+    assert!(!contains(b"", &BndmConfig::new(b"a", None)));
+}
+
+#[test]
+fn find_pattern_from_start() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let pattern = b"o";
+    let config = BndmConfig::new(pattern, None);
+
+    assert_eq!(find_pattern_from(source, &config, 0), Some(12));
+}
+
+#[test]
+fn find_pattern_from_absolute_index() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let pattern = b"?o";
+    let config = BndmConfig::new(pattern, Some(b'?'));
+
+    assert_eq!(find_pattern_from(source, &config, 12), Some(16));
+    assert_eq!(find_pattern_from(source, &config, 16), Some(16));
+    assert_eq!(find_pattern_from(source, &config, 41), None);
+}
+
+#[test]
+fn find_pattern_from_beyond_end() {
+    let source = b"abc";
+    let config = BndmConfig::new(b"?", Some(b'?'));
+
+    assert_eq!(find_pattern_from(source, &config, 2), Some(2));
+    assert_eq!(find_pattern_from(source, &config, 3), None);
+    assert_eq!(find_pattern_from(source, &config, 10), None);
+}
+
+#[test]
+fn find_pattern_from_long_pattern() {
+    let pattern = [b'a'; 70];
+    let source = [b'a'; 150];
+    let config = BndmConfig::new(&pattern, None);
+
+    assert_eq!(find_pattern_from(&source, &config, 75), Some(75));
+    assert_eq!(find_pattern_from(&source, &config, 81), None);
+}
+
+#[test]
+fn find_pattern_in_range_match() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let pattern = b"o";
+    let config = BndmConfig::new(pattern, None);
+
+    assert_eq!(find_pattern_in_range(source, &config, 13..30), Some(17));
+}
+
+#[test]
+fn find_pattern_in_range_excludes_partial_match() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let pattern = b"fox";
+    let config = BndmConfig::new(pattern, None);
+
+    assert_eq!(find_pattern_in_range(source, &config, 0..18), None);
+    assert_eq!(find_pattern_in_range(source, &config, 17..30), None);
+    assert_eq!(find_pattern_in_range(source, &config, 16..19), Some(16));
+}
+
+#[test]
+fn find_pattern_in_range_clamped() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let pattern = b"?og";
+    let config = BndmConfig::new(pattern, Some(b'?'));
+
+    assert_eq!(find_pattern_in_range(source, &config, 30..100), Some(40));
+    assert_eq!(find_pattern_in_range(source, &config, 50..100), None);
+}
+
+#[test]
+#[allow(clippy::reversed_empty_ranges)]
+fn find_pattern_in_range_empty() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let pattern = b"T";
+    let config = BndmConfig::new(pattern, None);
+
+    assert_eq!(find_pattern_in_range(source, &config, 0..0), None);
+    assert_eq!(find_pattern_in_range(source, &config, 5..2), None);
+}
+
+#[test]
+fn matches_at_match() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let pattern = b"j??ps";
+    let config = BndmConfig::new(pattern, Some(b'?'));
+
+    assert!(matches_at(source, &config, 20));
+    assert!(!matches_at(source, &config, 19));
+    assert!(!matches_at(source, &config, 21));
+}
+
+#[test]
+fn matches_at_bounds() {
+    let source = b"abc";
+    let config = BndmConfig::new(b"bc", None);
+
+    assert!(matches_at(source, &config, 1));
+    assert!(!matches_at(source, &config, 2));
+    assert!(!matches_at(source, &config, 3));
+    assert!(!matches_at(source, &config, usize::MAX));
+}
+
+#[test]
+fn matches_at_empty_pattern() {
+    let config = BndmConfig::new(b"", None);
+
+    assert!(!matches_at(b"abc", &config, 0));
+}
+
+#[test]
+fn matches_at_long_pattern() {
+    let mut pattern = vec![b'x'; 80];
+    pattern[75] = b'?';
+    let mut source = vec![b'x'; 100];
+    source[85] = b'y';
+    let config = BndmConfig::new(&pattern, Some(b'?'));
+
+    assert!(matches_at(&source, &config, 10));
+    assert!(!matches_at(&source, &config, 9));
+}
+
+#[test]
+fn matches_at_equivalence() {
+    let config = BndmConfig::with_equivalence(b"ab", None, &Equivalence::identity().with_class(b"Bb"));
+
+    assert!(matches_at(b"xaB", &config, 1));
+    assert!(!matches_at(b"xAb", &config, 1));
+}
+
+#[test]
+fn starts_with_pattern() {
+    let config = BndmConfig::new(b"T?e", Some(b'?'));
+
+    assert!(starts_with(b"The quick brown fox", &config));
+    assert!(starts_with(b"Tae", &config));
+    assert!(!starts_with(b" The", &config));
+    assert!(!starts_with(b"Th", &config));
+}
+
+#[test]
+fn ends_with_pattern() {
+    let config = BndmConfig::new(b"d?g", Some(b'?'));
+
+    assert!(ends_with(b"the lazy dog", &config));
+    assert!(ends_with(b"dig", &config));
+    assert!(!ends_with(b"dog ", &config));
+    assert!(!ends_with(b"og", &config));
+}
+
+#[test]
+fn starts_with_ends_with_empty_pattern() {
+    let config = BndmConfig::new(b"", None);
+
+    assert!(!starts_with(b"abc", &config));
+    assert!(!ends_with(b"abc", &config));
+}
+
+#[test]
+fn rfind_pattern_last_match() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let pattern = b"o";
+    let config = BndmConfig::new(pattern, None);
+
+    assert_eq!(rfind_pattern(source, &config), Some(41));
+}
+
+#[test]
+fn rfind_pattern_with_wildcard() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let pattern = b"?he";
+    let config = BndmConfig::new(pattern, Some(b'?'));
+
+    assert_eq!(rfind_pattern(source, &config), Some(31));
+}
+
+#[test]
+fn rfind_pattern_at_boundaries() {
+    let pattern = b"ab";
+    let config = BndmConfig::new(pattern, None);
+
+    assert_eq!(rfind_pattern(b"abxxxx", &config), Some(0));
+    assert_eq!(rfind_pattern(b"xxxxab", &config), Some(4));
+    assert_eq!(rfind_pattern(b"ab", &config), Some(0));
+}
+
+#[test]
+fn rfind_pattern_overlapping() {
+    let source = b"aaaaa";
+    let pattern = b"aaa";
+    let config = BndmConfig::new(pattern, None);
+
+    assert_eq!(rfind_pattern(source, &config), Some(2));
+}
+
+#[test]
+fn rfind_pattern_no_match() {
+    let pattern = b"cat";
+    let config = BndmConfig::new(pattern, None);
+
+    assert_eq!(rfind_pattern(b"The quick brown fox", &config), None);
+    assert_eq!(rfind_pattern(b"ca", &config), None);
+    assert_eq!(rfind_pattern(b"", &config), None);
+}
+
+#[test]
+fn rfind_pattern_empty_pattern() {
+    let config = BndmConfig::new(b"", None);
+
+    assert_eq!(rfind_pattern(b"abc", &config), None);
+}
+
+#[test]
+fn rfind_pattern_single_wildcard() {
+    let config = BndmConfig::new(b"?", Some(b'?'));
+
+    assert_eq!(rfind_pattern(b"abc", &config), Some(2));
+    assert_eq!(rfind_pattern(b"", &config), None);
+}
+
+#[test]
+fn rfind_pattern_long_pattern() {
+    let mut pattern = vec![b'x'; 100];
+    pattern[3] = b'?';
+    pattern[90] = b'y';
+    let mut source = vec![b'x'; 300];
+    source[100] = b'y';
+    source[200] = b'y';
+    source[113] = b'z';
+    let config = BndmConfig::new(&pattern, Some(b'?'));
+
+    assert_eq!(rfind_pattern(&source, &config), Some(110));
+    source[150] = b'z';
+    assert_eq!(rfind_pattern(&source, &config), Some(10));
+}
+
+#[test]
+fn rfind_pattern_equivalence() {
+    let source = "caf\u{e9} CAFE cafe".as_bytes();
+    let config = BndmConfig::with_equivalence(b"CAFE", None, &Equivalence::identity().with_class(b"Ee").with_class(b"Aa").with_class(b"Cc").with_class(b"Ff"));
+
+    assert_eq!(rfind_pattern(source, &config), Some(11));
+}
+
+#[test]
+fn rfind_pattern_matches_last_of_find_overlapping() {
+    let mut state = 0x1234_5678u32;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        (state % 3) as u8 + b'a'
+    };
+
+    for len in 1..80 {
+        let pattern: Vec<u8> = (0..len).map(|_| next()).map(|b| if b == b'c' { b'?' } else { b }).collect();
+        let source: Vec<u8> = (0..400).map(|_| next()).collect();
+        let config = BndmConfig::new(&pattern, Some(b'?'));
+        let expected = (0..=source.len() - len).rev()
+            .find(|&index| pattern.iter().zip(&source[index..]).all(|(&p, &s)| p == b'?' || p == s));
+
+        assert_eq!(rfind_pattern(&source, &config), expected, "pattern length {len}");
+    }
+}
+
+#[test]
+fn find_all_cb_multiple_matches() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let pattern = b"o";
+    let config = BndmConfig::new(pattern, None);
+    let mut indexes = Vec::new();
+    find_all_cb(source, &config, |index| indexes.push(index));
+
+    assert_eq!(indexes, vec![12, 17, 26, 41]);
+}
+
+#[test]
+fn find_all_cb_non_overlapping() {
+    let source = b"aaaaa";
+    let pattern = b"aa";
+    let config = BndmConfig::new(pattern, None);
+    let mut indexes = Vec::new();
+    find_all_cb(source, &config, |index| indexes.push(index));
+
+    assert_eq!(indexes, vec![0, 2]);
+}
+
+#[test]
+fn find_all_cb_with_wildcard() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let pattern = b"?he";
+    let config = BndmConfig::new(pattern, Some(b'?'));
+    let mut indexes = Vec::new();
+    find_all_cb(source, &config, |index| indexes.push(index));
+
+    assert_eq!(indexes, vec![0, 31]);
+}
+
+#[test]
+fn find_all_cb_wildcard_only() {
+    let source = b"abc";
+    let pattern = b"?";
+    let config = BndmConfig::new(pattern, Some(b'?'));
+    let mut indexes = Vec::new();
+    find_all_cb(source, &config, |index| indexes.push(index));
+
+    assert_eq!(indexes, vec![0, 1, 2]);
+}
+
+#[test]
+fn find_all_cb_empty_pattern() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let pattern = b"";
+    let config = BndmConfig::new(pattern, None);
+    let mut indexes = Vec::new();
+    find_all_cb(source, &config, |index| indexes.push(index));
+
+    assert_eq!(indexes, vec![]);
+}
+
+#[test]
+fn for_each_match_visits_all() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let pattern = b"?o";
+    let config = BndmConfig::new(pattern, Some(b'?'));
+    let mut indexes = Vec::new();
+    let result = for_each_match(source, &config, |index| {
+        indexes.push(index);
+        ControlFlow::Continue(())
+    });
+
+    assert_eq!(result, ControlFlow::Continue(()));
+    assert_eq!(indexes, vec![11, 16, 25, 40]);
+}
+
+#[test]
+fn for_each_match_stops_early() {
+    let source = b"aaaaaaaa";
+    let pattern = b"a";
+    let config = BndmConfig::new(pattern, None);
+    let mut count = 0;
+    let result = for_each_match(source, &config, |_| {
+        count += 1;
+        if count == 3 { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+    });
+
+    assert_eq!(result, ControlFlow::Break(()));
+    assert_eq!(count, 3);
+}
+
+#[test]
+fn for_each_match_no_match() {
+    let source = b"The quick brown fox";
+    let config = BndmConfig::new(b"cat", None);
+
+    assert_eq!(for_each_match(source, &config, |_| ControlFlow::Break(())), ControlFlow::Continue(()));
+}
+
+#[test]
+fn find_all_multiple_matches() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let pattern = b"o";
+    let config = BndmConfig::new(pattern, None);
+
+    assert_eq!(find_all(source, &config), vec![12, 17, 26, 41]);
+}
+
+#[test]
+fn find_all_non_overlapping_with_wildcard() {
+    let source = b"abababab";
+    let pattern = b"a?a";
+    let config = BndmConfig::new(pattern, Some(b'?'));
+
+    assert_eq!(find_all(source, &config), vec![0, 4]);
+}
+
+#[test]
+fn find_all_match_at_end() {
+    let source = b"xxxxab";
+    let pattern = b"?b";
+    let config = BndmConfig::new(pattern, Some(b'?'));
+
+    assert_eq!(find_all(source, &config), vec![4]);
+}
+
+#[test]
+fn find_all_long_pattern_with_wildcard() {
+    let mut pattern = vec![b'x'; 80];
+    pattern[70] = b'?';
+    let mut source = vec![b'x'; 200];
+    source[75] = b'y';
+    let config = BndmConfig::new(&pattern, Some(b'?'));
+
+    assert_eq!(find_all(&source, &config), vec![5, 85]);
+}
+
+#[test]
+fn find_all_empty_pattern() {
+    let source = b"abc";
+    let pattern = b"";
+    let config = BndmConfig::new(pattern, None);
+
+    assert_eq!(find_all(source, &config), vec![]);
+}
+
+#[test]
+fn find_up_to_limit() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let pattern = b"?o";
+    let config = BndmConfig::new(pattern, Some(b'?'));
+
+    assert_eq!(find_up_to(source, &config, 2), vec![11, 16]);
+    assert_eq!(find_up_to(source, &config, 4), vec![11, 16, 25, 40]);
+    assert_eq!(find_up_to(source, &config, 10), vec![11, 16, 25, 40]);
+}
+
+#[test]
+fn find_up_to_zero() {
+    let source = b"aaaa";
+    let config = BndmConfig::new(b"a", None);
+
+    assert_eq!(find_up_to(source, &config, 0), vec![]);
+}
+
+#[test]
+fn find_all_into_appends() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let pattern = b"?o";
+    let config = BndmConfig::new(pattern, Some(b'?'));
+    let mut indexes = vec![99];
+
+    assert_eq!(find_all_into(source, &config, &mut indexes), 4);
+    assert_eq!(indexes, vec![99, 11, 16, 25, 40]);
+}
+
+#[test]
+fn find_all_into_no_match() {
+    let source = b"The quick brown fox";
+    let config = BndmConfig::new(b"cat", None);
+    let mut indexes = Vec::new();
+
+    assert_eq!(find_all_into(source, &config, &mut indexes), 0);
+    assert!(indexes.is_empty());
+}
+
+#[test]
+fn find_all_into_slice_full() {
+    let source = b"aaaaaaaa";
+    let pattern = b"aa";
+    let config = BndmConfig::new(pattern, None);
+    let mut indexes = [0; 2];
+
+    assert_eq!(find_all_into_slice(source, &config, &mut indexes), 2);
+    assert_eq!(indexes, [0, 2]);
+}
+
+#[test]
+fn find_all_into_slice_partially_filled() {
+    let source = b"aaaaaaaa";
+    let pattern = b"aa";
+    let config = BndmConfig::new(pattern, None);
+    let mut indexes = [usize::MAX; 6];
+
+    assert_eq!(find_all_into_slice(source, &config, &mut indexes), 4);
+    assert_eq!(indexes, [0, 2, 4, 6, usize::MAX, usize::MAX]);
+}
+
+#[test]
+fn find_all_into_slice_empty_slice() {
+    let source = b"aaaa";
+    let config = BndmConfig::new(b"a", None);
+
+    assert_eq!(find_all_into_slice(source, &config, &mut []), 0);
+}
+
+#[test]
+fn count_matches_multiple_matches() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let pattern = b"?o";
+    let config = BndmConfig::new(pattern, Some(b'?'));
+
+    assert_eq!(count_matches(source, &config), 4);
+}
+
+#[test]
+fn count_matches_non_overlapping() {
+    let source = b"aaaaa";
+    let pattern = b"aa";
+    let config = BndmConfig::new(pattern, None);
+
+    assert_eq!(count_matches(source, &config), 2);
+}
+
+#[test]
+fn count_matches_no_match() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let config = BndmConfig::new(b"cat", None);
+
+    assert_eq!(count_matches(source, &config), 0);
+    assert_eq!(count_matches(source, &BndmConfig::new(b"", None)), 0);
+}
+
+#[test]
+fn find_nth_matches_find_all() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let pattern = b"?o";
+    let config = BndmConfig::new(pattern, Some(b'?'));
+    let indexes = find_all(source, &config);
+
+    for (n, &index) in indexes.iter().enumerate() {
+        assert_eq!(find_nth(source, &config, n), Some(index));
+    }
+    assert_eq!(find_nth(source, &config, indexes.len()), None);
+}
+
+#[test]
+fn find_nth_non_overlapping() {
+    let source = b"aaaaaa";
+    let pattern = b"aa";
+    let config = BndmConfig::new(pattern, None);
+
+    assert_eq!(find_nth(source, &config, 1), Some(2));
+    assert_eq!(find_nth(source, &config, 3), None);
+}
+
+#[test]
+fn find_nth_empty_pattern() {
+    let source = b"abc";
+    let config = BndmConfig::new(b"", None);
+
+    assert_eq!(find_nth(source, &config, 0), None);
+}
+
+#[test]
+fn find_pattern_verified_accepts_all() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let pattern = b"o";
+    let config = BndmConfig::new(pattern, None);
+    let index = find_pattern_verified(source, &config, |_, _| true);
+
+    assert_eq!(index, Some(12));
+}
+
+#[test]
+fn find_pattern_verified_rejects_all() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let pattern = b"o";
+    let config = BndmConfig::new(pattern, None);
+    let index = find_pattern_verified(source, &config, |_, _| false);
+
+    assert_eq!(index, None);
+}
+
+#[test]
+fn find_pattern_verified_overlapping_candidate() {
+    let source = b"aaab";
+    let pattern = b"aa";
+    let config = BndmConfig::new(pattern, None);
+    let index = find_pattern_verified(source, &config, |source, index| source[index + 2] == b'b');
+
+    assert_eq!(index, Some(1));
+}
+
+#[test]
+fn find_pattern_verified_context() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let pattern = b"?o";
+    let config = BndmConfig::new(pattern, Some(b'?'));
+    let mut candidates = Vec::new();
+    let index = find_pattern_verified(source, &config, |_, index| {
+        candidates.push(index);
+        source[index] == b'd'
+    });
+
+    assert_eq!(index, Some(40));
+    assert_eq!(candidates, vec![11, 16, 25, 40]);
+}
+
+#[test]
+fn find_all_verified_skips_rejected() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let pattern = b"o";
+    let config = BndmConfig::new(pattern, None);
+    let indexes = find_all_verified(source, &config, |source, index| source[index - 1] != b'r');
+
+    assert_eq!(indexes, vec![17, 26, 41]);
+}
+
+#[test]
+fn find_all_verified_non_overlapping() {
+    let source = b"aaaa";
+    let pattern = b"aa";
+    let config = BndmConfig::new(pattern, None);
+    let indexes = find_all_verified(source, &config, |_, _| true);
+
+    assert_eq!(indexes, vec![0, 2]);
+}
+
+#[test]
+fn config_filter_len() {
+    assert_eq!(BndmConfig::new(b"jumps", None).filter_len(), 5);
+    assert_eq!(BndmConfig::new(&[b'a'; 100], None).filter_len(), WORD_SIZE_IN_BITS);
+    assert_eq!(BndmConfig::new(b"", None).filter_len(), 0);
+}
+
+#[test]
+fn config_uses_wildcard() {
+    assert!(BndmConfig::new(b"ju??s", Some(b'?')).uses_wildcard());
+    assert!(!BndmConfig::new(b"jumps", Some(b'?')).uses_wildcard());
+    assert!(!BndmConfig::new(b"ju??s", None).uses_wildcard());
+    assert!(PatternBuilder::new().literal(b"ju").any().build().uses_wildcard());
+    assert!(!PatternBuilder::new().literal(b"ju").range(b'a'..=b'z').build().uses_wildcard());
+}
+
+#[test]
+fn config_wildcard_positions() {
+    assert_eq!(BndmConfig::new(b"?u??s", Some(b'?')).wildcard_positions(), vec![0, 2, 3]);
+    assert_eq!(BndmConfig::new(b"jumps", Some(b'?')).wildcard_positions(), vec![]);
+}
+
+#[test]
+fn config_is_pure_literal() {
+    assert!(BndmConfig::new(b"jumps", None).is_pure_literal());
+    assert!(BndmConfig::new(&[b'a'; 100], Some(b'?')).is_pure_literal());
+    assert!(!BndmConfig::new(b"ju?ps", Some(b'?')).is_pure_literal());
+    assert!(!BndmConfig::with_equivalence(b"caf\xe9", None, &Equivalence::latin1_diacritics()).is_pure_literal());
+}
+
+#[test]
+fn config_selectivity_estimate() {
+    assert_eq!(BndmConfig::new(b"aaaa", None).selectivity_estimate(), 255.0 / 256.0);
+    assert_eq!(BndmConfig::new(b"", None).selectivity_estimate(), 0.0);
+}
+
+#[cfg(feature = "macros")]
+#[test]
+fn pattern_macro() {
+    let config = crate::pattern!("4D 5A ?? ??");
+
+    assert_eq!(find_pattern(b"xxMZ\x90\x00", config), Some(2));
+    assert_eq!(find_pattern(b"MZ\x90", config), None);
+}
+
+#[cfg(feature = "macros")]
+#[test]
+fn pattern_macro_ranges_and_skips() {
+    fn config() -> &'static BndmConfig {
+        crate::pattern!(r"E8 [4] 4? [30-39] ?")
+    }
+
+    assert_eq!(find_pattern(b"\xe8\x01\x02\x03\x04\x48\x31\xff", config()), Some(0));
+    assert_eq!(find_pattern(b"\xe8\x01\x02\x03\x04\x58\x31\xff", config()), None);
+    assert!(std::ptr::eq(config(), config()));
+}
+
+#[test]
+fn clone_and_compare_configs() {
+    let config = BndmConfig::new(b"ju??s", Some(b'?'));
+    let clone = config.clone();
+
+    assert_eq!(config, clone);
+    assert_eq!(find_pattern(b"The quick brown fox jumps", &clone), Some(20));
+    assert_ne!(config, BndmConfig::new(b"ju??s", None));
+    assert_ne!(config, BndmConfig::new_nocase(b"ju??s", Some(b'?')));
+}
+
+#[test]
+fn debug_config() {
+    let config = BndmConfig::new(b"ju??s\n", Some(b'?'));
+
+    assert_eq!(format!("{config:?}"),
+        "BndmConfig { pattern: b\"ju??s\\n\", wildcard: Some(63), filter_len: 6, masks: [256 of 256 non-zero], equivalence: false, classes: false }");
+    assert!(format!("{:?}", BndmConfig::new(b"fox", None)).contains("masks: [3 of 256 non-zero]"));
+}
+
+#[test]
+fn config_is_cheap_to_move() {
+    let configs: Vec<BndmConfig> = (0..4).map(|index| BndmConfig::new(&[b'a' + index], None)).collect();
+
+    assert!(std::mem::size_of::<BndmConfig>() < std::mem::size_of::<[usize; MASKS_TABLE_SIZE]>());
+    assert_eq!(configs.into_iter().map(|config| find_pattern(b"abcd", &config)).collect::<Vec<_>>(),
+        vec![Some(0), Some(1), Some(2), Some(3)]);
+}
+
+#[test]
+fn clones_share_masks() {
+    let config = BndmConfig::new(b"ju??s", Some(b'?'));
+    let clones: Vec<BndmConfig> = (0..4).map(|_| config.clone()).collect();
+
+    assert!(clones.iter().all(|clone| Arc::ptr_eq(&clone.masks, &config.masks)));
+    assert_eq!(Arc::strong_count(&config.masks), 5);
+
+    let handles: Vec<_> = clones.into_iter()
+        .map(|clone| std::thread::spawn(move || find_pattern(b"The quick brown fox jumps", &clone)))
+        .collect();
+    assert!(handles.into_iter().all(|handle| handle.join().unwrap() == Some(20)));
+}
+
+#[test]
+fn search_methods() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let config = BndmConfig::new(b"o", None);
+
+    assert_eq!(config.find(source), find_pattern(source, &config));
+    assert_eq!(config.rfind(source), Some(41));
+    assert_eq!(config.find_from(source, 13), Some(17));
+    assert_eq!(config.find_all(source), vec![12, 17, 26, 41]);
+    assert_eq!(config.count(source), 4);
+    assert!(config.is_match(source));
+    assert!(!BndmConfig::new(b"cat", None).is_match(source));
+}
@@ -0,0 +1,167 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Configurations that borrow the pattern instead of copying it, for large patterns and for
+//! configurations that are created in bulk.
+
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use crate::{
+    BndmConfig, MASKS_TABLE_SIZE, PatternView, find_first_in_view, generate_masks, get_pattern_length_within_cpu_word, rfind_last_in_view
+};
+
+/// The `BndmConfigRef` struct is used to store the bitmasks of a pattern that is either
+/// borrowed or owned.
+///
+/// Unlike `BndmConfig`, creating a `BndmConfigRef` from a borrowed pattern does not copy the
+/// pattern, which avoids an allocation per configuration for signatures of kilobytes that are
+/// already held in memory, e.g. in a loaded signature database or in a `static`. Patterns that
+/// are built at runtime can be moved into the configuration without copying them either.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BndmConfigRef<'p> {
+    masks: [usize; MASKS_TABLE_SIZE],
+    wildcard: Option<u8>,
+    pattern: Cow<'p, [u8]>
+}
+
+impl<'p> BndmConfigRef<'p> {
+    /// Creates a new `BndmConfigRef` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `search_pattern` - The pattern to search for in the text.
+    /// * `wildcard` - An optional wildcard character. If provided, this character in the
+    ///   pattern can match any character in the text.
+    ///
+    /// # Returns
+    ///
+    /// * `BndmConfigRef` - A new `BndmConfigRef` instance that borrows the pattern.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::BndmConfigRef;
+    ///
+    /// let signatures = vec![b"fox".to_vec(), b"ju??s".to_vec()];
+    /// let configs: Vec<BndmConfigRef> = signatures.iter().map(|signature| BndmConfigRef::new(signature, Some(b'?'))).collect();
+    ///
+    /// assert_eq!(configs[1].find(b"The quick brown fox jumps"), Some(20));
+    /// ```
+    pub fn new(search_pattern: &'p [u8], wildcard: Option<u8>) -> BndmConfigRef<'p> {
+        let len = get_pattern_length_within_cpu_word(search_pattern.len());
+
+        BndmConfigRef {
+            masks: generate_masks(&search_pattern[..len], wildcard),
+            wildcard,
+            pattern: Cow::Borrowed(search_pattern)
+        }
+    }
+
+    /// Creates a new `BndmConfigRef` instance that takes ownership of the pattern.
+    ///
+    /// # Arguments
+    ///
+    /// * `search_pattern` - The pattern to search for in the text.
+    /// * `wildcard` - An optional wildcard character. If provided, this character in the
+    ///   pattern can match any character in the text.
+    ///
+    /// # Returns
+    ///
+    /// * `BndmConfigRef` - A new `BndmConfigRef` instance that owns the pattern.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::BndmConfigRef;
+    ///
+    /// let query = format!("{} {}", "brown", "fox");
+    /// let config = BndmConfigRef::from_vec(query.into_bytes(), None);
+    ///
+    /// assert_eq!(config.find(b"The quick brown fox jumps"), Some(10));
+    /// ```
+    pub fn from_vec(search_pattern: Vec<u8>, wildcard: Option<u8>) -> BndmConfigRef<'static> {
+        let len = get_pattern_length_within_cpu_word(search_pattern.len());
+
+        BndmConfigRef {
+            masks: generate_masks(&search_pattern[..len], wildcard),
+            wildcard,
+            pattern: Cow::Owned(search_pattern)
+        }
+    }
+
+    /// Returns whether the pattern is borrowed rather than owned.
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self.pattern, Cow::Borrowed(_))
+    }
+
+    /// Converts the configuration into one that owns the pattern, copying the pattern if it
+    /// is borrowed.
+    pub fn into_owned(self) -> BndmConfigRef<'static> {
+        BndmConfigRef {
+            masks: self.masks,
+            wildcard: self.wildcard,
+            pattern: Cow::Owned(self.pattern.into_owned())
+        }
+    }
+
+    /// Returns the pattern.
+    pub fn pattern(&self) -> &[u8] {
+        &self.pattern
+    }
+
+    /// Returns the wildcard character.
+    pub fn wildcard(&self) -> Option<u8> {
+        self.wildcard
+    }
+
+    /// Returns the index of the first occurrence of the pattern in the source, or `None` if
+    /// the pattern is not found.
+    pub fn find(&self, source: &[u8]) -> Option<usize> {
+        find_first_in_view(source, &self.view())
+    }
+
+    /// Returns the index of the last occurrence of the pattern in the source, or `None` if
+    /// the pattern is not found.
+    pub fn rfind(&self, source: &[u8]) -> Option<usize> {
+        rfind_last_in_view(source, &self.view())
+    }
+
+    /// Returns a `BndmConfig` instance with a copy of the pattern, for use with the other
+    /// search functions of the crate.
+    pub fn to_config(&self) -> BndmConfig {
+        BndmConfig {
+            masks: Arc::new(self.masks),
+            wildcard: self.wildcard,
+            pattern: self.pattern.to_vec(),
+            equivalence: None,
+            classes: None
+        }
+    }
+
+    fn view(&self) -> PatternView<'_> {
+        PatternView {
+            masks: &self.masks,
+            wildcard: self.wildcard,
+            pattern: &self.pattern,
+            equivalence: None,
+            classes: None
+        }
+    }
+}
+
+impl From<BndmConfigRef<'_>> for BndmConfig {
+    fn from(config: BndmConfigRef<'_>) -> BndmConfig {
+        BndmConfig {
+            masks: Arc::new(config.masks),
+            wildcard: config.wildcard,
+            pattern: config.pattern.into_owned(),
+            equivalence: None,
+            classes: None
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "./borrowed_test.rs"]
+mod borrowed_test;
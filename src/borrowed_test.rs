@@ -0,0 +1,53 @@
+use super::*;
+use crate::find_pattern;
+
+#[test]
+fn find_with_borrowed_pattern() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let pattern = b"ju??s".to_vec();
+    let config = BndmConfigRef::new(&pattern, Some(b'?'));
+
+    assert_eq!(config.find(source), Some(20));
+    assert_eq!(BndmConfigRef::new(b"cat", None).find(source), None);
+    assert_eq!(BndmConfigRef::new(b"", None).find(source), None);
+    assert!(std::ptr::eq(config.pattern(), pattern.as_slice()));
+}
+
+#[test]
+fn borrowed_equals_owned() {
+    let mut long_pattern = vec![b'a'; 70];
+    long_pattern[66] = b'?';
+    let patterns: [(&[u8], Option<u8>); 4] = [(b"ju??s", Some(b'?')), (b"aaaa", None), (b"?", Some(b'?')), (&long_pattern, Some(b'?'))];
+    let mut source = vec![b'a'; 100];
+    source[40] = b'b';
+
+    for (pattern, wildcard) in patterns {
+        let borrowed = BndmConfigRef::new(pattern, wildcard);
+        let owned = BndmConfig::new(pattern, wildcard);
+
+        assert_eq!(borrowed.to_config(), owned);
+        assert_eq!(borrowed.find(&source), find_pattern(&source, &owned));
+    }
+}
+
+#[test]
+fn owned_pattern() {
+    let config = BndmConfigRef::from_vec(b"ju??s".to_vec(), Some(b'?'));
+
+    assert!(!config.is_borrowed());
+    assert_eq!(config.find(b"The quick brown fox jumps"), Some(20));
+    assert_eq!(config, BndmConfigRef::new(b"ju??s", Some(b'?')).into_owned());
+}
+
+#[test]
+fn into_owned_outlives_pattern() {
+    let config = {
+        let pattern = b"fox".to_vec();
+        let config = BndmConfigRef::new(&pattern, None);
+        assert!(config.is_borrowed());
+        config.into_owned()
+    };
+
+    assert!(!config.is_borrowed());
+    assert_eq!(BndmConfig::from(config), BndmConfig::new(b"fox", None));
+}
@@ -0,0 +1,71 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! A builder for configurations with optional settings.
+
+use crate::error::validate_pattern;
+use crate::{BndmConfig, BndmError};
+
+/// The `BndmConfigBuilder` struct collects the settings of a `BndmConfig`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BndmConfigBuilder {
+    pattern: Vec<u8>,
+    wildcard: Option<u8>,
+    case_insensitive: bool
+}
+
+impl BndmConfig {
+    /// Creates a new `BndmConfigBuilder` instance without a pattern and with the default
+    /// settings.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{BndmConfig, find_pattern};
+    ///
+    /// let config = BndmConfig::builder().pattern(b"J?MPS").wildcard(b'?').case_insensitive(true).build().unwrap();
+    /// assert_eq!(find_pattern(b"The quick brown fox jumps over the lazy dog", &config), Some(20));
+    /// ```
+    pub fn builder() -> BndmConfigBuilder {
+        BndmConfigBuilder::default()
+    }
+}
+
+impl BndmConfigBuilder {
+    /// Sets the pattern to search for in the text.
+    pub fn pattern(mut self, pattern: &[u8]) -> BndmConfigBuilder {
+        self.pattern = pattern.to_vec();
+        self
+    }
+
+    /// Sets the wildcard character, which matches any character in the text.
+    pub fn wildcard(mut self, wildcard: u8) -> BndmConfigBuilder {
+        self.wildcard = Some(wildcard);
+        self
+    }
+
+    /// Sets whether ASCII letters of the pattern match both cases. Disabled by default.
+    pub fn case_insensitive(mut self, case_insensitive: bool) -> BndmConfigBuilder {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    /// Builds a `BndmConfig` instance with the settings of the builder.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<BndmConfig, BndmError>` - Returns a new `BndmConfig` instance, or an error if
+    ///   no pattern is set, or the pattern is empty or only consists of wildcards.
+    pub fn build(&self) -> Result<BndmConfig, BndmError> {
+        validate_pattern(&self.pattern, self.wildcard)?;
+
+        Ok(match self.case_insensitive {
+            true => BndmConfig::new_nocase(&self.pattern, self.wildcard),
+            false => BndmConfig::new(&self.pattern, self.wildcard)
+        })
+    }
+}
+
+#[cfg(test)]
+#[path = "./builder_test.rs"]
+mod builder_test;
@@ -0,0 +1,41 @@
+use super::*;
+use crate::find_pattern;
+
+#[test]
+fn build_literal() {
+    let config = BndmConfig::builder().pattern(b"fox").build().unwrap();
+
+    assert!(config.is_pure_literal());
+    assert_eq!(find_pattern(b"The quick brown fox", &config), Some(16));
+    assert_eq!(find_pattern(b"The quick brown FOX", &config), None);
+}
+
+#[test]
+fn build_with_wildcard() {
+    let config = BndmConfig::builder().wildcard(b'?').pattern(b"ju??s").build().unwrap();
+
+    assert_eq!(config.wildcard, Some(b'?'));
+    assert_eq!(find_pattern(b"The quick brown fox jumps", &config), Some(20));
+}
+
+#[test]
+fn build_case_insensitive() {
+    let config = BndmConfig::builder().pattern(b"Fox").case_insensitive(true).build().unwrap();
+
+    assert_eq!(find_pattern(b"The quick brown FOX", &config), Some(16));
+    let config = BndmConfig::builder().pattern(b"Fox").case_insensitive(true).case_insensitive(false).build().unwrap();
+    assert_eq!(find_pattern(b"The quick brown FOX", &config), None);
+}
+
+#[test]
+fn build_without_pattern() {
+    assert_eq!(BndmConfig::builder().build().err(), Some(BndmError::EmptyPattern));
+    assert_eq!(BndmConfig::builder().pattern(b"").wildcard(b'?').build().err(), Some(BndmError::EmptyPattern));
+    assert_eq!(BndmError::EmptyPattern.to_string(), "pattern is empty");
+}
+
+#[test]
+fn build_wildcard_only() {
+    assert_eq!(BndmConfig::builder().pattern(b"??").wildcard(b'?').build().err(), Some(BndmError::WildcardOnly));
+    assert!(BndmConfig::builder().pattern(b"??").build().is_ok());
+}
@@ -0,0 +1,440 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Searching in C64 memory images, with ROM/RAM banking, relocated code, 6502 code signatures
+//! and PETSCII text.
+
+use crate::{BndmConfig, MultiBndmConfig, PatternBuilder, find_overlapping_indexes};
+
+const MEMORY_SIZE: usize = 0x10000;
+const BASIC_ROM_ADDRESS: usize = 0xa000;
+const CHAR_ROM_ADDRESS: usize = 0xd000;
+const KERNAL_ROM_ADDRESS: usize = 0xe000;
+
+const LORAM: u8 = 1 << 0;
+const HIRAM: u8 = 1 << 1;
+const CHAREN: u8 = 1 << 2;
+
+/// The `C64Memory` struct combines a 64 KB RAM image with the ROMs that can be banked in by
+/// the processor port at address `$01`.
+pub struct C64Memory<'a> {
+    ram: &'a [u8],
+    basic_rom: Option<&'a [u8; 0x2000]>,
+    char_rom: Option<&'a [u8; 0x1000]>,
+    kernal_rom: Option<&'a [u8; 0x2000]>
+}
+
+/// A match of relocated code in a memory image.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Relocation {
+    /// The index of the match in the memory image.
+    pub offset: usize,
+
+    /// The address the code has been relocated to, i.e. the original address of the code
+    /// shifted by the same number of pages as the absolute addresses in the code.
+    pub base: u16
+}
+
+/// The options for generating a 6502 code signature with `opcode_signature`.
+///
+/// Operand bytes of instructions with absolute addressing, including `JMP` and `JSR`, are
+/// always wildcarded, as they change when code is relocated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OpcodeSignatureOptions {
+    /// Whether to wildcard immediate values, e.g. the `$0F` of `LDA #$0F`.
+    pub wildcard_immediate: bool,
+
+    /// Whether to wildcard zero page addresses, e.g. the `$FB` of `STA $FB`.
+    pub wildcard_zero_page: bool,
+
+    /// Whether to wildcard branch offsets, e.g. the offset of `BNE`.
+    pub wildcard_relative: bool
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum AddressingMode {
+    Implied,
+    Immediate,
+    ZeroPage,
+    Relative,
+    Absolute
+}
+
+/// The encoding of text in C64 memory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextEncoding {
+    /// Plain ASCII.
+    Ascii,
+
+    /// PETSCII in the uppercase/graphics character set, in which both cases of ASCII letters
+    /// are shown as uppercase letters.
+    Petscii,
+
+    /// PETSCII in the lowercase/uppercase character set, in which the cases of ASCII letters
+    /// are swapped, i.e. lowercase letters are `$41-$5A` and uppercase letters are `$C1-$DA`.
+    PetsciiShifted,
+
+    /// Screen codes of the uppercase/graphics character set, as stored in screen memory.
+    ScreenCode,
+
+    /// Screen codes of the lowercase/uppercase character set, as stored in screen memory.
+    ScreenCodeShifted
+}
+
+/// The encodings that are searched for by `find_c64_text`, in order of preference.
+pub const TEXT_ENCODINGS: [TextEncoding; 5] = [
+    TextEncoding::Ascii,
+    TextEncoding::Petscii,
+    TextEncoding::PetsciiShifted,
+    TextEncoding::ScreenCode,
+    TextEncoding::ScreenCodeShifted
+];
+
+/// A match of text in one of the C64 text encodings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TextMatch {
+    /// The index of the match in the source.
+    pub offset: usize,
+
+    /// The encoding in which the text has been found.
+    pub encoding: TextEncoding
+}
+
+impl<'a> C64Memory<'a> {
+    /// Creates a new `C64Memory` instance for a RAM image without ROMs.
+    ///
+    /// # Arguments
+    ///
+    /// * `ram` - The RAM image; images smaller than 64 KB are treated as if the missing part
+    ///   is filled with zeros, and images larger than 64 KB are truncated.
+    ///
+    /// # Returns
+    ///
+    /// * `C64Memory` - A new `C64Memory` instance.
+    pub fn new(ram: &'a [u8]) -> C64Memory<'a> {
+        C64Memory {
+            ram: &ram[..ram.len().min(MEMORY_SIZE)],
+            basic_rom: None,
+            char_rom: None,
+            kernal_rom: None
+        }
+    }
+
+    /// Sets the BASIC ROM image that is visible at `$A000-$BFFF`.
+    pub fn with_basic_rom(mut self, basic_rom: &'a [u8; 0x2000]) -> C64Memory<'a> {
+        self.basic_rom = Some(basic_rom);
+        self
+    }
+
+    /// Sets the character ROM image that is visible at `$D000-$DFFF`.
+    pub fn with_char_rom(mut self, char_rom: &'a [u8; 0x1000]) -> C64Memory<'a> {
+        self.char_rom = Some(char_rom);
+        self
+    }
+
+    /// Sets the KERNAL ROM image that is visible at `$E000-$FFFF`.
+    pub fn with_kernal_rom(mut self, kernal_rom: &'a [u8; 0x2000]) -> C64Memory<'a> {
+        self.kernal_rom = Some(kernal_rom);
+        self
+    }
+
+    /// Returns the 64 KB of memory as seen by the CPU for the given value of the processor
+    /// port at address `$01`, without cartridges.
+    ///
+    /// ROMs that have not been set are not banked in. The I/O area is not emulated; when I/O
+    /// is banked in, the RAM underneath is returned instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `port` - The value of the processor port, e.g. `$37` for the default configuration
+    ///   with BASIC, I/O and KERNAL visible.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - Returns the visible memory.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::C64Memory;
+    ///
+    /// let ram = [0u8; 0x10000];
+    /// let kernal = [0xeau8; 0x2000];
+    /// let memory = C64Memory::new(&ram).with_kernal_rom(&kernal);
+    /// assert_eq!(memory.visible(0x37)[0xfffc], 0xea);
+    /// assert_eq!(memory.visible(0x35)[0xfffc], 0x00);
+    /// ```
+    pub fn visible(&self, port: u8) -> Vec<u8> {
+        let mut memory = self.ram.to_vec();
+        memory.resize(MEMORY_SIZE, 0);
+
+        let loram = port & LORAM != 0;
+        let hiram = port & HIRAM != 0;
+        if let Some(basic_rom) = self.basic_rom.filter(|_| loram && hiram) {
+            memory[BASIC_ROM_ADDRESS..BASIC_ROM_ADDRESS + basic_rom.len()].copy_from_slice(basic_rom);
+        }
+        if let Some(char_rom) = self.char_rom.filter(|_| (loram || hiram) && port & CHAREN == 0) {
+            memory[CHAR_ROM_ADDRESS..CHAR_ROM_ADDRESS + char_rom.len()].copy_from_slice(char_rom);
+        }
+        if let Some(kernal_rom) = self.kernal_rom.filter(|_| hiram) {
+            memory[KERNAL_ROM_ADDRESS..KERNAL_ROM_ADDRESS + kernal_rom.len()].copy_from_slice(kernal_rom);
+        }
+        memory
+    }
+}
+
+/// Searches for code that may have been relocated by a whole number of pages.
+///
+/// When code is relocated to another page, only the high bytes of the absolute addresses that
+/// point into the code itself change, and they all change by the same number of pages. These
+/// bytes are wildcarded during the search, and every candidate is verified to have a
+/// consistent page offset.
+///
+/// # Arguments
+///
+/// * `memory` - The memory image to search in.
+/// * `code` - The code as it appears at its original address.
+/// * `base` - The original address of the code.
+/// * `relocated_bytes` - The indexes in the code of the high bytes of absolute addresses that
+///   change when the code is relocated.
+///
+/// # Returns
+///
+/// * `Vec<Relocation>` - Returns the index in the memory image and the relocated address of
+///   every match, including matches that have not been relocated.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{Relocation, find_relocated};
+///
+/// // JSR $1006, RTS at $1000 relocated to $2400 as JSR $2406, RTS
+/// let code = [0x20, 0x06, 0x10, 0x60];
+/// let memory = [0x00, 0x20, 0x06, 0x24, 0x60, 0x00];
+/// let relocations = find_relocated(&memory, &code, 0x1000, &[2]);
+/// assert_eq!(relocations, vec![Relocation { offset: 1, base: 0x2400 }]);
+/// ```
+pub fn find_relocated(memory: &[u8], code: &[u8], base: u16, relocated_bytes: &[usize]) -> Vec<Relocation> {
+    if code.is_empty() || code.len() > memory.len() || relocated_bytes.iter().any(|&index| index >= code.len()) {
+        return Vec::new();
+    }
+
+    let candidates: Vec<usize> = match unused_byte(code, relocated_bytes) {
+        Some(wildcard) => {
+            let mut pattern = code.to_vec();
+            relocated_bytes.iter().for_each(|&index| pattern[index] = wildcard);
+            find_overlapping_indexes(memory, &BndmConfig::new(&pattern, Some(wildcard))).collect()
+        }
+        None => (0..=memory.len() - code.len()).collect()
+    };
+
+    candidates.into_iter().filter_map(|offset| {
+        let window = &memory[offset..offset + code.len()];
+        let is_fixed = |index: usize| relocated_bytes.contains(&index) || window[index] == code[index];
+        let pages = relocated_bytes.first().map_or(0, |&index| window[index].wrapping_sub(code[index]));
+        let is_consistent = relocated_bytes.iter().all(|&index| window[index].wrapping_sub(code[index]) == pages);
+
+        ((0..code.len()).all(is_fixed) && is_consistent)
+            .then(|| Relocation { offset, base: base.wrapping_add((pages as u16) << 8) })
+    }).collect()
+}
+
+/// Generates a signature for a 6502 code snippet with the operands wildcarded, so the routine
+/// can be found after it has been relocated or assembled with different data addresses.
+///
+/// The instruction lengths follow the NMOS 6502, including the undocumented opcodes. The
+/// wildcard character is chosen among the byte values that do not occur in the signature. If
+/// every byte value occurs, the signature is built from classes of bytes instead, so that no
+/// literal byte is turned into a wildcard.
+///
+/// # Arguments
+///
+/// * `code` - The code snippet, starting at an instruction.
+/// * `options` - The options that specify which operands to wildcard besides absolute
+///   addresses.
+///
+/// # Returns
+///
+/// * `BndmConfig` - A new `BndmConfig` instance for the signature.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{OpcodeSignatureOptions, find_pattern, opcode_signature};
+///
+/// // LDA #$0F; STA $D418; JMP $1003
+/// let code = [0xa9, 0x0f, 0x8d, 0x18, 0xd4, 0x4c, 0x03, 0x10];
+/// let config = opcode_signature(&code, &OpcodeSignatureOptions::default());
+///
+/// // LDA #$0F; STA $D400; JMP $C003
+/// let source = [0x00, 0xa9, 0x0f, 0x8d, 0x00, 0xd4, 0x4c, 0x03, 0xc0];
+/// assert_eq!(find_pattern(&source, &config), Some(1));
+/// ```
+pub fn opcode_signature(code: &[u8], options: &OpcodeSignatureOptions) -> BndmConfig {
+    let mut wildcarded = Vec::new();
+    let mut index = 0;
+
+    while index < code.len() {
+        let mode = addressing_mode(code[index]);
+        let operand_len = match mode {
+            AddressingMode::Implied => 0,
+            AddressingMode::Absolute => 2,
+            _ => 1
+        };
+        let is_wildcarded = match mode {
+            AddressingMode::Implied => false,
+            AddressingMode::Immediate => options.wildcard_immediate,
+            AddressingMode::ZeroPage => options.wildcard_zero_page,
+            AddressingMode::Relative => options.wildcard_relative,
+            AddressingMode::Absolute => true
+        };
+
+        let operands = index + 1..(index + 1 + operand_len).min(code.len());
+        if is_wildcarded {
+            wildcarded.extend(operands.clone());
+        }
+        index = operands.end;
+    }
+
+    match unused_byte(code, &wildcarded) {
+        Some(wildcard) => {
+            let mut pattern = code.to_vec();
+            wildcarded.iter().for_each(|&index| pattern[index] = wildcard);
+            BndmConfig::new(&pattern, Some(wildcard))
+        }
+        None => code.iter().enumerate()
+            .fold(PatternBuilder::new(), |builder, (index, byte)| match wildcarded.contains(&index) {
+                true => builder.any(),
+                false => builder.literal(std::slice::from_ref(byte))
+            })
+            .build()
+    }
+}
+
+fn addressing_mode(opcode: u8) -> AddressingMode {
+    let group = opcode & 0x03;
+    let mode = (opcode >> 2) & 0x07;
+    let operation = opcode >> 5;
+
+    match (group, mode) {
+        (0, 0) => match operation {
+            0 | 2 | 3 => AddressingMode::Implied,
+            1 => AddressingMode::Absolute,
+            _ => AddressingMode::Immediate
+        },
+        (0, 4) => AddressingMode::Relative,
+        (2, 0) if operation < 4 => AddressingMode::Implied,
+        (0 | 2, 2 | 6) | (2, 4) => AddressingMode::Implied,
+        (_, 2) | (2, 0) => AddressingMode::Immediate,
+        (_, 3 | 6 | 7) => AddressingMode::Absolute,
+        _ => AddressingMode::ZeroPage
+    }
+}
+
+/// Encodes ASCII text in one of the C64 text encodings.
+///
+/// Bytes that have no equivalent in the encoding, such as control characters, are kept as is.
+///
+/// # Arguments
+///
+/// * `text` - The ASCII text to encode.
+/// * `encoding` - The encoding to encode the text in.
+///
+/// # Returns
+///
+/// * `Vec<u8>` - Returns the encoded text.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{TextEncoding, encode_c64_text};
+///
+/// assert_eq!(encode_c64_text(b"Ready.", TextEncoding::Petscii), b"READY.");
+/// assert_eq!(encode_c64_text(b"Ready.", TextEncoding::PetsciiShifted), b"\xd2EADY.");
+/// assert_eq!(encode_c64_text(b"Ready.", TextEncoding::ScreenCode), b"\x12\x05\x01\x04\x19.");
+/// ```
+pub fn encode_c64_text(text: &[u8], encoding: TextEncoding) -> Vec<u8> {
+    text.iter().map(|&byte| match encoding {
+        TextEncoding::Ascii => byte,
+        TextEncoding::Petscii => byte.to_ascii_uppercase(),
+        TextEncoding::PetsciiShifted => ascii_to_petscii_shifted(byte),
+        TextEncoding::ScreenCode => petscii_to_screen_code(byte.to_ascii_uppercase()),
+        TextEncoding::ScreenCodeShifted => petscii_to_screen_code(ascii_to_petscii_shifted(byte))
+    }).collect()
+}
+
+/// Searches for ASCII text in all C64 text encodings, so text typed in ASCII can be found in
+/// C64 programs and memory dumps regardless of how it has been stored.
+///
+/// The text is encoded in every encoding of `TEXT_ENCODINGS`, and the source is scanned once
+/// for all encoded texts together. The encodings are not combined into an `Equivalence`,
+/// because a match has to be in a single encoding, and because the encodings map some bytes
+/// onto bytes of other characters, e.g. `[` is stored as `$1B` in screen codes, which would
+/// merge unrelated characters into one class.
+///
+/// Occurrences of the same encoding do not overlap. If the text is found at the same index in
+/// more than one encoding, e.g. because it consists of digits only, it is reported once with
+/// the first encoding of `TEXT_ENCODINGS`.
+///
+/// # Arguments
+///
+/// * `source` - The source to search for the text.
+/// * `text` - The ASCII text to search for.
+///
+/// # Returns
+///
+/// * `Vec<TextMatch>` - Returns the matches ordered by their index.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{TextEncoding, TextMatch, find_c64_text};
+///
+/// let source = b"\x00\x00LOAD\x00\x0c\x0f\x01\x04";
+/// assert_eq!(find_c64_text(source, b"load"), vec![
+///     TextMatch { offset: 2, encoding: TextEncoding::Petscii },
+///     TextMatch { offset: 7, encoding: TextEncoding::ScreenCode }
+/// ]);
+/// ```
+pub fn find_c64_text(source: &[u8], text: &[u8]) -> Vec<TextMatch> {
+    let configs = TEXT_ENCODINGS.iter()
+        .map(|&encoding| BndmConfig::new(&encode_c64_text(text, encoding), None))
+        .collect();
+    let mut matches = MultiBndmConfig::new(configs).find_all(source);
+
+    matches.sort_by_key(|text_match| (text_match.offset, text_match.pattern));
+    matches.dedup_by_key(|text_match| text_match.offset);
+    matches.into_iter()
+        .map(|text_match| TextMatch { offset: text_match.offset, encoding: TEXT_ENCODINGS[text_match.pattern] })
+        .collect()
+}
+
+fn ascii_to_petscii_shifted(byte: u8) -> u8 {
+    match byte {
+        b'a'..=b'z' => byte - b'a' + 0x41,
+        b'A'..=b'Z' => byte - b'A' + 0xc1,
+        _ => byte
+    }
+}
+
+fn petscii_to_screen_code(byte: u8) -> u8 {
+    match byte {
+        0x40..=0x5f => byte - 0x40,
+        0x60..=0x7f => byte - 0x20,
+        0xc0..=0xdf => byte - 0x80,
+        _ => byte
+    }
+}
+
+pub(crate) fn unused_byte(bytes: &[u8], ignored_indexes: &[usize]) -> Option<u8> {
+    let mut used = [false; 256];
+    bytes.iter().enumerate()
+        .filter(|(index, _)| !ignored_indexes.contains(index))
+        .for_each(|(_, &byte)| used[byte as usize] = true);
+
+    used.iter().position(|&is_used| !is_used).map(|byte| byte as u8)
+}
+
+#[cfg(test)]
+#[path = "./c64_test.rs"]
+mod c64_test;
@@ -0,0 +1,257 @@
+use super::*;
+
+// LDX #$00; LDA $1010,X; STA $0400,X; INX; BNE $1002; JMP $1020
+const CODE: [u8; 14] = [0xa2, 0x00, 0xbd, 0x10, 0x10, 0x9d, 0x00, 0x04, 0xe8, 0xd0, 0xf7, 0x4c, 0x20, 0x10];
+const RELOCATED_BYTES: [usize; 2] = [4, 13];
+
+#[test]
+fn visible_default_banking() {
+    let ram = [0x11u8; 0x10000];
+    let basic = [0x22u8; 0x2000];
+    let char = [0x33u8; 0x1000];
+    let kernal = [0x44u8; 0x2000];
+    let memory = C64Memory::new(&ram).with_basic_rom(&basic).with_char_rom(&char).with_kernal_rom(&kernal);
+    let visible = memory.visible(0x37);
+
+    assert_eq!(visible[0x9fff], 0x11);
+    assert_eq!(visible[0xa000], 0x22);
+    assert_eq!(visible[0xd000], 0x11);
+    assert_eq!(visible[0xe000], 0x44);
+}
+
+#[test]
+fn visible_char_rom() {
+    let ram = [0x11u8; 0x10000];
+    let char = [0x33u8; 0x1000];
+    let memory = C64Memory::new(&ram).with_char_rom(&char);
+
+    assert_eq!(memory.visible(0x33)[0xd000], 0x33);
+    assert_eq!(memory.visible(0x30)[0xd000], 0x11);
+}
+
+#[test]
+fn visible_all_ram() {
+    let ram = [0x11u8; 0x10000];
+    let basic = [0x22u8; 0x2000];
+    let kernal = [0x44u8; 0x2000];
+    let memory = C64Memory::new(&ram).with_basic_rom(&basic).with_kernal_rom(&kernal);
+    let visible = memory.visible(0x34);
+
+    assert!(visible.iter().all(|&byte| byte == 0x11));
+}
+
+#[test]
+fn visible_basic_out_kernal_in() {
+    let ram = [0x11u8; 0x10000];
+    let basic = [0x22u8; 0x2000];
+    let kernal = [0x44u8; 0x2000];
+    let memory = C64Memory::new(&ram).with_basic_rom(&basic).with_kernal_rom(&kernal);
+    let visible = memory.visible(0x36);
+
+    assert_eq!(visible[0xa000], 0x11);
+    assert_eq!(visible[0xe000], 0x44);
+}
+
+#[test]
+fn visible_small_ram_image() {
+    let ram = [0x11u8; 0x100];
+    let memory = C64Memory::new(&ram);
+    let visible = memory.visible(0x37);
+
+    assert_eq!(visible.len(), 0x10000);
+    assert_eq!(visible[0xff], 0x11);
+    assert_eq!(visible[0x100], 0x00);
+}
+
+#[test]
+fn find_relocated_code() {
+    let mut memory = vec![0u8; 0x10000];
+    memory[0x1000..0x100e].copy_from_slice(&CODE);
+    let mut relocated = CODE;
+    relocated[4] = 0x30;
+    relocated[13] = 0x30;
+    memory[0x3000..0x300e].copy_from_slice(&relocated);
+    let relocations = find_relocated(&memory, &CODE, 0x1000, &RELOCATED_BYTES);
+
+    assert_eq!(relocations, vec![
+        Relocation { offset: 0x1000, base: 0x1000 },
+        Relocation { offset: 0x3000, base: 0x3000 }
+    ]);
+}
+
+#[test]
+fn find_relocated_inconsistent_pages() {
+    let mut memory = vec![0u8; 0x1000];
+    let mut relocated = CODE;
+    relocated[4] = 0x30;
+    relocated[13] = 0x31;
+    memory[0x100..0x10e].copy_from_slice(&relocated);
+    let relocations = find_relocated(&memory, &CODE, 0x1000, &RELOCATED_BYTES);
+
+    assert_eq!(relocations, vec![]);
+}
+
+#[test]
+fn find_relocated_wrapping_base() {
+    let mut relocated = CODE;
+    relocated[4] = 0x0f;
+    relocated[13] = 0x0f;
+    let relocations = find_relocated(&relocated, &CODE, 0x1000, &RELOCATED_BYTES);
+
+    assert_eq!(relocations, vec![Relocation { offset: 0, base: 0x0f00 }]);
+}
+
+#[test]
+fn find_relocated_in_visible_memory() {
+    let mut ram = vec![0u8; 0x10000];
+    ram[0xc000..0xc00e].copy_from_slice(&CODE);
+    ram[0xc004] = 0xc0;
+    ram[0xc00d] = 0xc0;
+    let memory = C64Memory::new(&ram);
+    let relocations = find_relocated(&memory.visible(0x37), &CODE, 0x1000, &RELOCATED_BYTES);
+
+    assert_eq!(relocations, vec![Relocation { offset: 0xc000, base: 0xc000 }]);
+}
+
+#[test]
+fn find_relocated_invalid_index() {
+    let relocations = find_relocated(&CODE, &CODE, 0x1000, &[14]);
+
+    assert_eq!(relocations, vec![]);
+}
+
+#[test]
+fn unused_byte_skips_used_bytes() {
+    assert_eq!(unused_byte(&[0x00, 0x01, 0x03], &[]), Some(0x02));
+    assert_eq!(unused_byte(&[0x00, 0x01, 0x03], &[0]), Some(0x00));
+}
+
+#[test]
+fn unused_byte_all_used() {
+    let bytes: Vec<u8> = (0..=255).collect();
+
+    assert_eq!(unused_byte(&bytes, &[]), None);
+}
+
+#[test]
+fn encode_text_petscii() {
+    assert_eq!(encode_c64_text(b"Hello, World!", TextEncoding::Petscii), b"HELLO, WORLD!");
+}
+
+#[test]
+fn encode_text_petscii_shifted() {
+    assert_eq!(encode_c64_text(b"Hello, World!", TextEncoding::PetsciiShifted), b"\xc8ELLO, \xd7ORLD!");
+}
+
+#[test]
+fn encode_text_screen_codes() {
+    assert_eq!(encode_c64_text(b"@AZ[ 09", TextEncoding::ScreenCode), b"\x00\x01\x1a\x1b\x2009");
+    assert_eq!(encode_c64_text(b"aZ", TextEncoding::ScreenCodeShifted), b"\x01\x5a");
+}
+
+#[test]
+fn find_text_in_all_encodings() {
+    let mut source = b"Press play on tape".to_vec();
+    source.extend_from_slice(b"..PRESS PLAY..");
+    source.extend_from_slice(b"..\xd0RESS..");
+    source.extend_from_slice(b"..\x10\x12\x05\x13\x13..");
+    source.extend_from_slice(b"..\x50\x12\x05\x13\x13..");
+    let matches = find_c64_text(&source, b"Press");
+
+    assert_eq!(matches, vec![
+        TextMatch { offset: 0, encoding: TextEncoding::Ascii },
+        TextMatch { offset: 20, encoding: TextEncoding::Petscii },
+        TextMatch { offset: 34, encoding: TextEncoding::PetsciiShifted },
+        TextMatch { offset: 43, encoding: TextEncoding::ScreenCode },
+        TextMatch { offset: 52, encoding: TextEncoding::ScreenCodeShifted }
+    ]);
+}
+
+#[test]
+fn find_text_same_in_multiple_encodings() {
+    let matches = find_c64_text(b"level 1984", b"1984");
+
+    assert_eq!(matches, vec![TextMatch { offset: 6, encoding: TextEncoding::Ascii }]);
+}
+
+#[test]
+fn addressing_modes() {
+    assert_eq!(addressing_mode(0x00), AddressingMode::Implied);
+    assert_eq!(addressing_mode(0x20), AddressingMode::Absolute);
+    assert_eq!(addressing_mode(0x60), AddressingMode::Implied);
+    assert_eq!(addressing_mode(0xa0), AddressingMode::Immediate);
+    assert_eq!(addressing_mode(0xa2), AddressingMode::Immediate);
+    assert_eq!(addressing_mode(0xa9), AddressingMode::Immediate);
+    assert_eq!(addressing_mode(0x0b), AddressingMode::Immediate);
+    assert_eq!(addressing_mode(0x01), AddressingMode::ZeroPage);
+    assert_eq!(addressing_mode(0xb1), AddressingMode::ZeroPage);
+    assert_eq!(addressing_mode(0x85), AddressingMode::ZeroPage);
+    assert_eq!(addressing_mode(0x96), AddressingMode::ZeroPage);
+    assert_eq!(addressing_mode(0xd0), AddressingMode::Relative);
+    assert_eq!(addressing_mode(0x4c), AddressingMode::Absolute);
+    assert_eq!(addressing_mode(0x6c), AddressingMode::Absolute);
+    assert_eq!(addressing_mode(0xbd), AddressingMode::Absolute);
+    assert_eq!(addressing_mode(0xbe), AddressingMode::Absolute);
+    assert_eq!(addressing_mode(0x0a), AddressingMode::Implied);
+    assert_eq!(addressing_mode(0x9a), AddressingMode::Implied);
+    assert_eq!(addressing_mode(0x02), AddressingMode::Implied);
+    assert_eq!(addressing_mode(0xea), AddressingMode::Implied);
+}
+
+#[test]
+fn opcode_signature_wildcards_absolute_operands() {
+    let config = opcode_signature(&CODE, &OpcodeSignatureOptions::default());
+    let wildcard = config.wildcard.unwrap();
+
+    assert_eq!(config.pattern, vec![0xa2, 0x00, 0xbd, wildcard, wildcard, 0x9d, wildcard, wildcard, 0xe8, 0xd0, 0xf7, 0x4c, wildcard, wildcard]);
+}
+
+#[test]
+fn opcode_signature_wildcards_optional_operands() {
+    // LDA #$00; STA $FB; BEQ +2
+    let code = [0xa9, 0x00, 0x85, 0xfb, 0xf0, 0x02];
+    let options = OpcodeSignatureOptions { wildcard_immediate: true, wildcard_zero_page: true, wildcard_relative: true };
+    let config = opcode_signature(&code, &options);
+    let wildcard = config.wildcard.unwrap();
+
+    assert_eq!(config.pattern, vec![0xa9, wildcard, 0x85, wildcard, 0xf0, wildcard]);
+}
+
+#[test]
+fn opcode_signature_truncated_instruction() {
+    let code = [0xea, 0x20, 0x00];
+    let config = opcode_signature(&code, &OpcodeSignatureOptions::default());
+    let wildcard = config.wildcard.unwrap();
+
+    assert_eq!(config.pattern, vec![0xea, 0x20, wildcard]);
+}
+
+#[test]
+fn opcode_signature_finds_routine_with_other_addresses() {
+    let config = opcode_signature(&CODE, &OpcodeSignatureOptions::default());
+    let source = [0x60, 0xa2, 0x00, 0xbd, 0x00, 0x80, 0x9d, 0x00, 0xd8, 0xe8, 0xd0, 0xf7, 0x4c, 0x00, 0x90];
+
+    assert_eq!(crate::find_pattern(&source, &config), Some(1));
+}
+
+#[test]
+fn opcode_signature_with_every_byte_value() {
+    // LDA #$00; LDA #$01; ...; LDA #$FF; JMP $1000
+    let mut code: Vec<u8> = (0..=255).flat_map(|byte| [0xa9, byte]).collect();
+    code.extend_from_slice(&[0x4c, 0x00, 0x10]);
+    let config = opcode_signature(&code, &OpcodeSignatureOptions::default());
+
+    let mut source = code.clone();
+    source[code.len() - 1] = 0xc0;
+    assert_eq!(crate::find_pattern(&source, &config), Some(0));
+
+    source[1] = 0x01;
+    assert_eq!(crate::find_pattern(&source, &config), None);
+}
+
+#[test]
+fn find_text_not_in_mixed_encodings() {
+    let source = b"L\x0f\x01\x04 lo\x01\x04";
+
+    assert_eq!(find_c64_text(source, b"load"), vec![]);
+}
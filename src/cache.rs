@@ -0,0 +1,131 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! A cache of compiled configurations for code that searches for the same dynamic patterns
+//! over and over again.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::BndmConfig;
+
+/// The `ConfigCache` struct keeps the most recently used configurations, keyed by pattern and
+/// wildcard, so that the bitmasks of a pattern are only generated once.
+///
+/// When the cache is full, the least recently used configuration is evicted. Configurations
+/// are handed out as `Arc` values, so they stay valid after being evicted and a cache behind
+/// a `Mutex` only needs to be locked for the lookup.
+#[derive(Clone, Debug)]
+pub struct ConfigCache {
+    capacity: usize,
+    entries: HashMap<(Vec<u8>, Option<u8>), CacheEntry>,
+    clock: u64
+}
+
+#[derive(Clone, Debug)]
+struct CacheEntry {
+    config: Arc<BndmConfig>,
+    last_used: u64
+}
+
+impl ConfigCache {
+    /// Creates a new, empty `ConfigCache` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - The maximum number of configurations to keep. A capacity of zero keeps
+    ///   no configurations.
+    ///
+    /// # Returns
+    ///
+    /// * `ConfigCache` - A new `ConfigCache` instance.
+    pub fn new(capacity: usize) -> ConfigCache {
+        ConfigCache {
+            capacity,
+            entries: HashMap::with_capacity(capacity),
+            clock: 0
+        }
+    }
+
+    /// Returns the configuration for the pattern, and compiles it if it is not in the cache.
+    ///
+    /// # Arguments
+    ///
+    /// * `search_pattern` - The pattern to search for in the text.
+    /// * `wildcard` - An optional wildcard character. If provided, this character in the pattern
+    ///   can match any character in the text.
+    ///
+    /// # Returns
+    ///
+    /// * `Arc<BndmConfig>` - The configuration for the pattern.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use bndm::{ConfigCache, find_pattern};
+    ///
+    /// let mut cache = ConfigCache::new(100);
+    /// let source = b"The quick brown fox jumps over the lazy dog";
+    ///
+    /// let config = cache.get(b"ju??s", Some(b'?'));
+    /// assert_eq!(find_pattern(source, &config), Some(20));
+    /// assert!(Arc::ptr_eq(&config, &cache.get(b"ju??s", Some(b'?'))));
+    /// ```
+    pub fn get(&mut self, search_pattern: &[u8], wildcard: Option<u8>) -> Arc<BndmConfig> {
+        self.clock += 1;
+        let key = (search_pattern.to_vec(), wildcard);
+        if let Some(entry) = self.entries.get_mut(&key) {
+            entry.last_used = self.clock;
+            return entry.config.clone();
+        }
+
+        let config = Arc::new(BndmConfig::new(search_pattern, wildcard));
+        if self.capacity > 0 {
+            if self.entries.len() >= self.capacity {
+                self.evict_least_recently_used();
+            }
+            self.entries.insert(key, CacheEntry { config: config.clone(), last_used: self.clock });
+        }
+        config
+    }
+
+    /// Returns whether the configuration for the pattern is in the cache, without marking it
+    /// as used.
+    pub fn contains(&self, search_pattern: &[u8], wildcard: Option<u8>) -> bool {
+        self.entries.contains_key(&(search_pattern.to_vec(), wildcard))
+    }
+
+    /// Returns the number of configurations in the cache.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether the cache contains no configurations.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the maximum number of configurations the cache keeps.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Removes all configurations from the cache.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    fn evict_least_recently_used(&mut self) {
+        let oldest = self.entries.iter()
+            .min_by_key(|(_, entry)| entry.last_used)
+            .map(|(key, _)| key.clone());
+        if let Some(key) = oldest {
+            self.entries.remove(&key);
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "./cache_test.rs"]
+mod cache_test;
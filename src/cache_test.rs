@@ -0,0 +1,45 @@
+use super::*;
+
+#[test]
+fn cache_reuses_configs() {
+    let mut cache = ConfigCache::new(2);
+    let config = cache.get(b"fox", None);
+
+    assert!(Arc::ptr_eq(&config, &cache.get(b"fox", None)));
+    assert!(!Arc::ptr_eq(&config, &cache.get(b"fox", Some(b'?'))));
+    assert_eq!(*config, BndmConfig::new(b"fox", None));
+    assert_eq!(cache.len(), 2);
+}
+
+#[test]
+fn cache_evicts_least_recently_used() {
+    let mut cache = ConfigCache::new(2);
+    cache.get(b"fox", None);
+    cache.get(b"dog", None);
+    cache.get(b"fox", None);
+    cache.get(b"cat", None);
+
+    assert!(cache.contains(b"fox", None));
+    assert!(!cache.contains(b"dog", None));
+    assert!(cache.contains(b"cat", None));
+    assert_eq!(cache.len(), 2);
+}
+
+#[test]
+fn cache_without_capacity() {
+    let mut cache = ConfigCache::new(0);
+    let config = cache.get(b"fox", None);
+
+    assert_eq!(config.pattern, b"fox");
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn cache_clear() {
+    let mut cache = ConfigCache::new(4);
+    cache.get(b"fox", None);
+    cache.clear();
+
+    assert!(cache.is_empty());
+    assert_eq!(cache.capacity(), 4);
+}
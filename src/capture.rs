@@ -0,0 +1,104 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Patterns with capturing wildcards, of which all positions of a group should match the same
+//! byte, e.g. for length or checksum fields that are repeated.
+
+use crate::{BndmConfig, find_all_verified, find_pattern_verified};
+
+/// The `CapturePattern` struct holds a pattern in which groups of wildcard positions should
+/// match the same, unknown byte.
+///
+/// The pattern is searched for with plain wildcards at the positions of the groups, and the
+/// equality of the bytes of every group is verified for every candidate.
+pub struct CapturePattern {
+    config: BndmConfig,
+    groups: Vec<Vec<usize>>
+}
+
+impl CapturePattern {
+    /// Creates a new `CapturePattern` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration for the BNDM search, in which every position of the
+    ///   groups should match any byte.
+    /// * `groups` - The positions of the pattern for every group that should match the same
+    ///   byte.
+    ///
+    /// # Returns
+    ///
+    /// * `CapturePattern` - A new `CapturePattern` instance.
+    pub fn new(config: BndmConfig, groups: Vec<Vec<usize>>) -> CapturePattern {
+        CapturePattern { config, groups }
+    }
+
+    /// Parses a pattern in which the wildcard character followed by a digit from `1` to `9`
+    /// is a capturing wildcard. All capturing wildcards with the same digit match the same
+    /// byte. A wildcard character followed by any other byte matches a single byte.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The pattern with capturing wildcards.
+    /// * `wildcard` - The wildcard character.
+    ///
+    /// # Returns
+    ///
+    /// * `CapturePattern` - A new `CapturePattern` instance.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::CapturePattern;
+    ///
+    /// let pattern = CapturePattern::parse(b"L?1:?:?1", b'?');
+    /// assert_eq!(pattern.find(b"L3:x:4 L5:x:5"), Some(7));
+    /// ```
+    pub fn parse(pattern: &[u8], wildcard: u8) -> CapturePattern {
+        let mut search_pattern = Vec::with_capacity(pattern.len());
+        let mut groups = vec![Vec::new(); 9];
+        let mut index = 0;
+
+        while index < pattern.len() {
+            let byte = pattern[index];
+            match pattern.get(index + 1) {
+                Some(&digit @ b'1'..=b'9') if byte == wildcard => {
+                    groups[(digit - b'1') as usize].push(search_pattern.len());
+                    index += 2;
+                }
+                _ => index += 1
+            }
+            search_pattern.push(byte);
+        }
+
+        groups.retain(|positions| positions.len() > 1);
+        CapturePattern::new(BndmConfig::new(&search_pattern, Some(wildcard)), groups)
+    }
+
+    /// Returns the configuration that is used to search for candidates.
+    pub fn config(&self) -> &BndmConfig {
+        &self.config
+    }
+
+    /// Returns the index of the first occurrence of the pattern in the source, or `None` if
+    /// the pattern is not found.
+    pub fn find(&self, source: &[u8]) -> Option<usize> {
+        find_pattern_verified(source, &self.config, |source, index| self.is_captured(source, index))
+    }
+
+    /// Returns the indexes of all non-overlapping occurrences of the pattern in the source.
+    pub fn find_all(&self, source: &[u8]) -> Vec<usize> {
+        find_all_verified(source, &self.config, |source, index| self.is_captured(source, index))
+    }
+
+    fn is_captured(&self, source: &[u8], index: usize) -> bool {
+        self.groups.iter().all(|positions| {
+            let mut bytes = positions.iter().map(|&position| source.get(index + position));
+            bytes.next().is_none_or(|first| bytes.all(|byte| byte == first))
+        })
+    }
+}
+
+#[cfg(test)]
+#[path = "./capture_test.rs"]
+mod capture_test;
@@ -0,0 +1,56 @@
+use super::*;
+
+#[test]
+fn parse_capture_groups() {
+    let pattern = CapturePattern::parse(b"?1?2-?2?1", b'?');
+
+    assert_eq!(pattern.config().pattern, b"??-??");
+    assert_eq!(pattern.find(b"ab-cd ab-ab ab-ba"), Some(12));
+    assert_eq!(pattern.find(b"ab-ab"), None);
+}
+
+#[test]
+fn plain_wildcards_are_not_captured() {
+    let pattern = CapturePattern::parse(b"?1?-?1?", b'?');
+
+    assert_eq!(pattern.find(b"xy-xz"), Some(0));
+    assert_eq!(pattern.find(b"xy-zy"), None);
+}
+
+#[test]
+fn single_capture_matches_any_byte() {
+    let pattern = CapturePattern::parse(b"a?1b", b'?');
+
+    assert_eq!(pattern.find(b"axb"), Some(0));
+}
+
+#[test]
+fn overlapping_candidates() {
+    let pattern = CapturePattern::parse(b"?1?1", b'?');
+
+    assert_eq!(pattern.find(b"abccd"), Some(2));
+    assert_eq!(pattern.find_all(b"aabbbcd"), vec![0, 2]);
+}
+
+#[test]
+fn new_with_groups() {
+    let config = BndmConfig::new(b"len=? ?", Some(b'?'));
+    let pattern = CapturePattern::new(config, vec![vec![4, 6]]);
+
+    assert_eq!(pattern.find(b"len=3 4 len=5 5"), Some(8));
+}
+
+#[test]
+fn captures_beyond_word_size() {
+    let mut pattern = b"?1".to_vec();
+    pattern.extend_from_slice(&[b'-'; 70]);
+    pattern.extend_from_slice(b"?1");
+    let pattern = CapturePattern::parse(&pattern, b'?');
+
+    let mut source = vec![b'-'; 80];
+    source[2] = b'x';
+    source[73] = b'y';
+    assert_eq!(pattern.find(&source), None);
+    source[73] = b'x';
+    assert_eq!(pattern.find(&source), Some(2));
+}
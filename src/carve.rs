@@ -0,0 +1,115 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Carving of files from raw data, such as disk images, by their header and footer.
+
+use std::ops::Range;
+
+use crate::{BndmConfig, find_pattern_from};
+
+/// A preset that describes how to carve files of a file format by their header and footer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CarvePreset {
+    /// The name of the file format.
+    pub format: &'static str,
+
+    /// The common file extension of the file format.
+    pub extension: &'static str,
+
+    /// The header at the start of the file.
+    pub header: &'static [u8],
+
+    /// The footer at the end of the file. If `None`, files are carved up to the maximum size.
+    pub footer: Option<&'static [u8]>,
+
+    /// The number of bytes that follow the footer and belong to the file.
+    pub footer_trailer: usize,
+
+    /// The maximum size of a file.
+    pub max_size: usize
+}
+
+const MAX_SIZE: usize = 64 * 1024 * 1024;
+
+/// Carving preset for JPEG images, from the start of image marker to the end of image marker.
+pub const JPEG: CarvePreset = CarvePreset {
+    format: "JPEG", extension: "jpg", header: b"\xff\xd8\xff", footer: Some(b"\xff\xd9"), footer_trailer: 0, max_size: MAX_SIZE
+};
+
+/// Carving preset for PNG images, from the signature to the end of the `IEND` chunk.
+pub const PNG: CarvePreset = CarvePreset {
+    format: "PNG", extension: "png", header: b"\x89PNG\r\n\x1a\n", footer: Some(b"IEND\xae\x42\x60\x82"), footer_trailer: 0, max_size: MAX_SIZE
+};
+
+/// Carving preset for PDF documents, from the `%PDF-` header to the first `%%EOF` marker.
+pub const PDF: CarvePreset = CarvePreset {
+    format: "PDF", extension: "pdf", header: b"%PDF-", footer: Some(b"%%EOF"), footer_trailer: 0, max_size: MAX_SIZE
+};
+
+/// Carving preset for ZIP archives, from the first local file header to the end of central
+/// directory record, excluding the archive comment.
+pub const ZIP: CarvePreset = CarvePreset {
+    format: "ZIP", extension: "zip", header: b"PK\x03\x04", footer: Some(b"PK\x05\x06"), footer_trailer: 18, max_size: MAX_SIZE
+};
+
+/// Carving preset for GZIP streams. GZIP streams have no footer marker, so they are carved up
+/// to the maximum size.
+pub const GZIP: CarvePreset = CarvePreset {
+    format: "GZIP", extension: "gz", header: b"\x1f\x8b\x08", footer: None, footer_trailer: 0, max_size: 16 * 1024 * 1024
+};
+
+/// The built-in carving presets.
+pub const CARVE_PRESETS: &[CarvePreset] = &[JPEG, PNG, PDF, ZIP, GZIP];
+
+/// Carves files of the file format described by the preset from the source.
+///
+/// For every header, the first footer within the maximum size is searched for. Headers without
+/// a footer within the maximum size are skipped. Carved files do not overlap; searching for the
+/// next header continues at the end of the carved file.
+///
+/// # Arguments
+///
+/// * `source` - The source to carve files from.
+/// * `preset` - The preset of the file format to carve.
+///
+/// # Returns
+///
+/// * `Vec<Range<usize>>` - Returns the ranges of the carved files in the source.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{carve, PDF};
+///
+/// let source = b"\x00\x00%PDF-1.4 ... %%EOF\x00\x00";
+/// assert_eq!(carve(source, &PDF), vec![2..20]);
+/// ```
+pub fn carve(source: &[u8], preset: &CarvePreset) -> Vec<Range<usize>> {
+    let header_config = BndmConfig::new(preset.header, None);
+    let footer_config = preset.footer.map(|footer| BndmConfig::new(footer, None));
+    let mut files = Vec::new();
+    let mut start_index = 0;
+
+    while let Some(start) = find_pattern_from(source, &header_config, start_index) {
+        let limit = source.len().min(start.saturating_add(preset.max_size));
+        let end = match &footer_config {
+            Some(footer_config) => find_pattern_from(&source[..limit], footer_config, start + preset.header.len())
+                .map(|index| index + footer_config.pattern.len() + preset.footer_trailer)
+                .filter(|&end| end <= limit),
+            None => Some(limit)
+        };
+
+        match end {
+            Some(end) => {
+                files.push(start..end);
+                start_index = end;
+            }
+            None => start_index = start + 1
+        }
+    }
+    files
+}
+
+#[cfg(test)]
+#[path = "./carve_test.rs"]
+mod carve_test;
@@ -0,0 +1,60 @@
+use super::*;
+
+#[test]
+fn carve_jpeg() {
+    let source = b"....\xff\xd8\xff\xe0JFIF....\xff\xd9....\xff\xd8\xff\xdbdata\xff\xd9";
+    let files = carve(source, &JPEG);
+
+    assert_eq!(files, vec![4..18, 22..32]);
+}
+
+#[test]
+fn carve_png() {
+    let source = b"xx\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR....\x00\x00\x00\x00IEND\xae\x42\x60\x82xx";
+    let files = carve(source, &PNG);
+
+    assert_eq!(files, vec![2..34]);
+}
+
+#[test]
+fn carve_zip_with_trailer() {
+    let mut source = b"..PK\x03\x04local....PK\x01\x02central..PK\x05\x06".to_vec();
+    source.extend_from_slice(&[0; 18]);
+    source.extend_from_slice(b"..");
+    let files = carve(&source, &ZIP);
+
+    assert_eq!(files, vec![2..50]);
+}
+
+#[test]
+fn carve_zip_truncated_trailer() {
+    let source = b"..PK\x03\x04local....PK\x05\x06\x00\x00";
+    let files = carve(source, &ZIP);
+
+    assert_eq!(files, vec![]);
+}
+
+#[test]
+fn carve_gzip_up_to_max_size() {
+    let preset = CarvePreset { max_size: 8, ..GZIP };
+    let source = b"..\x1f\x8b\x08\x00\x00\x00\x00\x00\x00\x00\x1f\x8b\x08\x00";
+    let files = carve(source, &preset);
+
+    assert_eq!(files, vec![2..10, 12..16]);
+}
+
+#[test]
+fn carve_footer_beyond_max_size() {
+    let preset = CarvePreset { max_size: 16, ..PDF };
+    let source = b"%PDF-1.7 long document %%EOF %PDF-1.7 %%EOF";
+    let files = carve(source, &preset);
+
+    assert_eq!(files, vec![29..43]);
+}
+
+#[test]
+fn carve_presets() {
+    let formats: Vec<&str> = CARVE_PRESETS.iter().map(|preset| preset.format).collect();
+
+    assert_eq!(formats, vec!["JPEG", "PNG", "PDF", "ZIP", "GZIP"]);
+}
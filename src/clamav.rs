@@ -0,0 +1,212 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! A subset of ClamAV body signatures with wildcards, gaps and alternatives, e.g.
+//! `4d5a??{2-4}(5045|4e45)*cafe`.
+
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+use crate::hex::parse_hex_byte;
+use crate::yara::{Token, TokenSearch, add_len_bounds, parse_jump_bounds};
+use crate::ByteClass;
+
+/// The error type for parsing ClamAV body signatures.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ClamAvError {
+    /// The signature has no bytes.
+    Empty,
+
+    /// A character at the given position is not valid in a signature.
+    InvalidCharacter(usize),
+
+    /// The gap at the given position is malformed, has a minimum larger than its maximum, is
+    /// at the start or end of a sequence, or makes the length of the signature overflow.
+    InvalidJump(usize),
+
+    /// The signature ended unexpectedly.
+    UnexpectedEnd
+}
+
+impl fmt::Display for ClamAvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClamAvError::Empty => write!(f, "signature is empty"),
+            ClamAvError::InvalidCharacter(position) => write!(f, "invalid character at position {position}"),
+            ClamAvError::InvalidJump(position) => write!(f, "invalid gap at position {position}"),
+            ClamAvError::UnexpectedEnd => write!(f, "unexpected end of signature")
+        }
+    }
+}
+
+impl Error for ClamAvError {}
+
+/// The `ClamAvSignature` struct holds a compiled ClamAV body signature.
+///
+/// The longest run of bytes outside of gaps and alternatives is searched for with BNDM, and
+/// the complete signature is verified at the start positions that the run allows.
+pub struct ClamAvSignature {
+    search: TokenSearch
+}
+
+impl FromStr for ClamAvSignature {
+    type Err = ClamAvError;
+
+    /// Parses a ClamAV body signature.
+    ///
+    /// Bytes are written as two hexadecimal digits, either of which can be `?`. Gaps are
+    /// written as `*`, `{4}`, `{2-4}`, `{-4}`, `{2-}` or `[2-4]`, alternatives as
+    /// `(aa|bbcc)`, and `!(aa|bb)` matches any byte except the given bytes.
+    fn from_str(signature: &str) -> Result<ClamAvSignature, ClamAvError> {
+        let mut parser = Parser { bytes: signature.as_bytes(), position: 0 };
+        let tokens = parser.parse_sequence(false)?;
+        Ok(ClamAvSignature { search: TokenSearch::new(tokens) })
+    }
+}
+
+impl ClamAvSignature {
+    /// Returns the range of the first occurrence of the signature in the source, or `None` if
+    /// the signature is not found.
+    ///
+    /// Gaps match as few bytes as possible and alternatives are tried from left to right.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::ClamAvSignature;
+    ///
+    /// let signature: ClamAvSignature = "4d5a{2-4}(5045|4e45)".parse().unwrap();
+    /// assert_eq!(signature.find(b"MZ.PE MZ...NE"), Some(6..13));
+    /// ```
+    pub fn find(&self, source: &[u8]) -> Option<Range<usize>> {
+        self.search.find_from(source, 0)
+    }
+
+    /// Returns the range of the first occurrence of the signature in the source that starts
+    /// at or after the start index, or `None` if the signature is not found.
+    pub fn find_from(&self, source: &[u8], start_index: usize) -> Option<Range<usize>> {
+        self.search.find_from(source, start_index)
+    }
+
+    /// Returns the ranges of all non-overlapping occurrences of the signature in the source.
+    pub fn find_all(&self, source: &[u8]) -> Vec<Range<usize>> {
+        self.search.find_all(source)
+    }
+}
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    position: usize
+}
+
+impl Parser<'_> {
+    fn parse_sequence(&mut self, nested: bool) -> Result<Vec<Token>, ClamAvError> {
+        let mut tokens = Vec::new();
+        let mut last_jump = None;
+        let mut bounds = (0, Some(0));
+
+        loop {
+            let position = self.position;
+            let token = match self.bytes.get(position) {
+                None if nested => return Err(ClamAvError::UnexpectedEnd),
+                None => break,
+                Some(b'|' | b')') if nested => break,
+                Some(b'*' | b'{' | b'[') => {
+                    let jump = self.parse_jump()?;
+                    if tokens.is_empty() {
+                        return Err(ClamAvError::InvalidJump(position));
+                    }
+                    last_jump = Some(position);
+                    jump
+                }
+                Some(b'(') => self.parse_alternatives()?,
+                Some(b'!') if self.bytes.get(position + 1) == Some(&b'(') => {
+                    self.position += 1;
+                    self.parse_negated_alternatives(position)?
+                }
+                Some(_) => self.parse_byte()?
+            };
+            if !matches!(token, Token::Jump(..)) {
+                last_jump = None;
+            }
+            bounds = token.len_bounds().and_then(|token_bounds| add_len_bounds(bounds, token_bounds))
+                .ok_or(ClamAvError::InvalidJump(position))?;
+            tokens.push(token);
+        }
+
+        match (tokens.is_empty(), last_jump) {
+            (_, Some(position)) => Err(ClamAvError::InvalidJump(position)),
+            (true, _) if nested => Err(ClamAvError::InvalidCharacter(self.position)),
+            (true, _) => Err(ClamAvError::Empty),
+            _ => Ok(tokens)
+        }
+    }
+
+    fn parse_alternatives(&mut self) -> Result<Token, ClamAvError> {
+        let mut alternatives = Vec::new();
+        loop {
+            self.position += 1;
+            alternatives.push(self.parse_sequence(true)?);
+            if self.bytes.get(self.position) == Some(&b')') {
+                break;
+            }
+        }
+        self.position += 1;
+
+        if alternatives.len() < 2 {
+            return Err(ClamAvError::InvalidCharacter(self.position - 1));
+        }
+        Ok(Token::Alternatives(alternatives))
+    }
+
+    fn parse_negated_alternatives(&mut self, start: usize) -> Result<Token, ClamAvError> {
+        let Token::Alternatives(alternatives) = self.parse_alternatives()? else {
+            return Err(ClamAvError::InvalidCharacter(start));
+        };
+
+        alternatives.iter()
+            .map(|alternative| match alternative.as_slice() {
+                [Token::Class(class)] if class.len() == 1 => Some(*class),
+                _ => None
+            })
+            .try_fold(ByteClass::empty(), |union, class| class.map(|class| union.union(class)))
+            .map(|union| Token::Class(union.complement()))
+            .ok_or(ClamAvError::InvalidCharacter(start))
+    }
+
+    fn parse_jump(&mut self) -> Result<Token, ClamAvError> {
+        let start = self.position;
+        let close = match self.bytes[start] {
+            b'*' => {
+                self.position += 1;
+                return Ok(Token::Jump(0, None));
+            }
+            b'{' => b'}',
+            _ => b']'
+        };
+        let end = self.bytes[start..].iter().position(|&byte| byte == close)
+            .ok_or(ClamAvError::UnexpectedEnd)? + start;
+        self.position = end + 1;
+
+        let (min, max) = std::str::from_utf8(&self.bytes[start + 1..end]).ok()
+            .and_then(parse_jump_bounds)
+            .ok_or(ClamAvError::InvalidJump(start))?;
+        Ok(Token::Jump(min, max))
+    }
+
+    fn parse_byte(&mut self) -> Result<Token, ClamAvError> {
+        let start = self.position;
+        let digits = self.bytes.get(start..start + 2).ok_or(ClamAvError::UnexpectedEnd)?;
+        let (value, mask) = std::str::from_utf8(digits).ok()
+            .and_then(parse_hex_byte)
+            .ok_or(ClamAvError::InvalidCharacter(start))?;
+        self.position += 2;
+        Ok(Token::Class(ByteClass::masked(value, mask)))
+    }
+}
+
+#[cfg(test)]
+#[path = "./clamav_test.rs"]
+mod clamav_test;
@@ -0,0 +1,93 @@
+use super::*;
+
+fn parse(signature: &str) -> ClamAvSignature {
+    signature.parse().unwrap()
+}
+
+fn parse_error(signature: &str) -> Option<ClamAvError> {
+    signature.parse::<ClamAvSignature>().err()
+}
+
+#[test]
+fn plain_bytes_and_wildcards() {
+    let signature = parse("4d5a??9?");
+
+    assert_eq!(signature.find(b"xMZ\x00\x90 MZ\x01\x9f"), Some(1..5));
+    assert_eq!(signature.find(b"MZ\x00\xa0"), None);
+}
+
+#[test]
+fn gaps() {
+    let signature = parse("aa{2}bb{1-3}cc{-1}dd");
+
+    assert_eq!(signature.find(b"\xaa..\xbb.\xcc\xdd"), Some(0..7));
+    assert_eq!(signature.find(b"\xaa..\xbb...\xcc.\xdd"), Some(0..10));
+    assert_eq!(signature.find(b"\xaa..\xbb....\xcc\xdd"), None);
+}
+
+#[test]
+fn unbounded_gaps() {
+    let signature = parse("aa*bb{2-}cc");
+
+    assert_eq!(signature.find(b"\xaa\xbb.\xcc"), None);
+    assert_eq!(signature.find(b"\xaa.....\xbb..\xcc"), Some(0..10));
+}
+
+#[test]
+fn bracket_gaps() {
+    let signature = parse("aa[1-2]bb");
+
+    assert_eq!(signature.find(b"\xaa\xbb \xaa..\xbb"), Some(3..7));
+}
+
+#[test]
+fn alternatives() {
+    let signature = parse("50(45|4b03|4e)");
+
+    assert_eq!(signature.find(b"P PK\x03"), Some(2..5));
+    assert_eq!(signature.find_all(b"PE PN PK\x04"), vec![0..2, 3..5]);
+    assert_eq!(signature.find_from(b"PE PN", 1), Some(3..5));
+}
+
+#[test]
+fn negated_alternatives() {
+    let signature = parse("41!(42|43)44");
+
+    assert_eq!(signature.find(b"ABD ACD AED"), Some(8..11));
+}
+
+#[test]
+fn long_anchor() {
+    let signature = parse(&format!("01{{1-2}}{}", "ab".repeat(70)));
+
+    let mut source = vec![0xab; 80];
+    assert_eq!(signature.find(&source), None);
+    source[3] = 0x01;
+    assert_eq!(signature.find(&source), Some(3..75));
+}
+
+#[test]
+fn invalid_signatures() {
+    assert_eq!(parse_error(""), Some(ClamAvError::Empty));
+    assert_eq!(parse_error("4g"), Some(ClamAvError::InvalidCharacter(0)));
+    assert_eq!(parse_error("4d 5a"), Some(ClamAvError::InvalidCharacter(2)));
+    assert_eq!(parse_error("4d5"), Some(ClamAvError::UnexpectedEnd));
+    assert_eq!(parse_error("*4d"), Some(ClamAvError::InvalidJump(0)));
+    assert_eq!(parse_error("4d{2}"), Some(ClamAvError::InvalidJump(2)));
+    assert_eq!(parse_error("4d{4-2}5a"), Some(ClamAvError::InvalidJump(2)));
+    assert_eq!(parse_error("4d{18446744073709551615}5a{5}4d"), Some(ClamAvError::InvalidJump(2)));
+    assert_eq!(parse_error("4d{}5a"), Some(ClamAvError::InvalidJump(2)));
+    assert_eq!(parse_error("4d{2"), Some(ClamAvError::UnexpectedEnd));
+    assert_eq!(parse_error("4d(5a|)"), Some(ClamAvError::InvalidCharacter(6)));
+    assert_eq!(parse_error("4d(5a|00"), Some(ClamAvError::UnexpectedEnd));
+    assert_eq!(parse_error("4d!(5a|0000)"), Some(ClamAvError::InvalidCharacter(2)));
+    assert_eq!(parse_error("4d!(5a|0?)"), Some(ClamAvError::InvalidCharacter(2)));
+    assert_eq!(ClamAvError::InvalidJump(3).to_string(), "invalid gap at position 3");
+}
+
+#[test]
+fn many_gaps_without_match() {
+    let signature: ClamAvSignature = ("41{0-30}".repeat(8) + "42").parse().unwrap();
+
+    assert_eq!(signature.find(&[0x41; 2000]), None);
+}
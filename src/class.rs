@@ -0,0 +1,317 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Classes of bytes per position of a pattern, for patterns that mix exact and looser
+//! positions.
+
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+
+use crate::{BndmConfig, MASKS_TABLE_SIZE, get_pattern_length_within_cpu_word, log_compiled};
+
+/// The `ByteClass` struct is a set of bytes that a position of a pattern matches.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct ByteClass {
+    bits: [u64; 4]
+}
+
+impl ByteClass {
+    /// Creates a new `ByteClass` instance that contains no bytes.
+    pub fn empty() -> ByteClass {
+        ByteClass::default()
+    }
+
+    /// Creates a new `ByteClass` instance that contains every byte.
+    pub fn any() -> ByteClass {
+        ByteClass { bits: [u64::MAX; 4] }
+    }
+
+    /// Creates a new `ByteClass` instance that only contains the given byte.
+    pub fn byte(byte: u8) -> ByteClass {
+        ByteClass::empty().with_byte(byte)
+    }
+
+    /// Creates a new `ByteClass` instance that contains the given bytes.
+    pub fn from_bytes(bytes: &[u8]) -> ByteClass {
+        bytes.iter().fold(ByteClass::empty(), |class, &byte| class.with_byte(byte))
+    }
+
+    /// Creates a new `ByteClass` instance that contains the bytes of the range, e.g. `b'0'..=b'9'`
+    /// for the ASCII digits.
+    pub fn range(range: RangeInclusive<u8>) -> ByteClass {
+        range.fold(ByteClass::empty(), ByteClass::with_byte)
+    }
+
+    /// Creates a new `ByteClass` instance that contains the byte and, if it is an ASCII
+    /// letter, the letter in the other case.
+    pub fn ascii_case_insensitive(byte: u8) -> ByteClass {
+        ByteClass::byte(byte.to_ascii_lowercase()).with_byte(byte.to_ascii_uppercase())
+    }
+
+    /// Creates a new `ByteClass` instance that contains every byte whose bits selected by the
+    /// mask equal those of the value, e.g. a value of `$40` and a mask of `$F0` for the bytes
+    /// with a high nibble of 4.
+    pub fn masked(value: u8, mask: u8) -> ByteClass {
+        (0..=u8::MAX).filter(|&byte| byte & mask == value & mask)
+            .fold(ByteClass::empty(), ByteClass::with_byte)
+    }
+
+    /// Adds the byte to the class.
+    ///
+    /// # Arguments
+    ///
+    /// * `byte` - The byte to add.
+    ///
+    /// # Returns
+    ///
+    /// * `ByteClass` - The updated `ByteClass` instance.
+    pub fn with_byte(mut self, byte: u8) -> ByteClass {
+        self.bits[byte as usize / 64] |= 1 << (byte % 64);
+        self
+    }
+
+    /// Returns the class that contains the bytes of both classes.
+    pub fn union(self, other: ByteClass) -> ByteClass {
+        ByteClass { bits: std::array::from_fn(|index| self.bits[index] | other.bits[index]) }
+    }
+
+    /// Returns the class that contains every byte that is not in this class.
+    pub fn complement(self) -> ByteClass {
+        ByteClass { bits: self.bits.map(|bits| !bits) }
+    }
+
+    /// Returns whether the class contains the byte.
+    pub fn contains(&self, byte: u8) -> bool {
+        self.bits[byte as usize / 64] & (1 << (byte % 64)) != 0
+    }
+
+    /// Returns the number of bytes in the class.
+    pub fn len(&self) -> usize {
+        self.bits.iter().map(|bits| bits.count_ones() as usize).sum()
+    }
+
+    /// Returns whether the class contains no bytes.
+    pub fn is_empty(&self) -> bool {
+        self.bits == [0; 4]
+    }
+
+    /// Returns an iterator over the bytes of the class, in ascending order.
+    pub fn bytes(&self) -> impl Iterator<Item = u8> + '_ {
+        (0..=u8::MAX).filter(|&byte| self.contains(byte))
+    }
+
+    pub(crate) fn from_bits(bits: [u64; 4]) -> ByteClass {
+        ByteClass { bits }
+    }
+
+    pub(crate) fn bits(&self) -> [u64; 4] {
+        self.bits
+    }
+}
+
+/// The `PatternBuilder` struct builds a pattern from a class of bytes per position, e.g. a
+/// case-exact magic number followed by a case-insensitive keyword.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PatternBuilder {
+    classes: Vec<ByteClass>
+}
+
+impl PatternBuilder {
+    /// Creates a new `PatternBuilder` instance for an empty pattern.
+    pub fn new() -> PatternBuilder {
+        PatternBuilder::default()
+    }
+
+    /// Appends positions that only match the given bytes.
+    pub fn literal(mut self, bytes: &[u8]) -> PatternBuilder {
+        self.classes.extend(bytes.iter().map(|&byte| ByteClass::byte(byte)));
+        self
+    }
+
+    /// Appends positions that match the given bytes, where ASCII letters match both cases.
+    pub fn nocase(mut self, bytes: &[u8]) -> PatternBuilder {
+        self.classes.extend(bytes.iter().map(|&byte| ByteClass::ascii_case_insensitive(byte)));
+        self
+    }
+
+    /// Appends a position that matches any of the given bytes.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{PatternBuilder, find_pattern};
+    ///
+    /// let config = PatternBuilder::new().literal(b"end").one_of(b"\r\n").build();
+    /// assert_eq!(find_pattern(b"the end\n", &config), Some(4));
+    /// assert_eq!(find_pattern(b"endless\r", &config), None);
+    /// ```
+    pub fn one_of(self, bytes: &[u8]) -> PatternBuilder {
+        self.class(ByteClass::from_bytes(bytes))
+    }
+
+    /// Appends a position that matches any byte except the given bytes.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{PatternBuilder, find_pattern};
+    ///
+    /// let config = PatternBuilder::new().literal(b"\"").none_of(b"\"\0").literal(b"\"").build();
+    /// assert_eq!(find_pattern(b"\"\0\"\"a\"", &config), Some(3));
+    /// ```
+    pub fn none_of(self, bytes: &[u8]) -> PatternBuilder {
+        self.class(ByteClass::from_bytes(bytes).complement())
+    }
+
+    /// Appends a position that matches any byte of the range.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{PatternBuilder, find_pattern};
+    ///
+    /// let digit = b'0'..=b'9';
+    /// let config = PatternBuilder::new().literal(b"HTTP/1.1 ").range(digit.clone()).range(digit.clone()).range(digit).build();
+    /// assert_eq!(find_pattern(b"HTTP/1.1 OK HTTP/1.1 404", &config), Some(12));
+    /// ```
+    pub fn range(self, range: RangeInclusive<u8>) -> PatternBuilder {
+        self.class(ByteClass::range(range))
+    }
+
+    /// Appends a position that matches every byte whose bits selected by the mask equal those
+    /// of the value, e.g. for nibble wildcards.
+    pub fn masked(self, value: u8, mask: u8) -> PatternBuilder {
+        self.class(ByteClass::masked(value, mask))
+    }
+
+    /// Appends a position that matches any byte.
+    pub fn any(self) -> PatternBuilder {
+        self.class(ByteClass::any())
+    }
+
+    /// Appends the given number of positions that match any byte.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{PatternBuilder, find_pattern};
+    ///
+    /// let config = PatternBuilder::new().literal(b"\xe8").skip(4).literal(b"\xc3").build();
+    /// assert_eq!(find_pattern(b"\x90\xe8\x10\x20\x30\x40\xc3", &config), Some(1));
+    /// ```
+    pub fn skip(mut self, count: usize) -> PatternBuilder {
+        self.classes.extend(std::iter::repeat_n(ByteClass::any(), count));
+        self
+    }
+
+    /// Appends a position that matches the bytes of the class.
+    pub fn class(mut self, class: ByteClass) -> PatternBuilder {
+        self.classes.push(class);
+        self
+    }
+
+    /// Returns the number of positions of the pattern.
+    pub fn len(&self) -> usize {
+        self.classes.len()
+    }
+
+    /// Returns whether the pattern has no positions.
+    pub fn is_empty(&self) -> bool {
+        self.classes.is_empty()
+    }
+
+    /// Builds a `BndmConfig` instance for the pattern.
+    ///
+    /// # Returns
+    ///
+    /// * `BndmConfig` - A new `BndmConfig` instance.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{PatternBuilder, find_pattern};
+    ///
+    /// let config = PatternBuilder::new().literal(b"LOG:").any().nocase(b"error").build();
+    /// assert_eq!(find_pattern(b"log: Error LOG: ERROR", &config), Some(11));
+    /// ```
+    pub fn build(&self) -> BndmConfig {
+        BndmConfig::with_classes(&self.classes)
+    }
+}
+
+impl BndmConfig {
+    /// Creates a new `BndmConfig` instance in which every position of the pattern matches any
+    /// byte of its class.
+    ///
+    /// The pattern of the config consists of the lowest byte of every class.
+    ///
+    /// # Arguments
+    ///
+    /// * `classes` - The class of bytes for every position of the pattern.
+    ///
+    /// # Returns
+    ///
+    /// * `BndmConfig` - A new `BndmConfig` instance.
+    pub fn with_classes(classes: &[ByteClass]) -> BndmConfig {
+        let filter_len = get_pattern_length_within_cpu_word(classes.len());
+        let mut masks = [0; MASKS_TABLE_SIZE];
+        for (position, class) in classes[..filter_len].iter().enumerate() {
+            class.bytes().for_each(|byte| masks[byte as usize] |= 1 << (filter_len - 1 - position));
+        }
+
+        let config = BndmConfig {
+            masks: Arc::new(masks),
+            wildcard: None,
+            pattern: classes.iter().map(|class| class.bytes().next().unwrap_or(0)).collect(),
+            equivalence: None,
+            classes: Some(classes.to_vec())
+        };
+        log_compiled(&config, " with classes");
+        config
+    }
+
+    /// Creates a new `BndmConfig` instance in which the byte following the escape byte is
+    /// always matched literally, so that the wildcard byte itself can be searched for.
+    ///
+    /// The escape byte is not part of the pattern. An escape byte at the end of the pattern
+    /// matches itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `search_pattern` - The pattern to search for in the text.
+    /// * `wildcard` - The optional wildcard character that matches any byte of the text.
+    /// * `escape` - The byte that makes the following byte of the pattern match literally.
+    ///
+    /// # Returns
+    ///
+    /// * `BndmConfig` - A new `BndmConfig` instance.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{BndmConfig, find_pattern};
+    ///
+    /// let source = b"Why! Who?";
+    /// let config = BndmConfig::new_escaped(b"W??\\?", Some(b'?'), b'\\');
+    /// assert_eq!(find_pattern(source, &config), Some(5));
+    /// ```
+    pub fn new_escaped(search_pattern: &[u8], wildcard: Option<u8>, escape: u8) -> BndmConfig {
+        let mut classes = Vec::with_capacity(search_pattern.len());
+        let mut bytes = search_pattern.iter();
+
+        while let Some(&byte) = bytes.next() {
+            classes.push(match byte {
+                _ if byte == escape => ByteClass::byte(*bytes.next().unwrap_or(&escape)),
+                _ if Some(byte) == wildcard => ByteClass::any(),
+                _ => ByteClass::byte(byte)
+            });
+        }
+
+        BndmConfig::with_classes(&classes)
+    }
+}
+
+#[cfg(test)]
+#[path = "./class_test.rs"]
+mod class_test;
@@ -0,0 +1,220 @@
+use super::*;
+use crate::{HaystackIndex, find_pattern, matches_at, rfind_pattern};
+
+#[test]
+fn byte_class_membership() {
+    let class = ByteClass::byte(b'a').with_byte(0xff).with_byte(0);
+
+    assert!(class.contains(b'a'));
+    assert!(class.contains(0xff));
+    assert!(class.contains(0));
+    assert!(!class.contains(b'b'));
+    assert_eq!(class.len(), 3);
+    assert_eq!(class.bytes().collect::<Vec<_>>(), vec![0, b'a', 0xff]);
+}
+
+#[test]
+fn byte_class_empty_and_any() {
+    assert!(ByteClass::empty().is_empty());
+    assert_eq!(ByteClass::empty().len(), 0);
+    assert_eq!(ByteClass::any().len(), 256);
+    assert!(!ByteClass::any().is_empty());
+}
+
+#[test]
+fn byte_class_ascii_case_insensitive() {
+    assert_eq!(ByteClass::ascii_case_insensitive(b'E').bytes().collect::<Vec<_>>(), vec![b'E', b'e']);
+    assert_eq!(ByteClass::ascii_case_insensitive(b'1').bytes().collect::<Vec<_>>(), vec![b'1']);
+}
+
+#[test]
+fn builder_mixed_case_sensitivity() {
+    let config = PatternBuilder::new().literal(b"MZ").nocase(b"this").build();
+
+    assert_eq!(find_pattern(b"mzthis MZThIs", &config), Some(7));
+    assert_eq!(find_pattern(b"mZTHIS", &config), None);
+}
+
+#[test]
+fn builder_any_position() {
+    let config = PatternBuilder::new().literal(b"a").any().literal(b"c").build();
+
+    assert_eq!(find_pattern(b"xxa\x00c", &config), Some(2));
+    assert_eq!(config.wildcard_positions(), vec![1]);
+    assert!(!config.is_pure_literal());
+}
+
+#[test]
+fn builder_literal_only() {
+    let config = PatternBuilder::new().literal(b"fox").build();
+
+    assert_eq!(find_pattern(b"The quick brown fox", &config), Some(16));
+    assert!(config.is_pure_literal());
+}
+
+#[test]
+fn builder_longer_than_word() {
+    let config = PatternBuilder::new().literal(&[b'x'; 64]).literal(b"MAGIC").nocase(b"end").build();
+    let mut source = vec![b'x'; 64];
+    source.extend_from_slice(b"MAGICEnD");
+
+    assert_eq!(find_pattern(&source, &config), Some(0));
+    assert_eq!(rfind_pattern(&source, &config), Some(0));
+
+    source[65] = b'a';
+    assert_eq!(find_pattern(&source, &config), None);
+    assert_eq!(rfind_pattern(&source, &config), None);
+}
+
+#[test]
+fn builder_empty_class_never_matches() {
+    let config = PatternBuilder::new().literal(b"a").class(ByteClass::empty()).build();
+
+    assert_eq!(find_pattern(b"abacad", &config), None);
+}
+
+#[test]
+fn builder_matches_at_and_index() {
+    let source = b"Error: ERROR error";
+    let config = PatternBuilder::new().literal(b"E").nocase(b"rror").build();
+
+    assert!(matches_at(source, &config, 7));
+    assert!(!matches_at(source, &config, 13));
+    assert_eq!(HaystackIndex::build(source).query(&config), vec![0, 7]);
+}
+
+#[test]
+fn builder_len() {
+    let builder = PatternBuilder::new();
+    assert!(builder.is_empty());
+
+    let builder = builder.literal(b"ab").any();
+    assert_eq!(builder.len(), 3);
+}
+
+#[test]
+fn byte_class_from_bytes() {
+    let class = ByteClass::from_bytes(b"\r\n\n");
+
+    assert_eq!(class.len(), 2);
+    assert!(class.contains(b'\r'));
+    assert!(class.contains(b'\n'));
+    assert_eq!(ByteClass::from_bytes(b""), ByteClass::empty());
+}
+
+#[test]
+fn builder_one_of() {
+    let config = PatternBuilder::new().one_of(&[0x0a, 0x0d]).literal(b"OK").build();
+
+    assert_eq!(find_pattern(b"OK\rOK", &config), Some(2));
+    assert_eq!(find_pattern(b"OK OK\nOK", &config), Some(5));
+    assert_eq!(find_pattern(b" OK", &config), None);
+}
+
+#[test]
+fn builder_one_of_longer_than_word() {
+    let config = PatternBuilder::new().literal(&[b'-'; 64]).one_of(b"+*").build();
+    let mut source = vec![b'-'; 65];
+
+    assert_eq!(find_pattern(&source, &config), None);
+    source[64] = b'*';
+    assert_eq!(find_pattern(&source, &config), Some(0));
+}
+
+#[test]
+fn byte_class_complement() {
+    let class = ByteClass::from_bytes(b"\0\xff").complement();
+
+    assert_eq!(class.len(), 254);
+    assert!(!class.contains(0));
+    assert!(!class.contains(0xff));
+    assert!(class.contains(b'a'));
+    assert_eq!(ByteClass::empty().complement(), ByteClass::any());
+    assert_eq!(class.complement(), ByteClass::from_bytes(b"\0\xff"));
+}
+
+#[test]
+fn builder_none_of() {
+    let config = PatternBuilder::new().literal(b"=").none_of(b"\0").build();
+
+    assert_eq!(find_pattern(b"=\0=1", &config), Some(2));
+    assert_eq!(find_pattern(b"=\0", &config), None);
+}
+
+#[test]
+fn builder_none_of_longer_than_word() {
+    let config = PatternBuilder::new().literal(&[b'a'; 64]).none_of(b"\0").build();
+    let mut source = vec![b'a'; 64];
+    source.push(0);
+
+    assert_eq!(find_pattern(&source, &config), None);
+    source[64] = b'b';
+    assert_eq!(find_pattern(&source, &config), Some(0));
+}
+
+#[test]
+fn byte_class_range() {
+    let digits = ByteClass::range(b'0'..=b'9');
+
+    assert_eq!(digits.len(), 10);
+    assert!(digits.contains(b'0'));
+    assert!(digits.contains(b'9'));
+    assert!(!digits.contains(b'/'));
+    assert!(!digits.contains(b':'));
+    assert_eq!(ByteClass::range(0..=255), ByteClass::any());
+    #[allow(clippy::reversed_empty_ranges)]
+    let empty = ByteClass::range(9..=0);
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn byte_class_union() {
+    let hex_digits = ByteClass::range(b'0'..=b'9').union(ByteClass::range(b'a'..=b'f'));
+
+    assert_eq!(hex_digits.len(), 16);
+    assert!(hex_digits.contains(b'c'));
+    assert!(!hex_digits.contains(b'g'));
+}
+
+#[test]
+fn builder_range() {
+    let config = PatternBuilder::new().range(b'0'..=b'9').literal(b".").range(b'0'..=b'9').build();
+
+    assert_eq!(find_pattern(b"v1.x 2.5", &config), Some(5));
+}
+
+#[test]
+fn new_escaped_literal_wildcard() {
+    let config = BndmConfig::new_escaped(b"a\\??", Some(b'?'), b'\\');
+
+    assert_eq!(find_pattern(b"abc a?c", &config), Some(4));
+    assert_eq!(config.wildcard_positions(), vec![2]);
+}
+
+#[test]
+fn new_escaped_escape_byte() {
+    let config = BndmConfig::new_escaped(b"a\\\\b\\", None, b'\\');
+
+    assert_eq!(find_pattern(b"a\\b a\\b\\", &config), Some(4));
+}
+
+#[test]
+fn new_escaped_long_pattern() {
+    let mut pattern = vec![b'?'; 70];
+    pattern.extend_from_slice(b"\\?");
+    let config = BndmConfig::new_escaped(&pattern, Some(b'?'), b'\\');
+
+    let mut source = vec![b'x'; 100];
+    assert_eq!(find_pattern(&source, &config), None);
+    source[80] = b'?';
+    assert_eq!(find_pattern(&source, &config), Some(10));
+}
+
+#[test]
+fn builder_skip() {
+    let config = PatternBuilder::new().literal(b"a").skip(3).literal(b"b").build();
+
+    assert_eq!(config.wildcard_positions(), vec![1, 2, 3]);
+    assert_eq!(find_pattern(b"a12b a123b", &config), Some(5));
+    assert_eq!(PatternBuilder::new().skip(0).len(), 0);
+}
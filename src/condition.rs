@@ -0,0 +1,336 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Boolean conditions over the occurrences of named patterns in a text.
+//!
+//! A condition combines named patterns with `&&`, `||` and `!`, and can compare the number
+//! of occurrences of a pattern with `#name`, e.g. `mz && (pe || elf) && !upx && #call >= 3`.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{BndmConfig, MultiBndmConfig};
+
+const MAX_DEPTH: usize = 256;
+
+/// A comparison between the number of occurrences of a pattern and a value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual
+}
+
+impl Comparison {
+    fn compare(self, count: usize, value: usize) -> bool {
+        match self {
+            Comparison::Equal => count == value,
+            Comparison::NotEqual => count != value,
+            Comparison::Less => count < value,
+            Comparison::LessOrEqual => count <= value,
+            Comparison::Greater => count > value,
+            Comparison::GreaterOrEqual => count >= value
+        }
+    }
+}
+
+/// A boolean condition over the occurrences of named patterns.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Condition {
+    /// The named pattern occurs at least once.
+    Matches(String),
+
+    /// The number of occurrences of the named pattern compares to the value.
+    Count(String, Comparison, usize),
+
+    /// The condition does not hold.
+    Not(Box<Condition>),
+
+    /// Both conditions hold.
+    And(Box<Condition>, Box<Condition>),
+
+    /// At least one of the conditions holds.
+    Or(Box<Condition>, Box<Condition>)
+}
+
+/// The error type for parsing and evaluating conditions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConditionError {
+    /// A character at the given position is not valid in a condition.
+    InvalidCharacter(usize),
+
+    /// A token at the given position was not expected.
+    UnexpectedToken(usize),
+
+    /// The condition ended unexpectedly.
+    UnexpectedEnd,
+
+    /// The condition refers to a pattern name that was not provided.
+    UnknownPattern(String),
+
+    /// The condition nests more than 256 levels deep at the given position, counting
+    /// parentheses, negations and chained operators.
+    TooDeep(usize)
+}
+
+impl fmt::Display for ConditionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConditionError::InvalidCharacter(position) => write!(f, "invalid character at position {position}"),
+            ConditionError::UnexpectedToken(position) => write!(f, "unexpected token at position {position}"),
+            ConditionError::UnexpectedEnd => write!(f, "unexpected end of condition"),
+            ConditionError::UnknownPattern(name) => write!(f, "unknown pattern '{name}'"),
+            ConditionError::TooDeep(position) => write!(f, "condition nests too deep at position {position}")
+        }
+    }
+}
+
+impl Error for ConditionError {}
+
+impl FromStr for Condition {
+    type Err = ConditionError;
+
+    fn from_str(expression: &str) -> Result<Condition, ConditionError> {
+        let mut parser = Parser { tokens: tokenize(expression)?, position: 0, depth: 0 };
+        let (condition, _) = parser.parse_or()?;
+
+        match parser.tokens.get(parser.position) {
+            Some((position, _)) => Err(ConditionError::UnexpectedToken(*position)),
+            None => Ok(condition)
+        }
+    }
+}
+
+impl Condition {
+    /// Evaluates the condition over the source string.
+    ///
+    /// All pattern names are checked first. The occurrences of every pattern the condition
+    /// refers to are then counted in a single pass over the source with a `MultiBndmConfig`,
+    /// after which the condition is evaluated without searching again.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source string to evaluate the condition over.
+    /// * `patterns` - The names and configurations of the patterns the condition refers to.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<bool, ConditionError>` - Returns whether the condition holds, or an error if
+    ///   the condition refers to a pattern name that is not provided.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{BndmConfig, Condition};
+    ///
+    /// let source = b"The quick brown fox jumps over the lazy dog";
+    /// let fox = BndmConfig::new(b"fox", None);
+    /// let cat = BndmConfig::new(b"cat", None);
+    /// let o = BndmConfig::new(b"o", None);
+    /// let condition: Condition = "fox && !cat && #o >= 4".parse().unwrap();
+    /// let result = condition.evaluate(source, &[("fox", &fox), ("cat", &cat), ("o", &o)]);
+    /// assert_eq!(result, Ok(true));
+    /// ```
+    pub fn evaluate(&self, source: &[u8], patterns: &[(&str, &BndmConfig)]) -> Result<bool, ConditionError> {
+        let mut referenced = Vec::new();
+        self.collect_patterns(patterns, &mut referenced)?;
+
+        let config = MultiBndmConfig::new(referenced.iter().map(|&index| patterns[index].1.clone()).collect());
+        let mut counts = vec![0; patterns.len()];
+        referenced.iter().zip(config.count_overlapping(source)).for_each(|(&index, count)| counts[index] = count);
+
+        Ok(self.holds(patterns, &counts))
+    }
+
+    fn collect_patterns(&self, patterns: &[(&str, &BndmConfig)], referenced: &mut Vec<usize>) -> Result<(), ConditionError> {
+        match self {
+            Condition::Matches(name) | Condition::Count(name, _, _) => {
+                let index = find_pattern_index(patterns, name)?;
+                if !referenced.contains(&index) {
+                    referenced.push(index);
+                }
+                Ok(())
+            }
+            Condition::Not(condition) => condition.collect_patterns(patterns, referenced),
+            Condition::And(left, right) | Condition::Or(left, right) => {
+                left.collect_patterns(patterns, referenced)?;
+                right.collect_patterns(patterns, referenced)
+            }
+        }
+    }
+
+    fn holds(&self, patterns: &[(&str, &BndmConfig)], counts: &[usize]) -> bool {
+        let count = |name: &str| find_pattern_index(patterns, name).map_or(0, |index| counts[index]);
+
+        match self {
+            Condition::Matches(name) => count(name) > 0,
+            Condition::Count(name, comparison, value) => comparison.compare(count(name), *value),
+            Condition::Not(condition) => !condition.holds(patterns, counts),
+            Condition::And(left, right) => left.holds(patterns, counts) && right.holds(patterns, counts),
+            Condition::Or(left, right) => left.holds(patterns, counts) || right.holds(patterns, counts)
+        }
+    }
+}
+
+fn find_pattern_index(patterns: &[(&str, &BndmConfig)], name: &str) -> Result<usize, ConditionError> {
+    patterns.iter().position(|(pattern_name, _)| *pattern_name == name)
+        .ok_or_else(|| ConditionError::UnknownPattern(name.to_owned()))
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Name(String),
+    Number(usize),
+    Count,
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+    Comparison(Comparison)
+}
+
+fn tokenize(expression: &str) -> Result<Vec<(usize, Token)>, ConditionError> {
+    let bytes = expression.as_bytes();
+    let mut tokens = Vec::new();
+    let mut position = 0;
+
+    while position < bytes.len() {
+        let start = position;
+        let next = bytes.get(position + 1).copied();
+
+        let token = match (bytes[position], next) {
+            (byte, _) if byte.is_ascii_whitespace() => {
+                position += 1;
+                continue;
+            }
+            (byte, _) if byte.is_ascii_alphabetic() || byte == b'_' => {
+                while position < bytes.len() && (bytes[position].is_ascii_alphanumeric() || bytes[position] == b'_') {
+                    position += 1;
+                }
+                tokens.push((start, Token::Name(expression[start..position].to_owned())));
+                continue;
+            }
+            (byte, _) if byte.is_ascii_digit() => {
+                while position < bytes.len() && bytes[position].is_ascii_digit() {
+                    position += 1;
+                }
+                let number = expression[start..position].parse().map_err(|_| ConditionError::InvalidCharacter(start))?;
+                tokens.push((start, Token::Number(number)));
+                continue;
+            }
+            (b'&', Some(b'&')) => Token::And,
+            (b'|', Some(b'|')) => Token::Or,
+            (b'=', Some(b'=')) => Token::Comparison(Comparison::Equal),
+            (b'!', Some(b'=')) => Token::Comparison(Comparison::NotEqual),
+            (b'<', Some(b'=')) => Token::Comparison(Comparison::LessOrEqual),
+            (b'>', Some(b'=')) => Token::Comparison(Comparison::GreaterOrEqual),
+            (b'<', _) => Token::Comparison(Comparison::Less),
+            (b'>', _) => Token::Comparison(Comparison::Greater),
+            (b'!', _) => Token::Not,
+            (b'#', _) => Token::Count,
+            (b'(', _) => Token::Open,
+            (b')', _) => Token::Close,
+            _ => return Err(ConditionError::InvalidCharacter(start))
+        };
+
+        position += match token {
+            Token::And | Token::Or | Token::Comparison(Comparison::Equal | Comparison::NotEqual |
+                Comparison::LessOrEqual | Comparison::GreaterOrEqual) => 2,
+            _ => 1
+        };
+        tokens.push((start, token));
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    position: usize,
+    depth: usize
+}
+
+// The parser returns the depth of every condition it builds, so that chains of operators are
+// limited as well as nested parentheses; both make evaluating the condition recurse deeper.
+impl Parser {
+    fn parse_or(&mut self) -> Result<(Condition, usize), ConditionError> {
+        let (mut condition, mut depth) = self.parse_and()?;
+        while self.next_if(&Token::Or) {
+            let position = self.tokens[self.position - 1].0;
+            let (right, right_depth) = self.parse_and()?;
+            depth = nested_depth(depth.max(right_depth), position)?;
+            condition = Condition::Or(Box::new(condition), Box::new(right));
+        }
+        Ok((condition, depth))
+    }
+
+    fn parse_and(&mut self) -> Result<(Condition, usize), ConditionError> {
+        let (mut condition, mut depth) = self.parse_unary()?;
+        while self.next_if(&Token::And) {
+            let position = self.tokens[self.position - 1].0;
+            let (right, right_depth) = self.parse_unary()?;
+            depth = nested_depth(depth.max(right_depth), position)?;
+            condition = Condition::And(Box::new(condition), Box::new(right));
+        }
+        Ok((condition, depth))
+    }
+
+    fn parse_unary(&mut self) -> Result<(Condition, usize), ConditionError> {
+        let (position, token) = self.next()?;
+
+        match token {
+            Token::Not => {
+                let (condition, depth) = self.parse_nested(position, Parser::parse_unary)?;
+                Ok((Condition::Not(Box::new(condition)), nested_depth(depth, position)?))
+            }
+            Token::Open => {
+                let (condition, depth) = self.parse_nested(position, Parser::parse_or)?;
+                match self.next()? {
+                    (_, Token::Close) => Ok((condition, depth)),
+                    (position, _) => Err(ConditionError::UnexpectedToken(position))
+                }
+            }
+            Token::Count => match (self.next()?, self.next()?, self.next()?) {
+                ((_, Token::Name(name)), (_, Token::Comparison(comparison)), (_, Token::Number(value))) =>
+                    Ok((Condition::Count(name, comparison, value), 0)),
+                _ => Err(ConditionError::UnexpectedToken(position))
+            },
+            Token::Name(name) => Ok((Condition::Matches(name), 0)),
+            _ => Err(ConditionError::UnexpectedToken(position))
+        }
+    }
+
+    fn parse_nested(&mut self, position: usize, parse: fn(&mut Parser) -> Result<(Condition, usize), ConditionError>)
+                    -> Result<(Condition, usize), ConditionError> {
+        self.depth = nested_depth(self.depth, position)?;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn next(&mut self) -> Result<(usize, Token), ConditionError> {
+        let token = self.tokens.get(self.position).cloned().ok_or(ConditionError::UnexpectedEnd)?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn next_if(&mut self, expected: &Token) -> bool {
+        let is_expected = self.tokens.get(self.position).is_some_and(|(_, token)| token == expected);
+        if is_expected {
+            self.position += 1;
+        }
+        is_expected
+    }
+}
+
+fn nested_depth(depth: usize, position: usize) -> Result<usize, ConditionError> {
+    (depth < MAX_DEPTH).then_some(depth + 1).ok_or(ConditionError::TooDeep(position))
+}
+
+#[cfg(test)]
+#[path = "./condition_test.rs"]
+mod condition_test;
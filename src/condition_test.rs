@@ -0,0 +1,112 @@
+use super::*;
+
+const SOURCE: &[u8] = b"The quick brown fox jumps over the lazy dog";
+
+fn evaluate(expression: &str) -> Result<bool, ConditionError> {
+    let fox = BndmConfig::new(b"fox", None);
+    let dog = BndmConfig::new(b"d?g", Some(b'?'));
+    let cat = BndmConfig::new(b"cat", None);
+    let o = BndmConfig::new(b"o", None);
+    let condition: Condition = expression.parse()?;
+
+    condition.evaluate(SOURCE, &[("fox", &fox), ("dog", &dog), ("cat", &cat), ("o", &o)])
+}
+
+#[test]
+fn evaluate_and() {
+    assert_eq!(evaluate("fox && dog"), Ok(true));
+    assert_eq!(evaluate("fox && cat"), Ok(false));
+}
+
+#[test]
+fn evaluate_or() {
+    assert_eq!(evaluate("cat || dog"), Ok(true));
+    assert_eq!(evaluate("cat || cat"), Ok(false));
+}
+
+#[test]
+fn evaluate_not() {
+    assert_eq!(evaluate("fox && dog && !cat"), Ok(true));
+    assert_eq!(evaluate("!fox"), Ok(false));
+    assert_eq!(evaluate("!!fox"), Ok(true));
+}
+
+#[test]
+fn evaluate_and_before_or() {
+    assert_eq!(evaluate("cat && fox || dog"), Ok(true));
+    assert_eq!(evaluate("cat && (fox || dog)"), Ok(false));
+}
+
+#[test]
+fn evaluate_count() {
+    assert_eq!(evaluate("#o == 4"), Ok(true));
+    assert_eq!(evaluate("#o >= 3"), Ok(true));
+    assert_eq!(evaluate("#o > 4"), Ok(false));
+    assert_eq!(evaluate("#o < 4"), Ok(false));
+    assert_eq!(evaluate("#o <= 4"), Ok(true));
+    assert_eq!(evaluate("#o != 4"), Ok(false));
+    assert_eq!(evaluate("#cat == 0 && #fox>=1"), Ok(true));
+}
+
+#[test]
+fn evaluate_unknown_pattern() {
+    assert_eq!(evaluate("fox && bird"), Err(ConditionError::UnknownPattern("bird".to_owned())));
+}
+
+#[test]
+fn evaluate_short_circuit_unknown_pattern() {
+    assert_eq!(evaluate("fox || bird"), Err(ConditionError::UnknownPattern("bird".to_owned())));
+    assert_eq!(evaluate("cat && #bird > 1"), Err(ConditionError::UnknownPattern("bird".to_owned())));
+}
+
+#[test]
+fn evaluate_repeated_pattern() {
+    assert_eq!(evaluate("fox && !(cat || !fox) && #fox == 1 && fox"), Ok(true));
+    assert_eq!(evaluate("#o == 4 && o && !(#o != 4)"), Ok(true));
+    assert_eq!(evaluate("cat || #cat > 0 || cat"), Ok(false));
+}
+
+#[test]
+fn parse_condition() {
+    let condition: Condition = "a && !(b || #c > 2)".parse().unwrap();
+
+    assert_eq!(condition, Condition::And(
+        Box::new(Condition::Matches("a".to_owned())),
+        Box::new(Condition::Not(Box::new(Condition::Or(
+            Box::new(Condition::Matches("b".to_owned())),
+            Box::new(Condition::Count("c".to_owned(), Comparison::Greater, 2))
+        ))))
+    ));
+}
+
+#[test]
+fn parse_invalid_character() {
+    assert_eq!("a & b".parse::<Condition>(), Err(ConditionError::InvalidCharacter(2)));
+}
+
+#[test]
+fn parse_unexpected_token() {
+    assert_eq!("a b".parse::<Condition>(), Err(ConditionError::UnexpectedToken(2)));
+    assert_eq!("#a && 3".parse::<Condition>(), Err(ConditionError::UnexpectedToken(0)));
+    assert_eq!("(a || b))".parse::<Condition>(), Err(ConditionError::UnexpectedToken(8)));
+}
+
+#[test]
+fn parse_unexpected_end() {
+    assert_eq!("a &&".parse::<Condition>(), Err(ConditionError::UnexpectedEnd));
+    assert_eq!("(a".parse::<Condition>(), Err(ConditionError::UnexpectedEnd));
+    assert_eq!("".parse::<Condition>(), Err(ConditionError::UnexpectedEnd));
+}
+
+#[test]
+fn parse_too_deep() {
+    let nested = format!("{}a{}", "(".repeat(256), ")".repeat(256));
+    assert!(nested.parse::<Condition>().is_ok());
+
+    let nested = format!("{}a{}", "(".repeat(257), ")".repeat(257));
+    assert_eq!(nested.parse::<Condition>(), Err(ConditionError::TooDeep(256)));
+    assert_eq!(format!("{}a", "!".repeat(257)).parse::<Condition>(), Err(ConditionError::TooDeep(256)));
+
+    let chained = format!("a{}", " && a".repeat(257));
+    assert_eq!(chained.parse::<Condition>(), Err(ConditionError::TooDeep(1282)));
+}
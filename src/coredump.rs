@@ -0,0 +1,363 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Scanning of the memory regions of ELF core files and Windows minidumps, reporting matches
+//! with their virtual address, module and region permissions.
+
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+
+use crate::{BndmConfig, find_overlapping_indexes};
+
+const ELF_MAGIC: &[u8] = b"\x7fELF";
+const ELF_CLASS_64: u8 = 2;
+const ELF_DATA_LITTLE_ENDIAN: u8 = 1;
+const ELF_TYPE_CORE: u16 = 4;
+const PT_LOAD: u32 = 1;
+const PT_NOTE: u32 = 4;
+const NT_FILE: u32 = 0x4649_4c45;
+
+const MINIDUMP_MAGIC: &[u8] = b"MDMP";
+const MODULE_LIST_STREAM: u32 = 4;
+const MEMORY_LIST_STREAM: u32 = 5;
+const MEMORY64_LIST_STREAM: u32 = 9;
+const MEMORY_INFO_LIST_STREAM: u32 = 16;
+const MINIDUMP_MODULE_SIZE: usize = 108;
+const MINIDUMP_MEMORY_INFO_SIZE: usize = 40;
+
+/// The access permissions of a memory region.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Permissions {
+    /// Whether the region can be read.
+    pub read: bool,
+
+    /// Whether the region can be written.
+    pub write: bool,
+
+    /// Whether the region can be executed.
+    pub execute: bool
+}
+
+/// A memory region of a core dump.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MemoryRegion {
+    /// The virtual address of the start of the region in the dumped process.
+    pub virtual_address: u64,
+
+    /// The range of the contents of the region in the dump.
+    pub data: Range<usize>,
+
+    /// The access permissions of the region, if the dump records them.
+    pub permissions: Option<Permissions>,
+
+    /// The name of the module or file that is mapped at the region, if any.
+    pub module: Option<String>
+}
+
+/// A match of a pattern in a memory region of a core dump.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RegionMatch {
+    /// The index of the pattern that matched.
+    pub pattern: usize,
+
+    /// The index of the region in `CoreDump::regions`.
+    pub region: usize,
+
+    /// The virtual address of the match in the dumped process.
+    pub virtual_address: u64,
+
+    /// The index of the match in the dump.
+    pub file_offset: usize
+}
+
+/// The error type for parsing core dumps.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CoreDumpError {
+    /// The data is neither a 64-bit little-endian ELF core file nor a minidump.
+    UnknownFormat,
+
+    /// A header or table of the dump extends beyond the end of the data.
+    Truncated
+}
+
+impl fmt::Display for CoreDumpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CoreDumpError::UnknownFormat => write!(f, "unknown core dump format"),
+            CoreDumpError::Truncated => write!(f, "truncated core dump")
+        }
+    }
+}
+
+impl Error for CoreDumpError {}
+
+/// The `CoreDump` struct holds a core dump and the memory regions it contains.
+pub struct CoreDump<'a> {
+    bytes: &'a [u8],
+    regions: Vec<MemoryRegion>
+}
+
+impl<'a> CoreDump<'a> {
+    /// Parses the memory regions of a 64-bit little-endian ELF core file or a minidump.
+    ///
+    /// For ELF core files, the regions are the loadable segments, and the modules are taken
+    /// from the `NT_FILE` note. For minidumps, the regions are taken from the memory list
+    /// streams, the modules from the module list stream, and the permissions from the memory
+    /// info list stream. Regions of which the contents are not included in the dump are
+    /// skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The contents of the core dump.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<CoreDump, CoreDumpError>` - Returns the parsed core dump, or an error if the
+    ///   format is unknown or the dump is truncated.
+    pub fn parse(bytes: &'a [u8]) -> Result<CoreDump<'a>, CoreDumpError> {
+        let regions = if bytes.starts_with(ELF_MAGIC) {
+            parse_elf_core(bytes)?
+        } else if bytes.starts_with(MINIDUMP_MAGIC) {
+            parse_minidump(bytes)?
+        } else {
+            return Err(CoreDumpError::UnknownFormat);
+        };
+
+        Ok(CoreDump { bytes, regions })
+    }
+
+    /// Returns the memory regions of the core dump.
+    pub fn regions(&self) -> &[MemoryRegion] {
+        &self.regions
+    }
+
+    /// Searches every memory region for all occurrences of the pattern.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration for the BNDM search, which includes the pattern and the
+    ///   bitmasks.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<RegionMatch>` - Returns the matches ordered by region and index. Occurrences may
+    ///   overlap, but do not cross the boundary of a region.
+    pub fn find_all(&self, config: &BndmConfig) -> Vec<RegionMatch> {
+        self.find_all_of(&[config])
+    }
+
+    /// Searches every memory region for all occurrences of all patterns.
+    ///
+    /// # Arguments
+    ///
+    /// * `configs` - The configurations of the patterns to search for.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<RegionMatch>` - Returns the matches ordered by region and index, and by pattern
+    ///   for matches at the same index.
+    pub fn find_all_of(&self, configs: &[&BndmConfig]) -> Vec<RegionMatch> {
+        let mut matches = Vec::new();
+
+        for (region_index, region) in self.regions.iter().enumerate() {
+            let data = &self.bytes[region.data.clone()];
+            let start = matches.len();
+
+            for (pattern, config) in configs.iter().enumerate() {
+                matches.extend(find_overlapping_indexes(data, config).map(|index| RegionMatch {
+                    pattern,
+                    region: region_index,
+                    virtual_address: region.virtual_address.wrapping_add(index as u64),
+                    file_offset: region.data.start + index
+                }));
+            }
+            matches[start..].sort_by_key(|region_match| (region_match.file_offset, region_match.pattern));
+        }
+        matches
+    }
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> Result<u16, CoreDumpError> {
+    bytes.get(offset..).and_then(|b| b.get(..2)).map(|b| u16::from_le_bytes([b[0], b[1]])).ok_or(CoreDumpError::Truncated)
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32, CoreDumpError> {
+    bytes.get(offset..).and_then(|b| b.get(..4)).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]])).ok_or(CoreDumpError::Truncated)
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> Result<u64, CoreDumpError> {
+    bytes.get(offset..).and_then(|b| b.get(..8))
+        .map(|b| u64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]))
+        .ok_or(CoreDumpError::Truncated)
+}
+
+fn file_range(bytes: &[u8], offset: u64, size: u64) -> Result<Range<usize>, CoreDumpError> {
+    let start = usize::try_from(offset).map_err(|_| CoreDumpError::Truncated)?;
+    let end = usize::try_from(size).ok().and_then(|size| start.checked_add(size)).ok_or(CoreDumpError::Truncated)?;
+    if end > bytes.len() {
+        return Err(CoreDumpError::Truncated);
+    }
+    Ok(start..end)
+}
+
+fn parse_elf_core(bytes: &[u8]) -> Result<Vec<MemoryRegion>, CoreDumpError> {
+    if bytes.get(4) != Some(&ELF_CLASS_64) || bytes.get(5) != Some(&ELF_DATA_LITTLE_ENDIAN) || read_u16(bytes, 16)? != ELF_TYPE_CORE {
+        return Err(CoreDumpError::UnknownFormat);
+    }
+
+    let header_offset = read_u64(bytes, 32)? as usize;
+    let header_size = read_u16(bytes, 54)? as usize;
+    let header_count = read_u16(bytes, 56)? as usize;
+
+    let mut regions = Vec::new();
+    let mut file_mappings = Vec::new();
+    for index in 0..header_count {
+        let header = header_offset.saturating_add(index * header_size);
+        let segment_type = read_u32(bytes, header)?;
+        let flags = read_u32(bytes, header + 4)?;
+        let data = file_range(bytes, read_u64(bytes, header + 8)?, read_u64(bytes, header + 32)?)?;
+
+        match segment_type {
+            PT_LOAD if !data.is_empty() => regions.push(MemoryRegion {
+                virtual_address: read_u64(bytes, header + 16)?,
+                data,
+                permissions: Some(Permissions { read: flags & 4 != 0, write: flags & 2 != 0, execute: flags & 1 != 0 }),
+                module: None
+            }),
+            PT_NOTE => file_mappings.extend(parse_file_note(&bytes[data])?),
+            _ => {}
+        }
+    }
+
+    for region in &mut regions {
+        region.module = file_mappings.iter()
+            .find(|(range, _)| range.contains(&region.virtual_address))
+            .map(|(_, name)| name.clone());
+    }
+    Ok(regions)
+}
+
+fn parse_file_note(notes: &[u8]) -> Result<Vec<(Range<u64>, String)>, CoreDumpError> {
+    let mut offset = 0;
+
+    while offset + 12 <= notes.len() {
+        let name_size = read_u32(notes, offset)? as usize;
+        let desc_size = read_u32(notes, offset + 4)? as usize;
+        let note_type = read_u32(notes, offset + 8)?;
+        let desc_start = name_size.checked_next_multiple_of(4).and_then(|size| (offset + 12).checked_add(size));
+        let desc_end = desc_start.and_then(|start| start.checked_add(desc_size));
+        let (desc_start, desc) = desc_start.zip(desc_end).and_then(|(start, end)| Some((start, notes.get(start..end)?)))
+            .ok_or(CoreDumpError::Truncated)?;
+
+        if note_type == NT_FILE {
+            let count = read_u64(desc, 0)? as usize;
+            let names_start = count.checked_mul(24).and_then(|size| size.checked_add(16)).ok_or(CoreDumpError::Truncated)?;
+            let names = desc.get(names_start..).ok_or(CoreDumpError::Truncated)?.split(|&byte| byte == 0);
+
+            return (0..count).zip(names).map(|(index, name)| {
+                let entry = 16 + index * 24;
+                Ok((read_u64(desc, entry)?..read_u64(desc, entry + 8)?, String::from_utf8_lossy(name).into_owned()))
+            }).collect();
+        }
+        offset = desc_size.checked_next_multiple_of(4).and_then(|size| desc_start.checked_add(size)).ok_or(CoreDumpError::Truncated)?;
+    }
+    Ok(Vec::new())
+}
+
+fn parse_minidump(bytes: &[u8]) -> Result<Vec<MemoryRegion>, CoreDumpError> {
+    let stream_count = read_u32(bytes, 8)? as usize;
+    let directory = read_u32(bytes, 12)? as usize;
+
+    let mut regions = Vec::new();
+    let mut modules = Vec::new();
+    let mut memory_infos = Vec::new();
+    for index in 0..stream_count {
+        let entry = directory + index * 12;
+        let stream_type = read_u32(bytes, entry)?;
+        let stream = read_u32(bytes, entry + 8)? as usize;
+
+        match stream_type {
+            MEMORY_LIST_STREAM => {
+                for range in 0..read_u32(bytes, stream)? as usize {
+                    let descriptor = stream + 4 + range * 16;
+                    if let Ok(data) = file_range(bytes, read_u32(bytes, descriptor + 12)? as u64, read_u32(bytes, descriptor + 8)? as u64) {
+                        regions.push(minidump_region(read_u64(bytes, descriptor)?, data));
+                    }
+                }
+            }
+            MEMORY64_LIST_STREAM => {
+                let mut data_offset = read_u64(bytes, stream + 8)?;
+                for range in 0..read_u64(bytes, stream)? as usize {
+                    let descriptor = stream + 16 + range * 16;
+                    let size = read_u64(bytes, descriptor + 8)?;
+                    if let Ok(data) = file_range(bytes, data_offset, size) {
+                        regions.push(minidump_region(read_u64(bytes, descriptor)?, data));
+                    }
+                    data_offset = data_offset.saturating_add(size);
+                }
+            }
+            MODULE_LIST_STREAM => {
+                for module in 0..read_u32(bytes, stream)? as usize {
+                    let module = stream + 4 + module * MINIDUMP_MODULE_SIZE;
+                    let base = read_u64(bytes, module)?;
+                    let size = read_u32(bytes, module + 8)? as u64;
+                    modules.push((base..base.saturating_add(size), read_minidump_string(bytes, read_u32(bytes, module + 20)? as usize)?));
+                }
+            }
+            MEMORY_INFO_LIST_STREAM => {
+                let header_size = read_u32(bytes, stream)? as usize;
+                let entry_size = read_u32(bytes, stream + 4)? as usize;
+                let count = read_u64(bytes, stream + 8)?;
+                let start = stream + header_size;
+                if entry_size < MINIDUMP_MEMORY_INFO_SIZE || count > (bytes.len().saturating_sub(start) / entry_size) as u64 {
+                    return Err(CoreDumpError::Truncated);
+                }
+                for info in 0..count as usize {
+                    let info = start + info * entry_size;
+                    let base = read_u64(bytes, info)?;
+                    let size = read_u64(bytes, info + 24)?;
+                    memory_infos.push((base..base.saturating_add(size), protection_permissions(read_u32(bytes, info + 36)?)));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for region in &mut regions {
+        region.module = modules.iter()
+            .find(|(range, _)| range.contains(&region.virtual_address))
+            .map(|(_, name)| name.clone());
+        region.permissions = memory_infos.iter()
+            .find(|(range, _)| range.contains(&region.virtual_address))
+            .map(|(_, permissions)| *permissions);
+    }
+    Ok(regions)
+}
+
+fn minidump_region(virtual_address: u64, data: Range<usize>) -> MemoryRegion {
+    MemoryRegion { virtual_address, data, permissions: None, module: None }
+}
+
+fn read_minidump_string(bytes: &[u8], offset: usize) -> Result<String, CoreDumpError> {
+    let len = read_u32(bytes, offset)? as usize;
+    let start = offset.checked_add(4).ok_or(CoreDumpError::Truncated)?;
+    let units: Vec<u16> = start.checked_add(len).and_then(|end| bytes.get(start..end)).ok_or(CoreDumpError::Truncated)?
+        .chunks_exact(2)
+        .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+        .collect();
+    Ok(String::from_utf16_lossy(&units))
+}
+
+fn protection_permissions(protect: u32) -> Permissions {
+    let protect = protect & 0xff;
+    Permissions {
+        read: protect & 0xee != 0,
+        write: protect & 0xcc != 0,
+        execute: protect & 0xf0 != 0
+    }
+}
+
+#[cfg(test)]
+#[path = "./coredump_test.rs"]
+mod coredump_test;
@@ -0,0 +1,256 @@
+use super::*;
+
+fn elf_core(segments: &[(u32, u32, u64, &[u8])]) -> Vec<u8> {
+    let header_offset = 64;
+    let data_offset = header_offset + segments.len() * 56;
+    let mut header = vec![0u8; 64];
+    header[..4].copy_from_slice(ELF_MAGIC);
+    header[4] = ELF_CLASS_64;
+    header[5] = ELF_DATA_LITTLE_ENDIAN;
+    header[16..18].copy_from_slice(&ELF_TYPE_CORE.to_le_bytes());
+    header[32..40].copy_from_slice(&(header_offset as u64).to_le_bytes());
+    header[54..56].copy_from_slice(&56u16.to_le_bytes());
+    header[56..58].copy_from_slice(&(segments.len() as u16).to_le_bytes());
+
+    let mut data = Vec::new();
+    for &(segment_type, flags, virtual_address, contents) in segments {
+        header.extend_from_slice(&segment_type.to_le_bytes());
+        header.extend_from_slice(&flags.to_le_bytes());
+        header.extend_from_slice(&((data_offset + data.len()) as u64).to_le_bytes());
+        header.extend_from_slice(&virtual_address.to_le_bytes());
+        header.extend_from_slice(&0u64.to_le_bytes());
+        header.extend_from_slice(&(contents.len() as u64).to_le_bytes());
+        header.extend_from_slice(&(contents.len() as u64).to_le_bytes());
+        header.extend_from_slice(&0u64.to_le_bytes());
+        data.extend_from_slice(contents);
+    }
+    header.extend(data);
+    header
+}
+
+fn file_note(mappings: &[(u64, u64, &str)]) -> Vec<u8> {
+    let mut desc = Vec::new();
+    desc.extend_from_slice(&(mappings.len() as u64).to_le_bytes());
+    desc.extend_from_slice(&4096u64.to_le_bytes());
+    for &(start, end, _) in mappings {
+        desc.extend_from_slice(&start.to_le_bytes());
+        desc.extend_from_slice(&end.to_le_bytes());
+        desc.extend_from_slice(&0u64.to_le_bytes());
+    }
+    for &(_, _, name) in mappings {
+        desc.extend_from_slice(name.as_bytes());
+        desc.push(0);
+    }
+    desc.resize(desc.len().next_multiple_of(4), 0);
+
+    let mut note = Vec::new();
+    note.extend_from_slice(&5u32.to_le_bytes());
+    note.extend_from_slice(&(desc.len() as u32).to_le_bytes());
+    note.extend_from_slice(&NT_FILE.to_le_bytes());
+    note.extend_from_slice(b"CORE\0\0\0\0");
+    note.extend(desc);
+    note
+}
+
+struct Minidump {
+    streams: Vec<(u32, Vec<u8>)>
+}
+
+impl Minidump {
+    // Builds a minidump with the streams after the header and the directory. Streams refer to
+    // absolute offsets, so their contents are built with the offset they will be placed at.
+    fn build(&self) -> Vec<u8> {
+        let mut bytes = MINIDUMP_MAGIC.to_vec();
+        bytes.extend_from_slice(&0xa793u32.to_le_bytes());
+        bytes.extend_from_slice(&(self.streams.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&32u32.to_le_bytes());
+        bytes.resize(32, 0);
+
+        let mut offset = 32 + self.streams.len() * 12;
+        for (stream_type, contents) in &self.streams {
+            bytes.extend_from_slice(&stream_type.to_le_bytes());
+            bytes.extend_from_slice(&(contents.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(&(offset as u32).to_le_bytes());
+            offset += contents.len();
+        }
+        for (_, contents) in &self.streams {
+            bytes.extend_from_slice(contents);
+        }
+        bytes
+    }
+}
+
+#[test]
+fn parse_unknown_format() {
+    assert_eq!(CoreDump::parse(b"not a dump").err(), Some(CoreDumpError::UnknownFormat));
+    assert_eq!(CoreDump::parse(b"\x7fELF\x01\x01").err(), Some(CoreDumpError::UnknownFormat));
+    assert_eq!(CoreDumpError::Truncated.to_string(), "truncated core dump");
+}
+
+#[test]
+fn parse_truncated_elf_core() {
+    let mut core = elf_core(&[(PT_LOAD, 5, 0x400000, b"code")]);
+    core.truncate(core.len() - 2);
+
+    assert_eq!(CoreDump::parse(&core).err(), Some(CoreDumpError::Truncated));
+}
+
+#[test]
+fn parse_file_note_with_overflowing_count() {
+    let mut note = file_note(&[(0x400000, 0x401000, "/usr/bin/app")]);
+    note[20..28].copy_from_slice(&(u64::MAX / 24).to_le_bytes());
+    let core = elf_core(&[(PT_NOTE, 0, 0, &note), (PT_LOAD, 5, 0x400000, b"code")]);
+
+    assert_eq!(CoreDump::parse(&core).err(), Some(CoreDumpError::Truncated));
+}
+
+#[test]
+fn parse_file_note_with_overflowing_size() {
+    let mut note = file_note(&[(0x400000, 0x401000, "/usr/bin/app")]);
+    note[4..8].copy_from_slice(&u32::MAX.to_le_bytes());
+    let core = elf_core(&[(PT_NOTE, 0, 0, &note)]);
+
+    assert_eq!(CoreDump::parse(&core).err(), Some(CoreDumpError::Truncated));
+}
+
+#[test]
+fn parse_minidump_skips_regions_outside_the_dump() {
+    let data_offset = 32 + 12 + 4 + 2 * 16;
+    let mut memory = Vec::new();
+    memory.extend_from_slice(&2u32.to_le_bytes());
+    memory.extend_from_slice(&0x10000u64.to_le_bytes());
+    memory.extend_from_slice(&4u32.to_le_bytes());
+    memory.extend_from_slice(&(data_offset as u32).to_le_bytes());
+    memory.extend_from_slice(&0x20000u64.to_le_bytes());
+    memory.extend_from_slice(&0x1000u32.to_le_bytes());
+    memory.extend_from_slice(&u32::MAX.to_le_bytes());
+    memory.extend_from_slice(b"data");
+    let dump_bytes = Minidump { streams: vec![(MEMORY_LIST_STREAM, memory)] }.build();
+    let dump = CoreDump::parse(&dump_bytes).unwrap();
+
+    assert_eq!(dump.regions().len(), 1);
+    assert_eq!(dump.regions()[0].virtual_address, 0x10000);
+    assert_eq!(&dump_bytes[dump.regions()[0].data.clone()], b"data");
+}
+
+#[test]
+fn parse_minidump_with_invalid_memory_info_list() {
+    for (entry_size, count) in [(0u32, u64::MAX), (39, 1), (48, 2), (48, u64::MAX)] {
+        let mut infos = Vec::new();
+        infos.extend_from_slice(&16u32.to_le_bytes());
+        infos.extend_from_slice(&entry_size.to_le_bytes());
+        infos.extend_from_slice(&count.to_le_bytes());
+        infos.extend(vec![0u8; 48]);
+        let dump_bytes = Minidump { streams: vec![(MEMORY_INFO_LIST_STREAM, infos)] }.build();
+
+        assert_eq!(CoreDump::parse(&dump_bytes).err(), Some(CoreDumpError::Truncated));
+    }
+}
+
+#[test]
+fn find_in_elf_core() {
+    let note = file_note(&[(0x400000, 0x401000, "/usr/bin/app")]);
+    let core = elf_core(&[
+        (PT_NOTE, 0, 0, &note),
+        (PT_LOAD, 5, 0x400000, b"..secret.."),
+        (PT_LOAD, 6, 0x7ff000, b"secret"),
+        (PT_LOAD, 4, 0x900000, b"")
+    ]);
+    let dump = CoreDump::parse(&core).unwrap();
+    let regions = dump.regions();
+
+    assert_eq!(regions.len(), 2);
+    assert_eq!(regions[0].module.as_deref(), Some("/usr/bin/app"));
+    assert_eq!(regions[0].permissions, Some(Permissions { read: true, write: false, execute: true }));
+    assert_eq!(regions[1].module, None);
+    assert_eq!(regions[1].permissions, Some(Permissions { read: true, write: true, execute: false }));
+
+    let matches = dump.find_all(&BndmConfig::new(b"secret", None));
+    assert_eq!(matches.len(), 2);
+    assert_eq!(matches[0].virtual_address, 0x400002);
+    assert_eq!(&core[matches[0].file_offset..matches[0].file_offset + 6], b"secret");
+    assert_eq!(matches[1].region, 1);
+    assert_eq!(matches[1].virtual_address, 0x7ff000);
+}
+
+#[test]
+fn find_does_not_cross_regions() {
+    let core = elf_core(&[(PT_LOAD, 4, 0x1000, b"sec"), (PT_LOAD, 4, 0x1003, b"ret")]);
+    let dump = CoreDump::parse(&core).unwrap();
+
+    assert_eq!(dump.find_all(&BndmConfig::new(b"secret", None)), vec![]);
+}
+
+#[test]
+fn find_in_minidump() {
+    let memory_offset = 32 + 4 * 12;
+    let module_name_offset = memory_offset + 48 + 12;
+
+    let mut memory64 = Vec::new();
+    memory64.extend_from_slice(&2u64.to_le_bytes());
+    let data_offset = memory_offset + memory64.len() + 8 + 32;
+    memory64.extend_from_slice(&(data_offset as u64).to_le_bytes());
+    memory64.extend_from_slice(&0x10000u64.to_le_bytes());
+    memory64.extend_from_slice(&6u64.to_le_bytes());
+    memory64.extend_from_slice(&0x20000u64.to_le_bytes());
+    memory64.extend_from_slice(&6u64.to_le_bytes());
+    memory64.extend_from_slice(b"MZ....");
+    memory64.extend_from_slice(b"..MZ..");
+    assert_eq!(memory_offset + memory64.len(), module_name_offset);
+
+    let mut name = Vec::new();
+    let units: Vec<u16> = "app.exe".encode_utf16().collect();
+    name.extend_from_slice(&((units.len() * 2) as u32).to_le_bytes());
+    units.iter().for_each(|unit| name.extend_from_slice(&unit.to_le_bytes()));
+
+    let mut modules = vec![0u8; 4 + MINIDUMP_MODULE_SIZE];
+    modules[..4].copy_from_slice(&1u32.to_le_bytes());
+    modules[4..12].copy_from_slice(&0x10000u64.to_le_bytes());
+    modules[12..16].copy_from_slice(&0x1000u32.to_le_bytes());
+    modules[24..28].copy_from_slice(&(module_name_offset as u32).to_le_bytes());
+
+    let mut infos = Vec::new();
+    infos.extend_from_slice(&16u32.to_le_bytes());
+    infos.extend_from_slice(&48u32.to_le_bytes());
+    infos.extend_from_slice(&1u64.to_le_bytes());
+    let mut info = vec![0u8; 48];
+    info[..8].copy_from_slice(&0x20000u64.to_le_bytes());
+    info[24..32].copy_from_slice(&0x1000u64.to_le_bytes());
+    info[36..40].copy_from_slice(&0x04u32.to_le_bytes());
+    infos.extend(info);
+
+    let dump_bytes = Minidump { streams: vec![
+        (MEMORY64_LIST_STREAM, memory64),
+        (0x1234, name),
+        (MODULE_LIST_STREAM, modules),
+        (MEMORY_INFO_LIST_STREAM, infos)
+    ] }.build();
+    let dump = CoreDump::parse(&dump_bytes).unwrap();
+    let regions = dump.regions();
+
+    assert_eq!(regions.len(), 2);
+    assert_eq!(regions[0].module.as_deref(), Some("app.exe"));
+    assert_eq!(regions[0].permissions, None);
+    assert_eq!(regions[1].module, None);
+    assert_eq!(regions[1].permissions, Some(Permissions { read: true, write: true, execute: false }));
+
+    let matches = dump.find_all(&BndmConfig::new(b"MZ", None));
+    assert_eq!(matches.iter().map(|m| (m.region, m.virtual_address)).collect::<Vec<_>>(), vec![(0, 0x10000), (1, 0x20002)]);
+}
+
+#[test]
+fn find_all_of_multiple_patterns() {
+    let core = elf_core(&[(PT_LOAD, 4, 0x1000, b"abcabc")]);
+    let dump = CoreDump::parse(&core).unwrap();
+    let matches = dump.find_all_of(&[&BndmConfig::new(b"bc", None), &BndmConfig::new(b"ab", None)]);
+
+    assert_eq!(matches.iter().map(|m| (m.pattern, m.virtual_address)).collect::<Vec<_>>(),
+        vec![(1, 0x1000), (0, 0x1001), (1, 0x1003), (0, 0x1004)]);
+}
+
+#[test]
+fn protection_flags() {
+    assert_eq!(protection_permissions(0x01), Permissions::default());
+    assert_eq!(protection_permissions(0x20), Permissions { read: true, write: false, execute: true });
+    assert_eq!(protection_permissions(0x104), Permissions { read: true, write: true, execute: false });
+}
@@ -0,0 +1,110 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Diagnostics of configurations that explain why a search is slower than expected or does
+//! not match what was intended.
+
+use std::fmt;
+
+use crate::{BndmConfig, MASKS_TABLE_SIZE};
+
+/// A property of a configuration that affects the results or the performance of a search.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Diagnostic {
+    /// The pattern is empty and never matches.
+    EmptyPattern,
+
+    /// Every position of the pattern matches any byte, so the pattern matches at every index
+    /// of a text that is long enough.
+    WildcardOnly,
+
+    /// The wildcard character is specified, but does not appear in the pattern.
+    UnusedWildcard(u8),
+
+    /// The pattern is longer than the word size. Only the first `filter_len` bytes are
+    /// scanned with the bitmasks, and the remaining `verified_len` bytes are verified for
+    /// every candidate.
+    VerifiedSuffix { filter_len: usize, verified_len: usize },
+
+    /// The pattern starts with `count` positions that match any byte. Every such position is
+    /// recognized as a prefix of the pattern in every window, so the window shifts by at most
+    /// `max_shift` bytes instead of the length of the scanned part of the pattern.
+    LeadingWildcards { count: usize, max_shift: usize },
+
+    /// Every byte value matches some position of the scanned part of the pattern, so no
+    /// single byte lets the window skip ahead. In the worst case, every window reads all
+    /// scanned positions and shifts by a single byte.
+    UnselectiveFilter
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Diagnostic::EmptyPattern => write!(f, "pattern is empty and never matches"),
+            Diagnostic::WildcardOnly => write!(f, "pattern only consists of wildcards and matches everywhere"),
+            Diagnostic::UnusedWildcard(wildcard) => write!(f, "wildcard byte {wildcard:#04x} specified but never appears in pattern"),
+            Diagnostic::VerifiedSuffix { filter_len, verified_len } =>
+                write!(f, "pattern longer than word size so only the first {filter_len} bytes drive the filter; {verified_len} bytes are verified per candidate"),
+            Diagnostic::LeadingWildcards { count, max_shift } =>
+                write!(f, "pattern starts with {count} wildcard positions so the window shifts by at most {max_shift} bytes"),
+            Diagnostic::UnselectiveFilter => write!(f, "every byte matches the filter so the window may shift by a single byte")
+        }
+    }
+}
+
+impl BndmConfig {
+    /// Returns the diagnostics of the configuration, in the order of the `Diagnostic` enum.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<Diagnostic>` - The properties of the configuration that affect the results or
+    ///   the performance of a search. An empty list means that nothing stands out.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{BndmConfig, Diagnostic};
+    ///
+    /// assert_eq!(BndmConfig::new(b"jumps", Some(b'?')).diagnostics(), vec![Diagnostic::UnusedWildcard(b'?')]);
+    /// assert_eq!(BndmConfig::new(b"??mps", Some(b'?')).diagnostics(), vec![
+    ///     Diagnostic::LeadingWildcards { count: 2, max_shift: 3 },
+    ///     Diagnostic::UnselectiveFilter
+    /// ]);
+    /// ```
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        if self.pattern.is_empty() {
+            diagnostics.push(Diagnostic::EmptyPattern);
+            return diagnostics;
+        }
+
+        let wildcard_positions = self.wildcard_positions();
+        if wildcard_positions.len() == self.pattern.len() {
+            diagnostics.push(Diagnostic::WildcardOnly);
+        }
+        if let Some(wildcard) = self.wildcard.filter(|_| wildcard_positions.is_empty()) {
+            diagnostics.push(Diagnostic::UnusedWildcard(wildcard));
+        }
+
+        let filter_len = self.filter_len();
+        if filter_len < self.pattern.len() {
+            diagnostics.push(Diagnostic::VerifiedSuffix { filter_len, verified_len: self.pattern.len() - filter_len });
+        }
+
+        let count = (0..filter_len).take_while(|&position| {
+            let bit = 1 << (filter_len - 1 - position);
+            self.masks.iter().all(|&mask| mask & bit != 0)
+        }).count();
+        if count > 0 && count < filter_len {
+            diagnostics.push(Diagnostic::LeadingWildcards { count, max_shift: filter_len - count });
+        }
+        if self.masks.iter().filter(|&&mask| mask != 0).count() == MASKS_TABLE_SIZE && count < filter_len {
+            diagnostics.push(Diagnostic::UnselectiveFilter);
+        }
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+#[path = "./diagnostics_test.rs"]
+mod diagnostics_test;
@@ -0,0 +1,52 @@
+use super::*;
+use crate::ByteClass;
+
+#[test]
+fn literal_pattern_has_no_diagnostics() {
+    assert!(BndmConfig::new(b"jumps", None).diagnostics().is_empty());
+}
+
+#[test]
+fn empty_pattern() {
+    assert_eq!(BndmConfig::new(b"", Some(b'?')).diagnostics(), vec![Diagnostic::EmptyPattern]);
+}
+
+#[test]
+fn wildcard_only() {
+    assert_eq!(BndmConfig::new(b"???", Some(b'?')).diagnostics(), vec![Diagnostic::WildcardOnly]);
+    assert_eq!(BndmConfig::with_classes(&[ByteClass::any(); 2]).diagnostics(), vec![Diagnostic::WildcardOnly]);
+}
+
+#[test]
+fn unused_wildcard() {
+    assert_eq!(BndmConfig::new(b"jumps", Some(b'*')).diagnostics(), vec![Diagnostic::UnusedWildcard(b'*')]);
+}
+
+#[test]
+fn verified_suffix() {
+    let pattern = vec![b'x'; 70];
+
+    assert_eq!(BndmConfig::new(&pattern, None).diagnostics(), vec![Diagnostic::VerifiedSuffix { filter_len: 64, verified_len: 6 }]);
+}
+
+#[test]
+fn leading_wildcards() {
+    assert_eq!(BndmConfig::new(b"?umps", Some(b'?')).diagnostics(), vec![
+        Diagnostic::LeadingWildcards { count: 1, max_shift: 4 },
+        Diagnostic::UnselectiveFilter
+    ]);
+}
+
+#[test]
+fn unselective_filter() {
+    assert_eq!(BndmConfig::new(b"ju?ps", Some(b'?')).diagnostics(), vec![Diagnostic::UnselectiveFilter]);
+    assert_eq!(BndmConfig::with_classes(&[ByteClass::byte(b'a'), ByteClass::range(1..=255).with_byte(0)]).diagnostics(),
+        vec![Diagnostic::UnselectiveFilter]);
+}
+
+#[test]
+fn display_diagnostics() {
+    assert_eq!(Diagnostic::UnusedWildcard(b'?').to_string(), "wildcard byte 0x3f specified but never appears in pattern");
+    assert_eq!(Diagnostic::VerifiedSuffix { filter_len: 64, verified_len: 6 }.to_string(),
+        "pattern longer than word size so only the first 64 bytes drive the filter; 6 bytes are verified per candidate");
+}
@@ -0,0 +1,129 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Skipping of high-entropy regions, such as compressed or encrypted data, in which plaintext
+//! patterns cannot occur.
+
+use std::ops::Range;
+
+use crate::{BndmConfig, find_pattern_in_ranges};
+
+/// The `EntropyFilter` struct splits a text into blocks and selects the blocks whose Shannon
+/// entropy does not exceed a maximum.
+pub struct EntropyFilter {
+    block_size: usize,
+    max_entropy: f64
+}
+
+impl EntropyFilter {
+    /// Creates a new `EntropyFilter` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `block_size` - The size of the blocks to estimate the entropy for; a size of zero is
+    ///   treated as one. Small blocks give unreliable estimates, so a size of at least 256 bytes
+    ///   is recommended.
+    /// * `max_entropy` - The maximum entropy of a block in bits per byte, between 0.0 and 8.0.
+    ///   Compressed and encrypted data typically have an entropy above 7.5.
+    ///
+    /// # Returns
+    ///
+    /// * `EntropyFilter` - A new `EntropyFilter` instance.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{BndmConfig, EntropyFilter};
+    ///
+    /// let mut source: Vec<u8> = (0..=255).collect();
+    /// source.extend_from_slice(b"The quick brown fox jumps over the lazy dog");
+    ///
+    /// let filter = EntropyFilter::new(256, 7.5);
+    /// let config = BndmConfig::new(b"fox", None);
+    /// assert_eq!(filter.low_entropy_ranges(&source), vec![256..299]);
+    /// assert_eq!(filter.find_pattern(&source, &config), Some(272));
+    /// ```
+    pub fn new(block_size: usize, max_entropy: f64) -> EntropyFilter {
+        EntropyFilter {
+            block_size: block_size.max(1),
+            max_entropy
+        }
+    }
+
+    /// Returns the ranges of the source string that consist of blocks with an entropy that
+    /// does not exceed the maximum. Adjacent blocks are merged into a single range.
+    pub fn low_entropy_ranges(&self, source: &[u8]) -> Vec<Range<usize>> {
+        let mut ranges: Vec<Range<usize>> = Vec::new();
+
+        for (block_index, block) in source.chunks(self.block_size).enumerate() {
+            if block_entropy(block) > self.max_entropy {
+                continue;
+            }
+
+            let start = block_index * self.block_size;
+            match ranges.last_mut() {
+                Some(last) if last.end == start => last.end = start + block.len(),
+                _ => ranges.push(start..start + block.len())
+            }
+        }
+        ranges
+    }
+
+    /// Searches for the pattern in the source string, skipping blocks with an entropy that
+    /// exceeds the maximum.
+    ///
+    /// Occurrences that cross the boundary between a low-entropy block and a skipped block are
+    /// not found.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source string to search for the pattern.
+    /// * `config` - The configuration for the BNDM search, which includes the pattern and the
+    ///   bitmasks.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<usize>` - Returns the index of the first occurrence of the pattern in the
+    ///   low-entropy blocks, or `None` if the pattern is not found.
+    pub fn find_pattern(&self, source: &[u8], config: &BndmConfig) -> Option<usize> {
+        find_pattern_in_ranges(source, config, &self.low_entropy_ranges(source))
+    }
+}
+
+/// Calculates the Shannon entropy of a block of bytes.
+///
+/// # Arguments
+///
+/// * `block` - The block of bytes.
+///
+/// # Returns
+///
+/// * `f64` - Returns the entropy in bits per byte, between 0.0 for an empty block or a block
+///   with a single repeated byte, and 8.0 for a block in which all byte values are equally
+///   frequent.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::block_entropy;
+///
+/// assert_eq!(block_entropy(b"aaaa"), 0.0);
+/// assert_eq!(block_entropy(b"abab"), 1.0);
+/// ```
+pub fn block_entropy(block: &[u8]) -> f64 {
+    let mut counts = [0usize; 256];
+    block.iter().for_each(|&byte| counts[byte as usize] += 1);
+
+    let len = block.len() as f64;
+    counts.iter().filter(|&&count| count > 0)
+        .map(|&count| {
+            let probability = count as f64 / len;
+            -probability * probability.log2()
+        })
+        .sum::<f64>()
+        .max(0.0)
+}
+
+#[cfg(test)]
+#[path = "./entropy_test.rs"]
+mod entropy_test;
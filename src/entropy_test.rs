@@ -0,0 +1,65 @@
+use super::*;
+
+fn random_block(seed: u32, len: usize) -> Vec<u8> {
+    let mut state = seed;
+    (0..len).map(|_| {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        (state >> 24) as u8
+    }).collect()
+}
+
+#[test]
+fn block_entropy_empty() {
+    assert_eq!(block_entropy(b""), 0.0);
+}
+
+#[test]
+fn block_entropy_single_byte() {
+    assert_eq!(block_entropy(&[0x41; 100]), 0.0);
+}
+
+#[test]
+fn block_entropy_all_bytes() {
+    let block: Vec<u8> = (0..=255).collect();
+
+    assert_eq!(block_entropy(&block), 8.0);
+}
+
+#[test]
+fn block_entropy_text() {
+    let entropy = block_entropy(b"The quick brown fox jumps over the lazy dog");
+
+    assert!(entropy > 4.0 && entropy < 5.0);
+}
+
+#[test]
+fn low_entropy_ranges_merge_adjacent_blocks() {
+    let mut source = vec![b'a'; 512];
+    source.extend(random_block(1, 1024));
+    source.extend(vec![b'b'; 300]);
+    let filter = EntropyFilter::new(256, 7.0);
+
+    assert_eq!(filter.low_entropy_ranges(&source), vec![0..512, 1536..1836]);
+}
+
+#[test]
+fn find_pattern_skip_high_entropy() {
+    let mut source = random_block(7, 1024);
+    source[100..103].copy_from_slice(b"fox");
+    source.extend_from_slice(b"The quick brown fox jumps over the lazy dog");
+    let config = BndmConfig::new(b"fox", None);
+    let filter = EntropyFilter::new(256, 7.0);
+
+    assert_eq!(filter.find_pattern(&source, &config), Some(1040));
+}
+
+#[test]
+fn find_pattern_all_high_entropy() {
+    let source = random_block(3, 2048);
+    let config = BndmConfig::new(&source[1000..1004], None);
+    let filter = EntropyFilter::new(512, 7.0);
+
+    assert_eq!(filter.find_pattern(&source, &config), None);
+}
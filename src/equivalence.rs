@@ -0,0 +1,125 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Equivalence classes of bytes, for matching bytes in the pattern against any byte of the
+//! same class in the text.
+
+/// The `Equivalence` struct maps every byte to the representative of its equivalence class.
+///
+/// A byte in the pattern matches every byte in the text that has the same representative.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct Equivalence {
+    classes: [u8; 256]
+}
+
+impl Default for Equivalence {
+    fn default() -> Equivalence {
+        Equivalence::identity()
+    }
+}
+
+impl Equivalence {
+    /// Creates a new `Equivalence` instance in which every byte is only equivalent to itself.
+    pub fn identity() -> Equivalence {
+        Equivalence {
+            classes: std::array::from_fn(|byte| byte as u8)
+        }
+    }
+
+    /// Creates a new `Equivalence` instance in which every ASCII letter is equivalent to its
+    /// other case, e.g. `E` is equivalent to `e`. Other bytes are only equivalent to
+    /// themselves.
+    pub fn ascii_case_insensitive() -> Equivalence {
+        Equivalence {
+            classes: std::array::from_fn(|byte| (byte as u8).to_ascii_lowercase())
+        }
+    }
+
+    /// Creates a new `Equivalence` instance that folds the accented Latin-1 (ISO 8859-1)
+    /// letters to their ASCII base letters, e.g. `é` (`$E9`) is equivalent to `e`.
+    ///
+    /// Only single-byte Latin-1 characters are folded. Multi-byte UTF-8 sequences differ in
+    /// length from their base letters and cannot be matched by a byte equivalence.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{BndmConfig, Equivalence, find_pattern};
+    ///
+    /// let source = b"Caf\xe9 cr\xe8me br\xfbl\xe9e";
+    /// let config = BndmConfig::with_equivalence(b"creme brulee", None, &Equivalence::latin1_diacritics());
+    /// assert_eq!(find_pattern(source, &config), Some(5));
+    /// ```
+    pub fn latin1_diacritics() -> Equivalence {
+        const FOLDS: [(u8, &[u8]); 18] = [
+            (b'A', b"\xc0\xc1\xc2\xc3\xc4\xc5"),
+            (b'C', b"\xc7"),
+            (b'D', b"\xd0"),
+            (b'E', b"\xc8\xc9\xca\xcb"),
+            (b'I', b"\xcc\xcd\xce\xcf"),
+            (b'N', b"\xd1"),
+            (b'O', b"\xd2\xd3\xd4\xd5\xd6\xd8"),
+            (b'U', b"\xd9\xda\xdb\xdc"),
+            (b'Y', b"\xdd"),
+            (b'a', b"\xe0\xe1\xe2\xe3\xe4\xe5"),
+            (b'c', b"\xe7"),
+            (b'd', b"\xf0"),
+            (b'e', b"\xe8\xe9\xea\xeb"),
+            (b'i', b"\xec\xed\xee\xef"),
+            (b'n', b"\xf1"),
+            (b'o', b"\xf2\xf3\xf4\xf5\xf6\xf8"),
+            (b'u', b"\xf9\xfa\xfb\xfc"),
+            (b'y', b"\xfd\xff")
+        ];
+
+        FOLDS.iter().fold(Equivalence::identity(), |equivalence, &(base, accented)| {
+            accented.iter().fold(equivalence, |equivalence, &byte| equivalence.with_class(&[base, byte]))
+        })
+    }
+
+    /// Makes all bytes in `bytes` equivalent to each other, merging their existing classes.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes to make equivalent.
+    ///
+    /// # Returns
+    ///
+    /// * `Equivalence` - The updated `Equivalence` instance.
+    pub fn with_class(mut self, bytes: &[u8]) -> Equivalence {
+        if let Some(&first) = bytes.first() {
+            let representative = self.fold(first);
+            let merged: Vec<u8> = bytes.iter().map(|&byte| self.fold(byte)).collect();
+
+            self.classes.iter_mut()
+                .filter(|class| merged.contains(class))
+                .for_each(|class| *class = representative);
+        }
+        self
+    }
+
+    /// Returns the representative of the equivalence class of the byte.
+    pub fn fold(&self, byte: u8) -> u8 {
+        self.classes[byte as usize]
+    }
+
+    /// Returns whether two bytes are in the same equivalence class.
+    pub fn is_equivalent(&self, first: u8, second: u8) -> bool {
+        self.fold(first) == self.fold(second)
+    }
+
+    /// Returns the number of bytes in the equivalence class of the byte.
+    pub fn class_size(&self, byte: u8) -> usize {
+        let class = self.fold(byte);
+        self.classes.iter().filter(|&&other| other == class).count()
+    }
+
+    pub(crate) fn from_classes(classes: [u8; 256]) -> Equivalence {
+        Equivalence { classes }
+    }
+}
+
+#[cfg(test)]
+#[path = "./equivalence_test.rs"]
+mod equivalence_test;
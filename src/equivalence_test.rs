@@ -0,0 +1,135 @@
+use super::*;
+use crate::{BndmConfig, ChunkFilter, HaystackIndex, find_pattern};
+
+#[test]
+fn identity_is_default() {
+    let equivalence = Equivalence::default();
+
+    assert_eq!(equivalence, Equivalence::identity());
+    assert!((0..=255).all(|byte| equivalence.fold(byte) == byte));
+}
+
+#[test]
+fn with_class_merges_classes() {
+    let equivalence = Equivalence::identity().with_class(b"ab").with_class(b"bc").with_class(b"xy");
+
+    assert!(equivalence.is_equivalent(b'a', b'c'));
+    assert!(equivalence.is_equivalent(b'x', b'y'));
+    assert!(!equivalence.is_equivalent(b'a', b'x'));
+    assert_eq!(equivalence.class_size(b'c'), 3);
+    assert_eq!(equivalence.class_size(b'd'), 1);
+}
+
+#[test]
+fn latin1_diacritics_fold_to_base_letters() {
+    let equivalence = Equivalence::latin1_diacritics();
+
+    assert_eq!(equivalence.fold(b'\xe9'), b'e');
+    assert_eq!(equivalence.fold(b'\xc5'), b'A');
+    assert_eq!(equivalence.fold(b'\xff'), b'y');
+    assert_eq!(equivalence.fold(b'\xd7'), b'\xd7');
+    assert_eq!(equivalence.fold(b'\xdf'), b'\xdf');
+    assert!(!equivalence.is_equivalent(b'e', b'E'));
+}
+
+#[test]
+fn find_pattern_with_diacritics_in_source() {
+    let config = BndmConfig::with_equivalence(b"naive", None, &Equivalence::latin1_diacritics());
+
+    assert_eq!(find_pattern(b"a na\xefve question", &config), Some(2));
+    assert_eq!(find_pattern(b"a native question", &config), None);
+}
+
+#[test]
+fn find_pattern_with_diacritics_in_pattern() {
+    let config = BndmConfig::with_equivalence(b"Z\xfcrich", None, &Equivalence::latin1_diacritics());
+
+    assert_eq!(find_pattern(b"from Zurich", &config), Some(5));
+    assert_eq!(find_pattern(b"from Z\xfbrich", &config), Some(5));
+}
+
+#[test]
+fn find_single_char_pattern_with_equivalence() {
+    let config = BndmConfig::with_equivalence(b"e", None, &Equivalence::latin1_diacritics());
+
+    assert_eq!(find_pattern(b"caf\xe9", &config), Some(3));
+}
+
+#[test]
+fn find_pattern_with_equivalence_and_wildcard() {
+    let config = BndmConfig::with_equivalence(b"r?sum?", Some(b'?'), &Equivalence::latin1_diacritics());
+
+    assert_eq!(find_pattern(b"my r\xe9sum\xe9", &config), Some(3));
+    assert_eq!(find_pattern(b"my r\xe9s\xfcmx", &config), Some(3));
+}
+
+#[test]
+fn find_pattern_longer_than_word_with_equivalence() {
+    let pattern = [b'e'; 70];
+    let mut source = vec![b'\xe9'; 70];
+    source[69] = b'\xea';
+    let config = BndmConfig::with_equivalence(&pattern, None, &Equivalence::latin1_diacritics());
+
+    assert_eq!(find_pattern(&source, &config), Some(0));
+
+    source[65] = b'x';
+    assert_eq!(find_pattern(&source, &config), None);
+}
+
+#[test]
+fn index_query_with_equivalence() {
+    let source = b"cr\xe8me br\xfbl\xe9e and creme brulee";
+    let index = HaystackIndex::build(source);
+    let config = BndmConfig::with_equivalence(b"creme", None, &Equivalence::latin1_diacritics());
+
+    assert_eq!(index.query(&config), vec![0, 17]);
+}
+
+#[test]
+fn chunk_filter_with_equivalence() {
+    let config = BndmConfig::with_equivalence(b"ee", None, &Equivalence::latin1_diacritics());
+    let filter = ChunkFilter::new(&[&config]);
+
+    assert!(filter.may_contain(b"\xe9\xe8"));
+}
+
+#[test]
+fn ascii_case_insensitive_classes() {
+    let equivalence = Equivalence::ascii_case_insensitive();
+
+    assert!(equivalence.is_equivalent(b'E', b'e'));
+    assert!(equivalence.is_equivalent(b'z', b'Z'));
+    assert!(!equivalence.is_equivalent(b'@', b'`'));
+    assert!(!equivalence.is_equivalent(b'\xc9', b'\xe9'));
+    assert_eq!(equivalence.class_size(b'a'), 2);
+    assert_eq!(equivalence.class_size(b'1'), 1);
+}
+
+#[test]
+fn find_pattern_nocase() {
+    let source = b"[12:00:01] Warning: retry; [12:00:02] ERROR: disk full";
+    let config = BndmConfig::new_nocase(b"error", None);
+
+    assert_eq!(find_pattern(source, &config), Some(38));
+    assert_eq!(find_pattern(source, &BndmConfig::new_nocase(b"WARNING", None)), Some(11));
+}
+
+#[test]
+fn find_pattern_nocase_with_wildcard() {
+    let source = b"Content-Length: 42";
+    let config = BndmConfig::new_nocase(b"content?length", Some(b'?'));
+
+    assert_eq!(find_pattern(source, &config), Some(0));
+}
+
+#[test]
+fn find_pattern_nocase_longer_than_word() {
+    let pattern = [b'a'; 70];
+    let mut source = vec![b'A'; 70];
+    let config = BndmConfig::new_nocase(&pattern, None);
+
+    assert_eq!(find_pattern(&source, &config), Some(0));
+
+    source[68] = b'b';
+    assert_eq!(find_pattern(&source, &config), None);
+}
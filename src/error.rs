@@ -0,0 +1,76 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! The error type for building configurations, and the constructor that reports it.
+
+use std::error::Error;
+use std::fmt;
+
+use crate::BndmConfig;
+
+/// The error type for building a `BndmConfig`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BndmError {
+    /// The pattern is empty, so it would never match.
+    EmptyPattern,
+
+    /// Every byte of the pattern is the wildcard character, so the pattern matches at every
+    /// index of a text that is long enough, and a pattern of a single wildcard also matches
+    /// an empty text.
+    WildcardOnly
+}
+
+impl fmt::Display for BndmError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BndmError::EmptyPattern => write!(f, "pattern is empty"),
+            BndmError::WildcardOnly => write!(f, "pattern only consists of wildcards")
+        }
+    }
+}
+
+impl Error for BndmError {}
+
+impl BndmConfig {
+    /// Creates a new `BndmConfig` instance, or returns an error if the pattern would never
+    /// match or would match everywhere.
+    ///
+    /// # Arguments
+    ///
+    /// * `search_pattern` - The pattern to search for in the text.
+    /// * `wildcard` - An optional wildcard character. If provided, this character in the pattern
+    ///   can match any character in the text.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<BndmConfig, BndmError>` - Returns a new `BndmConfig` instance, or an error if
+    ///   the pattern is empty or only consists of wildcards.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{BndmConfig, BndmError};
+    ///
+    /// assert!(BndmConfig::try_new(b"ju??s", Some(b'?')).is_ok());
+    /// assert_eq!(BndmConfig::try_new(b"", None).err(), Some(BndmError::EmptyPattern));
+    /// assert_eq!(BndmConfig::try_new(b"??", Some(b'?')).err(), Some(BndmError::WildcardOnly));
+    /// ```
+    pub fn try_new(search_pattern: &[u8], wildcard: Option<u8>) -> Result<BndmConfig, BndmError> {
+        validate_pattern(search_pattern, wildcard)?;
+        Ok(BndmConfig::new(search_pattern, wildcard))
+    }
+}
+
+pub(crate) fn validate_pattern(search_pattern: &[u8], wildcard: Option<u8>) -> Result<(), BndmError> {
+    if search_pattern.is_empty() {
+        return Err(BndmError::EmptyPattern);
+    }
+    match wildcard {
+        Some(wildcard) if search_pattern.iter().all(|&byte| byte == wildcard) => Err(BndmError::WildcardOnly),
+        _ => Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "./error_test.rs"]
+mod error_test;
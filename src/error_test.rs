@@ -0,0 +1,24 @@
+use super::*;
+use crate::find_pattern;
+
+#[test]
+fn try_new_valid_patterns() {
+    let config = BndmConfig::try_new(b"ju??s", Some(b'?')).unwrap();
+    assert_eq!(find_pattern(b"The quick brown fox jumps", &config), Some(20));
+
+    let config = BndmConfig::try_new(b"??", None).unwrap();
+    assert_eq!(find_pattern(b"a??", &config), Some(1));
+}
+
+#[test]
+fn try_new_empty_pattern() {
+    assert_eq!(BndmConfig::try_new(b"", None).err(), Some(BndmError::EmptyPattern));
+    assert_eq!(BndmConfig::try_new(b"", Some(b'?')).err(), Some(BndmError::EmptyPattern));
+}
+
+#[test]
+fn try_new_wildcard_only() {
+    assert_eq!(BndmConfig::try_new(b"?", Some(b'?')).err(), Some(BndmError::WildcardOnly));
+    assert_eq!(BndmConfig::try_new(&[b'?'; 70], Some(b'?')).err(), Some(BndmError::WildcardOnly));
+    assert_eq!(BndmError::WildcardOnly.to_string(), "pattern only consists of wildcards");
+}
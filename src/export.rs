@@ -0,0 +1,187 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! A versioned binary format for single configurations, for distributing precompiled
+//! signature databases.
+
+use std::error::Error;
+use std::fmt;
+use std::mem::size_of;
+use std::sync::Arc;
+
+use crate::masks::validate_view;
+use crate::{BndmConfig, ByteClass, Equivalence, MASKS_TABLE_SIZE, MaskTableError, WORD_SIZE_IN_BITS};
+
+const MAGIC: &[u8; 4] = b"BNDM";
+const FORMAT_VERSION: u16 = 1;
+const HEADER_SIZE: usize = 13;
+
+const FLAG_WILDCARD: u8 = 1;
+const FLAG_EQUIVALENCE: u8 = 2;
+const FLAG_CLASSES: u8 = 4;
+
+/// The error type for importing a configuration with `BndmConfig::from_bytes`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ImportError {
+    /// The bytes do not start with the header of an exported configuration.
+    InvalidHeader,
+
+    /// The configuration was exported in a version of the format that is not supported.
+    UnsupportedVersion(u16),
+
+    /// The bitmasks were compiled for a different word size than the word size of this target.
+    WordSizeMismatch { expected: usize, found: usize },
+
+    /// The bytes end before the configuration is complete.
+    Truncated,
+
+    /// There are bytes left after the configuration.
+    TrailingBytes(usize),
+
+    /// The bitmasks can't be used to scan a text, or do not agree with the pattern.
+    InvalidMasks(MaskTableError)
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ImportError::InvalidHeader => write!(f, "invalid header"),
+            ImportError::UnsupportedVersion(version) => write!(f, "unsupported format version {version}"),
+            ImportError::WordSizeMismatch { expected, found } =>
+                write!(f, "bitmasks of word size {found} can't be used with word size {expected}"),
+            ImportError::Truncated => write!(f, "unexpected end of data"),
+            ImportError::TrailingBytes(count) => write!(f, "{count} trailing bytes after the configuration"),
+            ImportError::InvalidMasks(error) => write!(f, "invalid bitmasks: {error}")
+        }
+    }
+}
+
+impl Error for ImportError {}
+
+impl BndmConfig {
+    /// Exports the compiled configuration to bytes that can be imported with
+    /// `BndmConfig::from_bytes`.
+    ///
+    /// The bytes start with a header that holds the version of the format and the word size
+    /// the bitmasks were compiled for. All numbers are stored in little-endian byte order.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<u8>` - The bytes of the exported configuration.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{BndmConfig, find_pattern};
+    ///
+    /// let bytes = BndmConfig::new(b"ju??s", Some(b'?')).to_bytes();
+    /// let config = BndmConfig::from_bytes(&bytes).unwrap();
+    /// assert_eq!(find_pattern(b"The quick brown fox jumps", &config), Some(20));
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let flags = self.wildcard.map_or(0, |_| FLAG_WILDCARD)
+            | self.equivalence.as_ref().map_or(0, |_| FLAG_EQUIVALENCE)
+            | self.classes.as_ref().map_or(0, |_| FLAG_CLASSES);
+
+        let mut bytes = Vec::with_capacity(HEADER_SIZE + MASKS_TABLE_SIZE * size_of::<usize>() + self.pattern.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&[WORD_SIZE_IN_BITS as u8, flags, self.wildcard.unwrap_or(0)]);
+        bytes.extend_from_slice(&(self.pattern.len() as u32).to_le_bytes());
+
+        self.masks.iter().for_each(|mask| bytes.extend_from_slice(&mask.to_le_bytes()));
+        if let Some(equivalence) = &self.equivalence {
+            bytes.extend((0..=u8::MAX).map(|byte| equivalence.fold(byte)));
+        }
+        if let Some(classes) = &self.classes {
+            classes.iter().for_each(|class| bytes.extend(class.bits().iter().flat_map(|bits| bits.to_le_bytes())));
+        }
+        bytes.extend_from_slice(&self.pattern);
+        bytes
+    }
+
+    /// Imports a configuration that was exported with `BndmConfig::to_bytes`, without
+    /// compiling the pattern again.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes of the exported configuration.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<BndmConfig, ImportError>` - Returns the configuration, or an error if the
+    ///   bytes are not a complete configuration, were exported in an unsupported version of
+    ///   the format, were compiled for a different word size, or contain bitmasks that do not
+    ///   fit the pattern.
+    pub fn from_bytes(bytes: &[u8]) -> Result<BndmConfig, ImportError> {
+        let mut reader = Reader { bytes };
+        if reader.take(MAGIC.len()).ok() != Some(MAGIC.as_slice()) {
+            return Err(ImportError::InvalidHeader);
+        }
+        let version = u16::from_le_bytes(reader.array()?);
+        if version != FORMAT_VERSION {
+            return Err(ImportError::UnsupportedVersion(version));
+        }
+        let [word_size, flags, wildcard] = reader.array()?;
+        if word_size as usize != WORD_SIZE_IN_BITS {
+            return Err(ImportError::WordSizeMismatch { expected: WORD_SIZE_IN_BITS, found: word_size as usize });
+        }
+        let pattern_len = u32::from_le_bytes(reader.array()?) as usize;
+
+        let mut masks = [0; MASKS_TABLE_SIZE];
+        for mask in masks.iter_mut() {
+            *mask = usize::from_le_bytes(reader.array()?);
+        }
+        let equivalence = match flags & FLAG_EQUIVALENCE {
+            0 => None,
+            _ => Some(Equivalence::from_classes(reader.array()?))
+        };
+        let classes = match flags & FLAG_CLASSES {
+            0 => None,
+            _ => Some((0..pattern_len).map(|_| {
+                let mut bits = [0; 4];
+                for word in bits.iter_mut() {
+                    *word = u64::from_le_bytes(reader.array()?);
+                }
+                Ok(ByteClass::from_bits(bits))
+            }).collect::<Result<Vec<_>, _>>()?)
+        };
+        let pattern = reader.take(pattern_len)?.to_vec();
+
+        if !reader.bytes.is_empty() {
+            return Err(ImportError::TrailingBytes(reader.bytes.len()));
+        }
+        let config = BndmConfig {
+            masks: Arc::new(masks),
+            wildcard: (flags & FLAG_WILDCARD != 0).then_some(wildcard),
+            pattern,
+            equivalence,
+            classes
+        };
+        validate_view(&config.view()).map_err(ImportError::InvalidMasks)?;
+        Ok(config)
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8]
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ImportError> {
+        if len > self.bytes.len() {
+            return Err(ImportError::Truncated);
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], ImportError> {
+        self.take(N).map(|bytes| bytes.try_into().unwrap())
+    }
+}
+
+#[cfg(test)]
+#[path = "./export_test.rs"]
+mod export_test;
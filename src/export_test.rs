@@ -0,0 +1,86 @@
+use super::*;
+use crate::{PatternBuilder, find_pattern};
+
+#[test]
+fn export_round_trip() {
+    let configs = [
+        BndmConfig::new(b"fox", None),
+        BndmConfig::new(b"ju??s", Some(b'?')),
+        BndmConfig::new_nocase(b"LAZY", None),
+        PatternBuilder::new().literal(b"d").range(b'n'..=b'o').any().build(),
+        BndmConfig::new(&[b'a'; 70], None)
+    ];
+
+    let source = b"The quick brown fox jumps over the lazy dog";
+    for config in &configs {
+        let imported = BndmConfig::from_bytes(&config.to_bytes()).unwrap();
+        assert_eq!(imported, *config);
+        assert_eq!(find_pattern(source, &imported), find_pattern(source, config));
+    }
+}
+
+#[test]
+fn export_header() {
+    let bytes = BndmConfig::new(b"fox", Some(b'?')).to_bytes();
+
+    assert_eq!(&bytes[..4], b"BNDM");
+    assert_eq!(&bytes[4..6], &[1, 0]);
+    assert_eq!(bytes[6] as usize, WORD_SIZE_IN_BITS);
+    assert_eq!(&bytes[7..9], &[FLAG_WILDCARD, b'?']);
+    assert_eq!(&bytes[9..13], &[3, 0, 0, 0]);
+    assert_eq!(bytes.len(), HEADER_SIZE + MASKS_TABLE_SIZE * size_of::<usize>() + 3);
+}
+
+#[test]
+fn import_errors() {
+    let bytes = BndmConfig::new(b"fox", None).to_bytes();
+
+    assert_eq!(BndmConfig::from_bytes(b"BND").err(), Some(ImportError::InvalidHeader));
+    assert_eq!(BndmConfig::from_bytes(&bytes[1..]).err(), Some(ImportError::InvalidHeader));
+    assert_eq!(BndmConfig::from_bytes(&bytes[..bytes.len() - 1]).err(), Some(ImportError::Truncated));
+    assert_eq!(BndmConfig::from_bytes(&bytes[..5]).err(), Some(ImportError::Truncated));
+    assert_eq!(BndmConfig::from_bytes(&[bytes.as_slice(), b"!"].concat()).err(), Some(ImportError::TrailingBytes(1)));
+
+    let mut newer = bytes.clone();
+    newer[4] = 2;
+    assert_eq!(BndmConfig::from_bytes(&newer).err(), Some(ImportError::UnsupportedVersion(2)));
+
+    let mut other_word_size = bytes.clone();
+    other_word_size[6] = if WORD_SIZE_IN_BITS == 64 { 32 } else { 64 };
+    assert_eq!(BndmConfig::from_bytes(&other_word_size).err(),
+        Some(ImportError::WordSizeMismatch { expected: WORD_SIZE_IN_BITS, found: other_word_size[6] as usize }));
+    assert_eq!(ImportError::WordSizeMismatch { expected: 32, found: 64 }.to_string(),
+        "bitmasks of word size 64 can't be used with word size 32");
+}
+
+#[test]
+fn import_rejects_invalid_masks() {
+    let mut bytes = BndmConfig::new(b"ab", None).to_bytes();
+    let masks_len = MASKS_TABLE_SIZE * size_of::<usize>();
+    bytes[HEADER_SIZE..HEADER_SIZE + masks_len].chunks_mut(size_of::<usize>())
+        .for_each(|mask| mask.copy_from_slice(&(!2usize).to_le_bytes()));
+    assert_eq!(BndmConfig::from_bytes(&bytes).err(), Some(ImportError::InvalidMasks(MaskTableError::UnusedBits(0))));
+
+    let mut bytes = BndmConfig::new(b"ab", None).to_bytes();
+    let last = bytes.len() - 1;
+    bytes[last] = b'c';
+    assert_eq!(BndmConfig::from_bytes(&bytes).err(), Some(ImportError::InvalidMasks(MaskTableError::InconsistentPattern(1))));
+
+    let mut bytes = BndmConfig::new(b"", None).to_bytes();
+    bytes[HEADER_SIZE] = 1;
+    assert_eq!(BndmConfig::from_bytes(&bytes).err(), Some(ImportError::InvalidMasks(MaskTableError::UnusedBits(0))));
+}
+
+#[test]
+fn import_accepts_all_constructors() {
+    let configs = [
+        BndmConfig::new(b"", None),
+        BndmConfig::with_classes(&[ByteClass::byte(b'a'), ByteClass::empty(), ByteClass::any()]),
+        BndmConfig::from_hex("4? [2] 8B ?? C3").unwrap(),
+        BndmConfig::new_nocase(&[b'x'; 80], Some(b'?'))
+    ];
+
+    for config in configs {
+        assert_eq!(BndmConfig::from_bytes(&config.to_bytes()), Ok(config));
+    }
+}
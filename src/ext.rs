@@ -0,0 +1,63 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! An extension trait for searching byte slices with method calls.
+
+use crate::{BndmConfig, find_all, find_pattern, rfind_pattern};
+
+/// The `FindBndm` trait adds BNDM searches to byte slices, and to anything that dereferences
+/// to a byte slice such as `Vec<u8>` and byte arrays.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{BndmConfig, FindBndm};
+///
+/// let source = b"The quick brown fox jumps over the lazy dog";
+/// let config = BndmConfig::new(b"fox", None);
+/// assert_eq!(source.find_bndm(&config), Some(16));
+/// assert_eq!(source.find_bndm_pattern(b"ju??s", Some(b'?')), Some(20));
+/// ```
+pub trait FindBndm {
+    /// Returns the index of the first occurrence of the pattern, or `None` if the pattern is
+    /// not found.
+    fn find_bndm(&self, config: &BndmConfig) -> Option<usize>;
+
+    /// Returns the index of the last occurrence of the pattern, or `None` if the pattern is
+    /// not found.
+    fn rfind_bndm(&self, config: &BndmConfig) -> Option<usize>;
+
+    /// Returns the indexes of all non-overlapping occurrences of the pattern.
+    fn find_all_bndm(&self, config: &BndmConfig) -> Vec<usize>;
+
+    /// Compiles the pattern and returns the index of its first occurrence, or `None` if the
+    /// pattern is not found. This is meant for one-off searches; build a `BndmConfig` once to
+    /// search for the same pattern repeatedly.
+    ///
+    /// # Arguments
+    ///
+    /// * `search_pattern` - The pattern to search for.
+    /// * `wildcard` - An optional wildcard character. If provided, this character in the
+    ///   pattern can match any character.
+    fn find_bndm_pattern(&self, search_pattern: &[u8], wildcard: Option<u8>) -> Option<usize> {
+        self.find_bndm(&BndmConfig::new(search_pattern, wildcard))
+    }
+}
+
+impl FindBndm for [u8] {
+    fn find_bndm(&self, config: &BndmConfig) -> Option<usize> {
+        find_pattern(self, config)
+    }
+
+    fn rfind_bndm(&self, config: &BndmConfig) -> Option<usize> {
+        rfind_pattern(self, config)
+    }
+
+    fn find_all_bndm(&self, config: &BndmConfig) -> Vec<usize> {
+        find_all(self, config)
+    }
+}
+
+#[cfg(test)]
+#[path = "./ext_test.rs"]
+mod ext_test;
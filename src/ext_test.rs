@@ -0,0 +1,21 @@
+use super::*;
+
+#[test]
+fn find_in_slices_and_vectors() {
+    let source = b"The quick brown fox jumps over the lazy dog".to_vec();
+    let config = BndmConfig::new(b"o", None);
+
+    assert_eq!(source.find_bndm(&config), Some(12));
+    assert_eq!(source.rfind_bndm(&config), Some(41));
+    assert_eq!(source[..20].find_all_bndm(&config), vec![12, 17]);
+    assert_eq!(b"abc".find_bndm(&config), None);
+}
+
+#[test]
+fn find_one_off_pattern() {
+    let source: &[u8] = b"The quick brown fox jumps over the lazy dog";
+
+    assert_eq!(source.find_bndm_pattern(b"l??y", Some(b'?')), Some(35));
+    assert_eq!(source.find_bndm_pattern(b"l??y", None), None);
+    assert_eq!(source.find_bndm_pattern(b"", None), None);
+}
@@ -0,0 +1,108 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Configurations for patterns of a fixed size that are built at compile time, e.g. for
+//! signatures in `static` items.
+
+use std::sync::Arc;
+
+use crate::{BndmConfig, MASKS_TABLE_SIZE, PatternView, WORD_SIZE_IN_BITS, find_first_in_view};
+
+/// The `FixedConfig` struct is used to store a pattern of a fixed size and the bitmasks.
+///
+/// Unlike `BndmConfig`, a `FixedConfig` can be created in a const context, so the bitmasks of
+/// a static signature are computed by the compiler and can be shared across threads without
+/// any initialization at runtime.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FixedConfig<const N: usize> {
+    masks: [usize; MASKS_TABLE_SIZE],
+    wildcard: Option<u8>,
+    pattern: [u8; N]
+}
+
+impl<const N: usize> FixedConfig<N> {
+    /// Creates a new `FixedConfig` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `search_pattern` - The pattern to search for in the text.
+    /// * `wildcard` - An optional wildcard character. If provided, this character in the
+    ///   pattern can match any character in the text.
+    ///
+    /// # Returns
+    ///
+    /// * `FixedConfig` - A new `FixedConfig` instance.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::FixedConfig;
+    ///
+    /// static PE_HEADER: FixedConfig<4> = FixedConfig::new(b"PE\0\0", None);
+    ///
+    /// assert_eq!(PE_HEADER.find(b"MZ..PE\0\0"), Some(4));
+    /// ```
+    pub const fn new(search_pattern: &[u8; N], wildcard: Option<u8>) -> FixedConfig<N> {
+        let filter_len = if N < WORD_SIZE_IN_BITS { N } else { WORD_SIZE_IN_BITS };
+
+        let mut wildcard_mask = 0;
+        if let Some(wildcard) = wildcard {
+            let mut position = 0;
+            while position < filter_len {
+                wildcard_mask = (wildcard_mask << 1) | (search_pattern[position] == wildcard) as usize;
+                position += 1;
+            }
+        }
+
+        let mut masks = [wildcard_mask; MASKS_TABLE_SIZE];
+        let mut position = 0;
+        while position < filter_len {
+            masks[search_pattern[position] as usize] |= 1 << (filter_len - 1 - position);
+            position += 1;
+        }
+
+        FixedConfig { masks, wildcard, pattern: *search_pattern }
+    }
+
+    /// Returns the pattern.
+    pub const fn pattern(&self) -> &[u8; N] {
+        &self.pattern
+    }
+
+    /// Returns the wildcard character.
+    pub const fn wildcard(&self) -> Option<u8> {
+        self.wildcard
+    }
+
+    /// Returns the index of the first occurrence of the pattern in the source, or `None` if
+    /// the pattern is not found.
+    pub fn find(&self, source: &[u8]) -> Option<usize> {
+        find_first_in_view(source, &self.view())
+    }
+
+    /// Returns a `BndmConfig` instance for the pattern, for use with the other search
+    /// functions of the crate.
+    pub fn to_config(&self) -> BndmConfig {
+        BndmConfig {
+            masks: Arc::new(self.masks),
+            wildcard: self.wildcard,
+            pattern: self.pattern.to_vec(),
+            equivalence: None,
+            classes: None
+        }
+    }
+
+    fn view(&self) -> PatternView<'_> {
+        PatternView {
+            masks: &self.masks,
+            wildcard: self.wildcard,
+            pattern: &self.pattern,
+            equivalence: None,
+            classes: None
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "./fixed_test.rs"]
+mod fixed_test;
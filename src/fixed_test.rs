@@ -0,0 +1,57 @@
+use super::*;
+use crate::find_pattern;
+
+static FOX: FixedConfig<3> = FixedConfig::new(b"fox", None);
+const JUMPS: FixedConfig<5> = FixedConfig::new(b"ju??s", Some(b'?'));
+
+#[test]
+fn find_in_static() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+
+    assert_eq!(FOX.find(source), Some(16));
+    assert_eq!(JUMPS.find(source), Some(20));
+    assert_eq!(FOX.find(b"The quick brown cat"), None);
+}
+
+#[test]
+fn masks_equal_runtime_masks() {
+    let configs = [
+        (FixedConfig::new(b"ju??s", Some(b'?')).to_config(), BndmConfig::new(b"ju??s", Some(b'?'))),
+        (FixedConfig::new(b"aaaa", None).to_config(), BndmConfig::new(b"aaaa", None)),
+        (FixedConfig::new(&[b'?'; 70], Some(b'?')).to_config(), BndmConfig::new(&[b'?'; 70], Some(b'?')))
+    ];
+
+    for (fixed, runtime) in configs {
+        assert_eq!(fixed.masks, runtime.masks);
+        assert_eq!(fixed.pattern, runtime.pattern);
+        assert_eq!(fixed.wildcard, runtime.wildcard);
+    }
+}
+
+#[test]
+fn single_byte_and_empty_patterns() {
+    assert_eq!(FixedConfig::new(b"o", None).find(b"fox"), Some(1));
+    assert_eq!(FixedConfig::new(b"?", Some(b'?')).find(b""), Some(0));
+    assert_eq!(FixedConfig::new(b"", None).find(b"fox"), None);
+}
+
+#[test]
+fn long_pattern() {
+    const LONG: FixedConfig<70> = {
+        let mut pattern = [b'x'; 70];
+        pattern[69] = b'y';
+        FixedConfig::new(&pattern, None)
+    };
+
+    let mut source = vec![b'x'; 100];
+    assert_eq!(LONG.find(&source), None);
+    source[90] = b'y';
+    assert_eq!(LONG.find(&source), Some(21));
+    assert_eq!(find_pattern(&source, &LONG.to_config()), Some(21));
+}
+
+#[test]
+fn accessors() {
+    assert_eq!(JUMPS.pattern(), b"ju??s");
+    assert_eq!(JUMPS.wildcard(), Some(b'?'));
+}
@@ -0,0 +1,213 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Patterns with gaps of a bounded, variable number of bytes between their parts, e.g.
+//! `A?{2,5}B`.
+
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+use std::ops::{Range, RangeInclusive};
+
+use crate::{BndmConfig, find_pattern_from, matches_at};
+
+/// The error type for parsing patterns with gaps.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GapPatternError {
+    /// The pattern has no bytes.
+    Empty,
+
+    /// The gap at the given index is malformed, has a minimum larger than its maximum, or is
+    /// not surrounded by bytes of the pattern.
+    InvalidGap(usize)
+}
+
+impl fmt::Display for GapPatternError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GapPatternError::Empty => write!(f, "pattern is empty"),
+            GapPatternError::InvalidGap(index) => write!(f, "invalid gap at index {index}")
+        }
+    }
+}
+
+impl Error for GapPatternError {}
+
+/// The `GapPattern` struct holds a sequence of patterns that are separated by gaps of a
+/// bounded number of arbitrary bytes.
+///
+/// The first pattern is searched for with BNDM. Every following pattern is verified within
+/// the window that its gap allows after the end of the previous pattern.
+pub struct GapPattern {
+    head: BndmConfig,
+    tail: Vec<(RangeInclusive<usize>, BndmConfig)>
+}
+
+impl GapPattern {
+    /// Creates a new `GapPattern` instance that starts with the given pattern.
+    ///
+    /// # Arguments
+    ///
+    /// * `head` - The configuration of the first pattern.
+    ///
+    /// # Returns
+    ///
+    /// * `GapPattern` - A new `GapPattern` instance.
+    pub fn new(head: BndmConfig) -> GapPattern {
+        GapPattern { head, tail: Vec::new() }
+    }
+
+    /// Appends a gap and a pattern that should follow the gap.
+    ///
+    /// # Arguments
+    ///
+    /// * `gap` - The minimum and maximum number of arbitrary bytes between the end of the
+    ///   previous pattern and the start of the given pattern.
+    /// * `config` - The configuration of the pattern that follows the gap.
+    ///
+    /// # Returns
+    ///
+    /// * `GapPattern` - The `GapPattern` instance with the gap and pattern appended.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{BndmConfig, GapPattern};
+    ///
+    /// let pattern = GapPattern::new(BndmConfig::new(b"fox", None))
+    ///     .then(10..=12, BndmConfig::new(b"the", None));
+    /// assert_eq!(pattern.find(b"The quick brown fox jumps over the lazy dog"), Some(16..34));
+    /// ```
+    pub fn then(mut self, gap: RangeInclusive<usize>, config: BndmConfig) -> GapPattern {
+        self.tail.push((gap, config));
+        self
+    }
+
+    /// Parses a pattern in which gaps are written as the wildcard character followed by the
+    /// bounds of the gap in braces, e.g. `?{2,5}` for 2 to 5 arbitrary bytes or `?{3}` for
+    /// exactly 3 arbitrary bytes. A wildcard character without braces matches a single byte.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The pattern with gaps.
+    /// * `wildcard` - The wildcard character.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<GapPattern, GapPatternError>` - Returns a new `GapPattern` instance, or an
+    ///   error if the pattern is empty or contains an invalid gap.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::GapPattern;
+    ///
+    /// let pattern = GapPattern::parse(b"A?{2,5}B", b'?').unwrap();
+    /// assert_eq!(pattern.find(b"A1B xx A12B"), Some(7..11));
+    /// ```
+    pub fn parse(pattern: &[u8], wildcard: u8) -> Result<GapPattern, GapPatternError> {
+        let mut parts: Vec<(RangeInclusive<usize>, Vec<u8>)> = Vec::new();
+        let mut gap = 0..=0;
+        let mut gap_index = None;
+        let mut part = Vec::new();
+        let mut index = 0;
+
+        while index < pattern.len() {
+            if pattern[index] == wildcard && pattern.get(index + 1) == Some(&b'{') {
+                let close = pattern[index..].iter().position(|&byte| byte == b'}')
+                    .ok_or(GapPatternError::InvalidGap(index))? + index;
+                if part.is_empty() {
+                    return Err(GapPatternError::InvalidGap(index));
+                }
+                parts.push((gap, std::mem::take(&mut part)));
+                gap = parse_gap(&pattern[index + 2..close]).ok_or(GapPatternError::InvalidGap(index))?;
+                gap_index = Some(index);
+                index = close + 1;
+            } else {
+                part.push(pattern[index]);
+                index += 1;
+            }
+        }
+
+        match gap_index {
+            _ if !part.is_empty() => parts.push((gap, part)),
+            Some(index) => return Err(GapPatternError::InvalidGap(index)),
+            None => return Err(GapPatternError::Empty)
+        }
+
+        let mut parts = parts.into_iter().map(|(gap, part)| (gap, BndmConfig::new(&part, Some(wildcard))));
+        let (_, head) = parts.next().ok_or(GapPatternError::Empty)?;
+        Ok(GapPattern { head, tail: parts.collect() })
+    }
+
+    /// Returns the range of the first occurrence of the pattern in the source, or `None` if
+    /// the pattern is not found.
+    ///
+    /// For the leftmost occurrence of the first pattern that can be completed, the following
+    /// patterns are taken at the earliest positions their gaps allow.
+    pub fn find(&self, source: &[u8]) -> Option<Range<usize>> {
+        self.find_from(source, 0)
+    }
+
+    /// Returns the range of the first occurrence of the pattern in the source that starts at
+    /// or after the start index, or `None` if the pattern is not found.
+    pub fn find_from(&self, source: &[u8], start_index: usize) -> Option<Range<usize>> {
+        let mut start_index = start_index;
+        let mut failed = HashSet::new();
+        while let Some(index) = find_pattern_from(source, &self.head, start_index) {
+            if let Some(end) = self.match_tail(source, 0, index + self.head.pattern.len(), &mut failed) {
+                return Some(index..end);
+            }
+            start_index = index + 1;
+        }
+        None
+    }
+
+    /// Returns the ranges of all non-overlapping occurrences of the pattern in the source.
+    pub fn find_all(&self, source: &[u8]) -> Vec<Range<usize>> {
+        let mut ranges = Vec::new();
+        let mut start_index = 0;
+        while let Some(range) = self.find_from(source, start_index) {
+            start_index = range.end.max(range.start + 1);
+            ranges.push(range);
+        }
+        ranges
+    }
+
+    // Returns the end of the earliest completion of the parts from the given part on. Whether
+    // a part can be completed at an end does not depend on how that end was reached, so failed
+    // pairs are remembered to keep the search linear in the number of gap positions.
+    fn match_tail(&self, source: &[u8], part: usize, end: usize, failed: &mut HashSet<(usize, usize)>) -> Option<usize> {
+        let Some((gap, config)) = self.tail.get(part) else {
+            return Some(end);
+        };
+        if failed.contains(&(part, end)) {
+            return None;
+        }
+
+        let first = end.saturating_add(*gap.start());
+        let last = end.saturating_add(*gap.end()).min(source.len());
+        let tail_end = (first..=last)
+            .filter(|&index| matches_at(source, config, index))
+            .find_map(|index| self.match_tail(source, part + 1, index + config.pattern.len(), failed));
+        if tail_end.is_none() {
+            failed.insert((part, end));
+        }
+        tail_end
+    }
+}
+
+fn parse_gap(bounds: &[u8]) -> Option<RangeInclusive<usize>> {
+    if bounds.is_empty() || !bounds.iter().all(|&byte| byte.is_ascii_digit() || byte == b',') {
+        return None;
+    }
+
+    let bounds = std::str::from_utf8(bounds).ok()?;
+    let (min, max) = bounds.split_once(',').unwrap_or((bounds, bounds));
+    let (min, max) = (min.parse().ok()?, max.parse().ok()?);
+    (min <= max).then_some(min..=max)
+}
+
+#[cfg(test)]
+#[path = "./gap_test.rs"]
+mod gap_test;
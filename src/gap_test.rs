@@ -0,0 +1,78 @@
+use super::*;
+
+#[test]
+fn parse_gap_pattern() {
+    let pattern = GapPattern::parse(b"A?{2,5}B", b'?').unwrap();
+
+    assert_eq!(pattern.find(b"A1B xx A12B"), Some(7..11));
+    assert_eq!(pattern.find(b"A123456B"), None);
+    assert_eq!(pattern.find(b"A12345B"), Some(0..7));
+}
+
+#[test]
+fn parse_exact_gap() {
+    let pattern = GapPattern::parse(b"ab?{3}c?d", b'?').unwrap();
+
+    assert_eq!(pattern.find(b"ab12c_d ab123c_d"), Some(8..16));
+}
+
+#[test]
+fn parse_invalid_gaps() {
+    assert_eq!(GapPattern::parse(b"", b'?').err(), Some(GapPatternError::Empty));
+    assert_eq!(GapPattern::parse(b"?{2}A", b'?').err(), Some(GapPatternError::InvalidGap(0)));
+    assert_eq!(GapPattern::parse(b"A?{2}", b'?').err(), Some(GapPatternError::InvalidGap(1)));
+    assert_eq!(GapPattern::parse(b"A?{2}?{3}B", b'?').err(), Some(GapPatternError::InvalidGap(5)));
+    assert_eq!(GapPattern::parse(b"A?{5,2}B", b'?').err(), Some(GapPatternError::InvalidGap(1)));
+    assert_eq!(GapPattern::parse(b"A?{+2}B", b'?').err(), Some(GapPatternError::InvalidGap(1)));
+    assert_eq!(GapPattern::parse(b"A?{2B", b'?').err(), Some(GapPatternError::InvalidGap(1)));
+    assert_eq!(GapPattern::parse(b"A?{}B", b'?').err(), Some(GapPatternError::InvalidGap(1)));
+}
+
+#[test]
+fn backtracks_over_gap_candidates() {
+    let pattern = GapPattern::new(BndmConfig::new(b"a", None))
+        .then(0..=4, BndmConfig::new(b"b", None))
+        .then(0..=0, BndmConfig::new(b"c", None));
+
+    assert_eq!(pattern.find(b"a_b_bc"), Some(0..6));
+}
+
+#[test]
+fn gap_at_end_of_source() {
+    let pattern = GapPattern::new(BndmConfig::new(b"ab", None))
+        .then(1..=usize::MAX, BndmConfig::new(b"c", None));
+
+    assert_eq!(pattern.find(b"abc ab c"), Some(0..8));
+    assert_eq!(pattern.find(b"abc"), None);
+}
+
+#[test]
+fn find_all_gap_patterns() {
+    let pattern = GapPattern::parse(b"<?{0,3}>", b'?').unwrap();
+
+    assert_eq!(pattern.find_all(b"<> <a> <abcd> <abc>"), vec![0..2, 3..6, 14..19]);
+    assert_eq!(pattern.find_from(b"<> <a>", 1), Some(3..6));
+}
+
+#[test]
+fn long_parts() {
+    let head = vec![b'x'; 70];
+    let pattern = GapPattern::new(BndmConfig::new(&head, None))
+        .then(2..=3, BndmConfig::new(&[b'y'; 66], None));
+
+    let mut source = head.clone();
+    source.extend_from_slice(b"..");
+    source.extend_from_slice(&[b'y'; 66]);
+    assert_eq!(pattern.find(&source), Some(0..138));
+}
+
+#[test]
+fn many_gaps_without_match() {
+    let pattern = (0..20).fold(GapPattern::new(BndmConfig::new(b"a", None)), |pattern, _| {
+        pattern.then(0..=20, BndmConfig::new(b"a", None))
+    }).then(0..=20, BndmConfig::new(b"b", None));
+    let source = [b'a'; 2000];
+
+    assert_eq!(pattern.find(&source), None);
+    assert_eq!(pattern.find_all(&source), vec![]);
+}
@@ -0,0 +1,136 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Parsing of hexadecimal signatures with nibble wildcards, e.g. `4? 8B ?5`.
+
+use std::error::Error;
+use std::fmt;
+
+use crate::{BndmConfig, PatternBuilder};
+
+/// The largest number of bytes a single skip token can skip.
+const MAX_SKIP: usize = 0x10000;
+
+/// The error type for parsing hexadecimal signatures.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HexPatternError {
+    /// The signature has no bytes.
+    Empty,
+
+    /// The token at the given index is not a byte of two hexadecimal digits or wildcards.
+    InvalidToken(usize)
+}
+
+impl fmt::Display for HexPatternError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HexPatternError::Empty => write!(f, "signature is empty"),
+            HexPatternError::InvalidToken(index) => write!(f, "invalid token at index {index}")
+        }
+    }
+}
+
+impl Error for HexPatternError {}
+
+impl BndmConfig {
+    /// Creates a new `BndmConfig` instance from a hexadecimal signature.
+    ///
+    /// The signature consists of bytes of two hexadecimal digits separated by whitespace.
+    /// Either digit can be replaced by `?` to match any value of that nibble, so `4?` matches
+    /// the bytes `$40` to `$4F`, and `??` or a single `?`, as in IDA and x64dbg signatures,
+    /// matches any byte. Leading and trailing wildcards are kept, so they require bytes before
+    /// and after the other bytes of the signature. A range of bytes is written as
+    /// `[30-39]`, and a number of bytes to skip is written in decimal as `[8]`, which is the
+    /// same as writing `??` that many times. A single skip is at most 65536 bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `signature` - The hexadecimal signature.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<BndmConfig, HexPatternError>` - Returns a new `BndmConfig` instance, or an
+    ///   error if the signature is empty or contains an invalid token.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{BndmConfig, find_pattern};
+    ///
+    /// let config = BndmConfig::from_hex("4? 8B ?5").unwrap();
+    /// assert_eq!(find_pattern(b"\x90\x48\x8b\x05\x90", &config), Some(1));
+    /// assert_eq!(find_pattern(b"\x90\x48\x8b\x06\x90", &config), None);
+    ///
+    /// let config = BndmConfig::from_hex("3A [30-39] [30-39]").unwrap();
+    /// assert_eq!(find_pattern(b"12:3x 12:34", &config), Some(8));
+    ///
+    /// let config = BndmConfig::from_hex("E8 ? ? ? ? 48 8B 05 ?").unwrap();
+    /// assert_eq!(find_pattern(b"\xe8\x01\x02\x03\x04\x48\x8b\x05\x10", &config), Some(0));
+    ///
+    /// let config = BndmConfig::from_hex("E8 [4] C3").unwrap();
+    /// assert_eq!(find_pattern(b"\x90\xe8\x10\x20\x30\x40\xc3", &config), Some(1));
+    /// ```
+    pub fn from_hex(signature: &str) -> Result<BndmConfig, HexPatternError> {
+        let builder = signature.split_whitespace().enumerate().try_fold(PatternBuilder::new(), |builder, (index, token)| {
+            parse_token(builder, token).ok_or(HexPatternError::InvalidToken(index))
+        })?;
+
+        if builder.is_empty() {
+            return Err(HexPatternError::Empty);
+        }
+        Ok(builder.build())
+    }
+}
+
+fn parse_token(builder: PatternBuilder, token: &str) -> Option<PatternBuilder> {
+    if token == "?" {
+        return Some(builder.any());
+    }
+
+    match token.strip_prefix('[').and_then(|range| range.strip_suffix(']')) {
+        Some(range) => match range.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (parse_hex_digits(start)?, parse_hex_digits(end)?);
+                (start <= end).then(|| builder.range(start..=end))
+            }
+            None => parse_count(range).map(|count| builder.skip(count))
+        },
+        None => parse_hex_byte(token).map(|(value, mask)| builder.masked(value, mask))
+    }
+}
+
+fn parse_count(digits: &str) -> Option<usize> {
+    match digits.bytes().all(|digit| digit.is_ascii_digit()) {
+        true => digits.parse().ok().filter(|&count| count <= MAX_SKIP),
+        false => None
+    }
+}
+
+fn parse_hex_digits(digits: &str) -> Option<u8> {
+    match parse_hex_byte(digits)? {
+        (value, 0xff) => Some(value),
+        _ => None
+    }
+}
+
+/// Parses a byte of two hexadecimal digits or wildcards into a value and a mask of the digits
+/// that are not wildcards.
+pub(crate) fn parse_hex_byte(token: &str) -> Option<(u8, u8)> {
+    let &[high, low] = token.as_bytes() else {
+        return None;
+    };
+    let (high_value, high_mask) = parse_nibble(high)?;
+    let (low_value, low_mask) = parse_nibble(low)?;
+    Some(((high_value << 4) | low_value, (high_mask << 4) | low_mask))
+}
+
+fn parse_nibble(digit: u8) -> Option<(u8, u8)> {
+    match digit {
+        b'?' => Some((0, 0)),
+        _ => (digit as char).to_digit(16).map(|value| (value as u8, 0xf))
+    }
+}
+
+#[cfg(test)]
+#[path = "./hex_test.rs"]
+mod hex_test;
@@ -0,0 +1,147 @@
+use super::*;
+use crate::{ByteClass, find_pattern, rfind_pattern};
+
+#[test]
+fn from_hex_literal_bytes() {
+    let config = BndmConfig::from_hex("4D 5a 90 00").unwrap();
+
+    assert_eq!(config.pattern, b"MZ\x90\x00");
+    assert!(config.is_pure_literal());
+    assert_eq!(find_pattern(b"xxMZ\x90\x00", &config), Some(2));
+}
+
+#[test]
+fn from_hex_high_nibble_wildcard() {
+    let config = BndmConfig::from_hex("?5").unwrap();
+
+    assert_eq!(find_pattern(b"\x04\x15\xf5", &config), Some(1));
+    assert_eq!(rfind_pattern(b"\x04\x15\xf5", &config), Some(2));
+}
+
+#[test]
+fn from_hex_low_nibble_wildcard() {
+    let config = BndmConfig::from_hex("4? 8B").unwrap();
+
+    assert_eq!(find_pattern(b"\x3f\x8b\x4f\x8b", &config), Some(2));
+    assert_eq!(find_pattern(b"\x50\x8b", &config), None);
+}
+
+#[test]
+fn from_hex_byte_wildcard() {
+    let config = BndmConfig::from_hex("E8 ?? ?? ?? ??").unwrap();
+
+    assert_eq!(config.wildcard_positions(), vec![1, 2, 3, 4]);
+    assert_eq!(find_pattern(b"\x90\xe8\x01\x02\x03\x04", &config), Some(1));
+}
+
+#[test]
+fn from_hex_longer_than_word() {
+    let signature = ["00"; 70].join(" ") + " 1?";
+    let config = BndmConfig::from_hex(&signature).unwrap();
+    let mut source = vec![0; 71];
+    source[70] = 0x1c;
+
+    assert_eq!(find_pattern(&source, &config), Some(0));
+
+    source[70] = 0x2c;
+    assert_eq!(find_pattern(&source, &config), None);
+}
+
+#[test]
+fn from_hex_errors() {
+    assert_eq!(BndmConfig::from_hex("").err(), Some(HexPatternError::Empty));
+    assert_eq!(BndmConfig::from_hex("   ").err(), Some(HexPatternError::Empty));
+    assert_eq!(BndmConfig::from_hex("4D 5").err(), Some(HexPatternError::InvalidToken(1)));
+    assert_eq!(BndmConfig::from_hex("4D 5AB").err(), Some(HexPatternError::InvalidToken(1)));
+    assert_eq!(BndmConfig::from_hex("G0").err(), Some(HexPatternError::InvalidToken(0)));
+    assert_eq!(BndmConfig::from_hex("+1").err(), Some(HexPatternError::InvalidToken(0)));
+    assert_eq!(HexPatternError::InvalidToken(3).to_string(), "invalid token at index 3");
+}
+
+#[test]
+fn byte_class_masked() {
+    let class = ByteClass::masked(0x40, 0xf0);
+
+    assert_eq!(class.len(), 16);
+    assert!(class.contains(0x40));
+    assert!(class.contains(0x4f));
+    assert!(!class.contains(0x50));
+    assert_eq!(ByteClass::masked(0x12, 0xff), ByteClass::byte(0x12));
+    assert_eq!(ByteClass::masked(0x12, 0x00), ByteClass::any());
+}
+
+#[test]
+fn from_hex_range() {
+    let config = BndmConfig::from_hex("[30-39] [30-39] 25").unwrap();
+
+    assert_eq!(find_pattern(b"5% 9x% 42%", &config), Some(7));
+    assert_eq!(BndmConfig::from_hex("[41-41]").unwrap().pattern, b"A");
+}
+
+#[test]
+fn from_hex_invalid_range() {
+    assert_eq!(BndmConfig::from_hex("00 [39-30]").err(), Some(HexPatternError::InvalidToken(1)));
+    assert_eq!(BndmConfig::from_hex("[30-3]").err(), Some(HexPatternError::InvalidToken(0)));
+    assert_eq!(BndmConfig::from_hex("[30 39]").err(), Some(HexPatternError::InvalidToken(0)));
+    assert_eq!(BndmConfig::from_hex("[+3-39]").err(), Some(HexPatternError::InvalidToken(0)));
+    assert_eq!(BndmConfig::from_hex("[3A39]").err(), Some(HexPatternError::InvalidToken(0)));
+    assert_eq!(BndmConfig::from_hex("[3?-39]").err(), Some(HexPatternError::InvalidToken(0)));
+}
+
+#[test]
+fn from_hex_skip() {
+    let config = BndmConfig::from_hex("4D [2] 5A").unwrap();
+
+    assert_eq!(config.pattern.len(), 4);
+    assert_eq!(config.wildcard_positions(), vec![1, 2]);
+    assert_eq!(find_pattern(b"xM\x01\x02Z", &config), Some(1));
+    assert_eq!(find_pattern(b"xM\x01Z", &config), None);
+}
+
+#[test]
+fn from_hex_long_skip() {
+    let config = BndmConfig::from_hex("AA [70] BB").unwrap();
+
+    let mut source = vec![0u8; 100];
+    source[10] = 0xaa;
+    assert_eq!(find_pattern(&source, &config), None);
+    source[81] = 0xbb;
+    assert_eq!(find_pattern(&source, &config), Some(10));
+}
+
+#[test]
+fn from_hex_invalid_skip() {
+    assert_eq!(BndmConfig::from_hex("AA [+2] BB").err(), Some(HexPatternError::InvalidToken(1)));
+    assert_eq!(BndmConfig::from_hex("AA [] BB").err(), Some(HexPatternError::InvalidToken(1)));
+    assert_eq!(BndmConfig::from_hex("[0]").err(), Some(HexPatternError::Empty));
+    assert_eq!(BndmConfig::from_hex("E8 [18446744073709551615]").err(), Some(HexPatternError::InvalidToken(1)));
+    assert_eq!(BndmConfig::from_hex("E8 [4000000000]").err(), Some(HexPatternError::InvalidToken(1)));
+    assert_eq!(BndmConfig::from_hex("E8 [65537]").err(), Some(HexPatternError::InvalidToken(1)));
+    assert_eq!(BndmConfig::from_hex("E8 [65536]").unwrap().pattern.len(), 65537);
+}
+
+#[test]
+fn from_hex_ida_signature() {
+    let config = BndmConfig::from_hex("E8 ? ? ? ? 48 8B 05").unwrap();
+
+    assert_eq!(config.wildcard_positions(), vec![1, 2, 3, 4]);
+    assert_eq!(find_pattern(b"\x90\xe8\xaa\xbb\xcc\xdd\x48\x8b\x05", &config), Some(1));
+}
+
+#[test]
+fn from_hex_leading_and_trailing_wildcards() {
+    let config = BndmConfig::from_hex("? 48 8B ?").unwrap();
+
+    assert_eq!(find_pattern(b"\x48\x8b\x00", &config), None);
+    assert_eq!(find_pattern(b"\x00\x48\x8b", &config), None);
+    assert_eq!(find_pattern(b"\x00\x48\x8b\x00", &config), Some(0));
+    assert_eq!(rfind_pattern(b"\x00\x48\x8b\x00\x48\x8b\x00", &config), Some(3));
+}
+
+#[test]
+fn from_hex_only_wildcards() {
+    let config = BndmConfig::from_hex("? ??").unwrap();
+
+    assert_eq!(find_pattern(b"a", &config), None);
+    assert_eq!(find_pattern(b"ab", &config), Some(0));
+}
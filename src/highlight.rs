@@ -0,0 +1,93 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Conversion of matches into non-overlapping highlight ranges for rendering.
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+/// A range of the text to highlight, together with the tags of the matches that cover it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Highlight<T> {
+    /// The range of the text to highlight.
+    pub range: Range<usize>,
+
+    /// The tags of the matches covering the range, in ascending order and without duplicates.
+    pub tags: Vec<T>
+}
+
+/// Converts matches into non-overlapping highlight ranges within a viewport.
+///
+/// The matches are clipped to the viewport. Overlapping matches are split up at their
+/// boundaries, so that every highlight range is covered by the same set of tags, and adjacent
+/// ranges that are covered by the same set of tags are merged.
+///
+/// # Arguments
+///
+/// * `matches` - The ranges of the matches in the text, each with a tag, e.g. the identifier
+///   of the pattern that matched.
+/// * `viewport` - The range of the text that is rendered.
+///
+/// # Returns
+///
+/// * `Vec<Highlight<T>>` - Returns the highlight ranges in ascending order.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{Highlight, highlight_ranges};
+///
+/// let matches = vec![(0..5, 'a'), (3..8, 'b'), (8..10, 'b'), (20..30, 'a')];
+/// let highlights = highlight_ranges(matches, 2..25);
+/// assert_eq!(highlights, vec![
+///     Highlight { range: 2..3, tags: vec!['a'] },
+///     Highlight { range: 3..5, tags: vec!['a', 'b'] },
+///     Highlight { range: 5..10, tags: vec!['b'] },
+///     Highlight { range: 20..25, tags: vec!['a'] }
+/// ]);
+/// ```
+pub fn highlight_ranges<T: Clone + Ord>(matches: impl IntoIterator<Item = (Range<usize>, T)>, viewport: Range<usize>) -> Vec<Highlight<T>> {
+    let mut events: Vec<(usize, bool, T)> = matches.into_iter()
+        .map(|(range, tag)| (range.start.max(viewport.start)..range.end.min(viewport.end), tag))
+        .filter(|(range, _)| !range.is_empty())
+        .flat_map(|(range, tag)| [(range.start, true, tag.clone()), (range.end, false, tag)])
+        .collect();
+    events.sort_by_key(|&(position, _, _)| position);
+
+    let mut highlights: Vec<Highlight<T>> = Vec::new();
+    let mut active: BTreeMap<T, usize> = BTreeMap::new();
+    let mut events = events.into_iter().peekable();
+
+    while let Some((position, is_start, tag)) = events.next() {
+        update_active_tags(&mut active, is_start, tag);
+
+        let Some(&(next_position, _, _)) = events.peek() else {
+            break;
+        };
+        if next_position == position || active.is_empty() {
+            continue;
+        }
+
+        let tags: Vec<T> = active.keys().cloned().collect();
+        match highlights.last_mut() {
+            Some(last) if last.range.end == position && last.tags == tags => last.range.end = next_position,
+            _ => highlights.push(Highlight { range: position..next_position, tags })
+        }
+    }
+    highlights
+}
+
+fn update_active_tags<T: Ord>(active: &mut BTreeMap<T, usize>, is_start: bool, tag: T) {
+    if is_start {
+        *active.entry(tag).or_insert(0) += 1;
+    } else if let Some(count) = active.get_mut(&tag) {
+        *count -= 1;
+        if *count == 0 {
+            active.remove(&tag);
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "./highlight_test.rs"]
+mod highlight_test;
@@ -0,0 +1,75 @@
+use super::*;
+
+#[test]
+fn highlight_ranges_without_overlap() {
+    let matches = vec![(0..3, 1), (5..8, 2)];
+    let highlights = highlight_ranges(matches, 0..10);
+
+    assert_eq!(highlights, vec![
+        Highlight { range: 0..3, tags: vec![1] },
+        Highlight { range: 5..8, tags: vec![2] }
+    ]);
+}
+
+#[test]
+fn highlight_ranges_split_overlap() {
+    let matches = vec![(0..5, 1), (3..8, 2)];
+    let highlights = highlight_ranges(matches, 0..10);
+
+    assert_eq!(highlights, vec![
+        Highlight { range: 0..3, tags: vec![1] },
+        Highlight { range: 3..5, tags: vec![1, 2] },
+        Highlight { range: 5..8, tags: vec![2] }
+    ]);
+}
+
+#[test]
+fn highlight_ranges_merge_same_tag() {
+    let matches = vec![(0..5, 1), (3..8, 1), (8..10, 1)];
+    let highlights = highlight_ranges(matches, 0..20);
+
+    assert_eq!(highlights, vec![Highlight { range: 0..10, tags: vec![1] }]);
+}
+
+#[test]
+fn highlight_ranges_nested() {
+    let matches = vec![(0..10, 1), (2..4, 2)];
+    let highlights = highlight_ranges(matches, 0..10);
+
+    assert_eq!(highlights, vec![
+        Highlight { range: 0..2, tags: vec![1] },
+        Highlight { range: 2..4, tags: vec![1, 2] },
+        Highlight { range: 4..10, tags: vec![1] }
+    ]);
+}
+
+#[test]
+fn highlight_ranges_clip_to_viewport() {
+    let matches = vec![(0..5, 1), (8..12, 2), (15..20, 3)];
+    let highlights = highlight_ranges(matches, 3..10);
+
+    assert_eq!(highlights, vec![
+        Highlight { range: 3..5, tags: vec![1] },
+        Highlight { range: 8..10, tags: vec![2] }
+    ]);
+}
+
+#[test]
+fn highlight_ranges_unsorted_matches() {
+    let matches = vec![(6..9, "b"), (0..2, "a"), (1..7, "a")];
+    let highlights = highlight_ranges(matches, 0..10);
+
+    assert_eq!(highlights, vec![
+        Highlight { range: 0..6, tags: vec!["a"] },
+        Highlight { range: 6..7, tags: vec!["a", "b"] },
+        Highlight { range: 7..9, tags: vec!["b"] }
+    ]);
+}
+
+#[test]
+fn highlight_ranges_empty_matches() {
+    let matches = vec![(3..3, 1), (12..15, 2)];
+    let highlights = highlight_ranges(matches, 0..10);
+
+    assert_eq!(highlights, vec![]);
+}
@@ -0,0 +1,111 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Incremental search for patterns that grow or shrink by one byte at a time.
+
+use crate::{BndmConfig, find_overlapping_indexes};
+
+/// The `IncrementalSearch` struct keeps track of the matches of a pattern that is typed one byte
+/// at a time, e.g. in the find bar of an editor.
+///
+/// When a byte is appended to the pattern, only the matches of the previous pattern are
+/// verified instead of scanning the whole source again. When a byte is removed, the matches of
+/// the shorter pattern are restored without scanning at all.
+pub struct IncrementalSearch<'a> {
+    source: &'a [u8],
+    wildcard: Option<u8>,
+    pattern: Vec<u8>,
+    matches: Vec<Vec<usize>>
+}
+
+impl<'a> IncrementalSearch<'a> {
+    /// Creates a new `IncrementalSearch` instance with an empty pattern.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source string to search in.
+    /// * `wildcard` - An optional wildcard character. If provided, this character in the pattern
+    ///   can match any character in the text.
+    ///
+    /// # Returns
+    ///
+    /// * `IncrementalSearch` - A new `IncrementalSearch` instance.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::IncrementalSearch;
+    ///
+    /// let source = b"The quick brown fox jumps over the lazy dog";
+    /// let mut search = IncrementalSearch::new(source, Some(b'?'));
+    /// assert_eq!(search.push(b'o'), &[12, 17, 26, 41]);
+    /// assert_eq!(search.push(b'?'), &[12, 17, 26, 41]);
+    /// assert_eq!(search.push(b'e'), &[26]);
+    /// assert_eq!(search.pop(), &[12, 17, 26, 41]);
+    /// ```
+    pub fn new(source: &'a [u8], wildcard: Option<u8>) -> IncrementalSearch<'a> {
+        IncrementalSearch {
+            source,
+            wildcard,
+            pattern: Vec::new(),
+            matches: Vec::new()
+        }
+    }
+
+    /// Appends a byte to the pattern and returns the indexes of all occurrences of the new pattern.
+    ///
+    /// Occurrences may overlap. Only the occurrences of the previous pattern are verified, so
+    /// the source is only scanned completely for the first byte of the pattern.
+    pub fn push(&mut self, byte: u8) -> &[usize] {
+        let matches = match self.matches.last() {
+            Some(previous_matches) => {
+                let offset = self.pattern.len();
+                let is_wildcard = self.wildcard == Some(byte);
+
+                previous_matches.iter().copied()
+                    .filter(|&index| self.source.get(index + offset).is_some_and(|&source_byte| is_wildcard || source_byte == byte))
+                    .collect()
+            }
+            None => find_overlapping_indexes(self.source, &BndmConfig::new(&[byte], self.wildcard)).collect()
+        };
+
+        self.pattern.push(byte);
+        self.matches.push(matches);
+        self.matches()
+    }
+
+    /// Removes the last byte of the pattern and returns the indexes of all occurrences of the
+    /// shortened pattern, without scanning the source.
+    pub fn pop(&mut self) -> &[usize] {
+        self.pattern.pop();
+        self.matches.pop();
+        self.matches()
+    }
+
+    /// Clears the pattern.
+    pub fn clear(&mut self) {
+        self.pattern.clear();
+        self.matches.clear();
+    }
+
+    /// Returns the current pattern.
+    pub fn pattern(&self) -> &[u8] {
+        &self.pattern
+    }
+
+    /// Returns the indexes of all occurrences of the current pattern, or an empty slice if the
+    /// pattern is empty.
+    pub fn matches(&self) -> &[usize] {
+        self.matches.last().map_or(&[], |matches| matches)
+    }
+
+    /// Creates a `BndmConfig` for the current pattern, e.g. to continue searching in other
+    /// sources with the pattern that has been typed.
+    pub fn config(&self) -> BndmConfig {
+        BndmConfig::new(&self.pattern, self.wildcard)
+    }
+}
+
+#[cfg(test)]
+#[path = "./incremental_test.rs"]
+mod incremental_test;
@@ -0,0 +1,80 @@
+use super::*;
+
+#[test]
+fn push_narrows_matches() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let mut search = IncrementalSearch::new(source, None);
+
+    assert_eq!(search.push(b'o'), &[12, 17, 26, 41]);
+    assert_eq!(search.push(b'v'), &[26]);
+    assert_eq!(search.push(b'e'), &[26]);
+    assert_eq!(search.push(b'n'), &[]);
+    assert_eq!(search.pattern(), b"oven");
+}
+
+#[test]
+fn pop_restores_matches() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let mut search = IncrementalSearch::new(source, None);
+    search.push(b't');
+    search.push(b'h');
+    search.push(b'x');
+
+    assert_eq!(search.pop(), &[31]);
+    assert_eq!(search.pop(), &[31]);
+    assert_eq!(search.pop(), &[]);
+    assert_eq!(search.pop(), &[]);
+    assert_eq!(search.pattern(), b"");
+}
+
+#[test]
+fn push_wildcard() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let mut search = IncrementalSearch::new(source, Some(b'?'));
+
+    assert_eq!(search.push(b'?'), (0..source.len()).collect::<Vec<_>>());
+    assert_eq!(search.push(b'h'), &[0, 31]);
+    assert_eq!(search.push(b'?'), &[0, 31]);
+}
+
+#[test]
+fn push_overlapping_matches() {
+    let source = b"aaaa";
+    let mut search = IncrementalSearch::new(source, None);
+    search.push(b'a');
+    search.push(b'a');
+
+    assert_eq!(search.push(b'a'), &[0, 1]);
+}
+
+#[test]
+fn push_at_end_of_source() {
+    let source = b"abcab";
+    let mut search = IncrementalSearch::new(source, None);
+    search.push(b'a');
+    search.push(b'b');
+
+    assert_eq!(search.push(b'c'), &[0]);
+}
+
+#[test]
+fn clear_resets_pattern() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let mut search = IncrementalSearch::new(source, None);
+    search.push(b'q');
+    search.clear();
+
+    assert_eq!(search.matches(), &[]);
+    assert_eq!(search.push(b'z'), &[37]);
+}
+
+#[test]
+fn config_matches_current_pattern() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let mut search = IncrementalSearch::new(source, None);
+    search.push(b'f');
+    search.push(b'o');
+    let config = search.config();
+
+    assert_eq!(crate::find_pattern(b"a fox", &config), Some(2));
+}
@@ -0,0 +1,322 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! A q-gram index over a source string for answering many queries against the same source.
+
+use std::borrow::Cow;
+use std::io::{self, ErrorKind, Read, Write};
+
+use crate::{BndmConfig, find_overlapping_indexes, find_pattern};
+use crate::archive::cast_slice;
+
+const GRAM_SIZE: usize = 3;
+const FILE_MAGIC: &[u8; 8] = b"BNDMIDX\0";
+const FILE_VERSION: u32 = 1;
+const HEADER_SIZE: usize = 40;
+const ALIGNMENT: usize = 8;
+
+/// The `HaystackIndex` struct stores, for every sequence of 3 bytes in the source string, the
+/// indexes at which it occurs.
+///
+/// Queries look up the literal 3-byte grams of the pattern, intersect their candidate
+/// positions, and verify the remaining candidates with BNDM. Patterns without any literal
+/// gram, e.g. patterns shorter than 3 bytes or with a wildcard or an equivalence class of
+/// several bytes in every gram, fall back to scanning the source.
+pub struct HaystackIndex<'a> {
+    source: &'a [u8],
+    grams: Cow<'a, [u32]>,
+    offsets: Cow<'a, [u64]>,
+    positions: Cow<'a, [u64]>
+}
+
+impl<'a> HaystackIndex<'a> {
+    /// Builds the index for the source string.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source string to index.
+    ///
+    /// # Returns
+    ///
+    /// * `HaystackIndex` - A new `HaystackIndex` instance.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{BndmConfig, HaystackIndex};
+    ///
+    /// let source = b"The quick brown fox jumps over the lazy dog";
+    /// let index = HaystackIndex::build(source);
+    /// assert_eq!(index.query(&BndmConfig::new(b"o?er", Some(b'?'))), vec![26]);
+    /// assert_eq!(index.query(&BndmConfig::new(b"the", None)), vec![31]);
+    /// ```
+    pub fn build(source: &'a [u8]) -> HaystackIndex<'a> {
+        let mut entries: Vec<(u32, usize)> = source.windows(GRAM_SIZE).enumerate()
+            .map(|(position, gram)| (gram_key(gram), position))
+            .collect();
+        entries.sort_unstable();
+
+        let mut grams = Vec::new();
+        let mut offsets = Vec::new();
+        for (offset, &(gram, _)) in entries.iter().enumerate() {
+            if grams.last() != Some(&gram) {
+                grams.push(gram);
+                offsets.push(offset as u64);
+            }
+        }
+        offsets.push(entries.len() as u64);
+
+        HaystackIndex {
+            source,
+            grams: Cow::Owned(grams),
+            offsets: Cow::Owned(offsets),
+            positions: Cow::Owned(entries.into_iter().map(|(_, position)| position as u64).collect())
+        }
+    }
+
+    /// Returns the source string of the index.
+    pub fn source(&self) -> &'a [u8] {
+        self.source
+    }
+
+    /// Searches for all occurrences of the pattern in the indexed source string.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration for the BNDM search, which includes the pattern and the
+    ///   bitmasks.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<usize>` - Returns the indexes of all occurrences of the pattern in ascending order.
+    ///   Occurrences may overlap.
+    pub fn query(&self, config: &BndmConfig) -> Vec<usize> {
+        let pattern_len = config.pattern.len();
+        let mut gram_positions: Vec<(usize, &[u64])> = config.pattern.windows(GRAM_SIZE).enumerate()
+            .filter(|&(offset, _)| (offset..offset + GRAM_SIZE).all(|position| config.is_literal_at(position)))
+            .map(|(offset, gram)| (offset, self.gram_positions(gram_key(gram))))
+            .collect();
+        gram_positions.sort_by_key(|(_, positions)| positions.len());
+
+        let Some(&(first_offset, first_positions)) = gram_positions.first() else {
+            return find_overlapping_indexes(self.source, config).collect();
+        };
+
+        first_positions.iter()
+            .map(|&position| position as usize)
+            .filter(|&position| position >= first_offset)
+            .map(|position| position - first_offset)
+            .filter(|&index| index + pattern_len <= self.source.len())
+            .filter(|&index| gram_positions[1..].iter()
+                .all(|(offset, positions)| positions.binary_search(&((index + offset) as u64)).is_ok()))
+            .filter(|&index| find_pattern(&self.source[index..index + pattern_len], config) == Some(0))
+            .collect()
+    }
+
+    /// Writes the index to a writer, e.g. a file, so it can be loaded again with
+    /// `HaystackIndex::read_from` without rebuilding it.
+    ///
+    /// The source string itself is not written. The format consists of a header with a magic
+    /// number, a version, the gram size, the length of the source string and the number of
+    /// entries, followed by the gram table, the offset table and the position table as
+    /// little-endian integers. Every table starts at an offset that is a multiple of 8 bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The writer to write the index to.
+    ///
+    /// # Returns
+    ///
+    /// * `io::Result<()>` - Returns an error if writing fails.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{BndmConfig, HaystackIndex};
+    ///
+    /// let source = b"The quick brown fox jumps over the lazy dog";
+    /// let mut file = Vec::new();
+    /// HaystackIndex::build(source).write_to(&mut file).unwrap();
+    ///
+    /// let index = HaystackIndex::read_from(source, file.as_slice()).unwrap();
+    /// assert_eq!(index.query(&BndmConfig::new(b"lazy", None)), vec![35]);
+    /// ```
+    pub fn write_to(&self, mut writer: impl Write) -> io::Result<()> {
+        writer.write_all(FILE_MAGIC)?;
+        writer.write_all(&FILE_VERSION.to_le_bytes())?;
+        writer.write_all(&(GRAM_SIZE as u32).to_le_bytes())?;
+        writer.write_all(&(self.source.len() as u64).to_le_bytes())?;
+        writer.write_all(&(self.grams.len() as u64).to_le_bytes())?;
+        writer.write_all(&(self.positions.len() as u64).to_le_bytes())?;
+
+        for gram in self.grams.iter() {
+            writer.write_all(&gram.to_le_bytes())?;
+        }
+        if !self.grams.len().is_multiple_of(2) {
+            writer.write_all(&[0; 4])?;
+        }
+        for value in self.offsets.iter().chain(self.positions.iter()) {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+        writer.flush()
+    }
+
+    /// Reads an index that has been written with `HaystackIndex::write_to`.
+    ///
+    /// The tables are copied from the reader into memory, so reading an index takes time and
+    /// memory in proportion to its size, even when the reader is backed by a memory-mapped
+    /// file. Use `HaystackIndex::from_bytes` to use the tables in place instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source string the index has been built for.
+    /// * `reader` - The reader to read the index from.
+    ///
+    /// # Returns
+    ///
+    /// * `io::Result<HaystackIndex>` - Returns the index, or an error of kind `InvalidData` if
+    ///   the data is not a valid index or has been built for a source string of another length.
+    pub fn read_from(source: &'a [u8], mut reader: impl Read) -> io::Result<HaystackIndex<'a>> {
+        let (gram_count, position_count) = read_header(source, &mut reader)?;
+
+        let grams = (0..gram_count).map(|_| read_u32(&mut reader)).collect::<io::Result<Vec<_>>>()?;
+        if !gram_count.is_multiple_of(2) {
+            read_u32(&mut reader)?;
+        }
+        let offsets = (0..=gram_count).map(|_| read_u64(&mut reader)).collect::<io::Result<Vec<_>>>()?;
+        let positions = (0..position_count).map(|_| read_u64(&mut reader)).collect::<io::Result<Vec<_>>>()?;
+
+        validate_tables(&grams, &offsets, &positions)?;
+        Ok(HaystackIndex { source, grams: Cow::Owned(grams), offsets: Cow::Owned(offsets), positions: Cow::Owned(positions) })
+    }
+
+    /// Opens an index that has been written with `HaystackIndex::write_to` without copying its
+    /// tables, e.g. from a memory-mapped file.
+    ///
+    /// The tables are validated once when the index is opened. On big-endian targets the
+    /// tables cannot be used in place, and they are copied like with `HaystackIndex::read_from`.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source string the index has been built for.
+    /// * `bytes` - The bytes of the index. They should be aligned to 8 bytes, which holds for
+    ///   memory-mapped files.
+    ///
+    /// # Returns
+    ///
+    /// * `io::Result<HaystackIndex>` - Returns the index, or an error of kind `InvalidData` if
+    ///   the bytes are not aligned or not a valid index, or have been written for a source
+    ///   string of another length.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{BndmConfig, HaystackIndex};
+    ///
+    /// let source = b"The quick brown fox jumps over the lazy dog";
+    /// let mut file = Vec::new();
+    /// HaystackIndex::build(source).write_to(&mut file).unwrap();
+    ///
+    /// let mut buffer = vec![0; file.len() + 8];
+    /// let start = buffer.as_ptr().align_offset(8);
+    /// buffer[start..start + file.len()].copy_from_slice(&file);
+    ///
+    /// let index = HaystackIndex::from_bytes(source, &buffer[start..start + file.len()]).unwrap();
+    /// assert_eq!(index.query(&BndmConfig::new(b"lazy", None)), vec![35]);
+    /// ```
+    pub fn from_bytes(source: &'a [u8], bytes: &'a [u8]) -> io::Result<HaystackIndex<'a>> {
+        if cfg!(target_endian = "big") {
+            return HaystackIndex::read_from(source, bytes);
+        }
+        if bytes.as_ptr().align_offset(ALIGNMENT) != 0 {
+            return Err(invalid_data("index is not aligned to 8 bytes"));
+        }
+
+        let (gram_count, position_count) = read_header(source, &mut &bytes[..])?;
+        let offsets_start = HEADER_SIZE + gram_count.next_multiple_of(2) * 4;
+        let positions_start = offsets_start + (gram_count + 1) * 8;
+        let end = position_count.checked_mul(8).and_then(|size| size.checked_add(positions_start))
+            .ok_or_else(|| invalid_data("invalid number of index entries"))?;
+        if bytes.len() < end {
+            return Err(io::Error::from(ErrorKind::UnexpectedEof));
+        }
+
+        let grams: &[u32] = cast_slice(&bytes[HEADER_SIZE..HEADER_SIZE + gram_count * 4]);
+        let offsets: &[u64] = cast_slice(&bytes[offsets_start..positions_start]);
+        let positions: &[u64] = cast_slice(&bytes[positions_start..end]);
+
+        validate_tables(grams, offsets, positions)?;
+        Ok(HaystackIndex { source, grams: Cow::Borrowed(grams), offsets: Cow::Borrowed(offsets), positions: Cow::Borrowed(positions) })
+    }
+
+    fn gram_positions(&self, gram: u32) -> &[u64] {
+        self.grams.binary_search(&gram)
+            .map_or(&[], |index| &self.positions[self.offsets[index] as usize..self.offsets[index + 1] as usize])
+    }
+}
+
+// Reads the header of an index and returns the number of grams and positions.
+fn read_header(source: &[u8], reader: &mut impl Read) -> io::Result<(usize, usize)> {
+    let mut magic = [0; 8];
+    reader.read_exact(&mut magic)?;
+    if &magic != FILE_MAGIC || read_u32(reader)? != FILE_VERSION || read_u32(reader)? != GRAM_SIZE as u32 {
+        return Err(invalid_data("unsupported index format"));
+    }
+    if read_u64(reader)? != source.len() as u64 {
+        return Err(invalid_data("index does not match the length of the source"));
+    }
+
+    let gram_count = read_len(reader)?;
+    let position_count = read_len(reader)?;
+    if position_count != source.len().saturating_sub(GRAM_SIZE - 1) || gram_count > position_count {
+        return Err(invalid_data("invalid number of index entries"));
+    }
+    Ok((gram_count, position_count))
+}
+
+// Checks that the grams are ascending, that the offsets divide the positions over the grams,
+// and that the positions of every gram are ascending and within the source, which the binary
+// searches of the queries rely on.
+fn validate_tables(grams: &[u32], offsets: &[u64], positions: &[u64]) -> io::Result<()> {
+    let position_count = positions.len() as u64;
+    let is_valid = grams.windows(2).all(|pair| pair[0] < pair[1])
+        && offsets.first() == Some(&0) && offsets.last() == Some(&position_count)
+        && offsets.windows(2).all(|pair| pair[0] <= pair[1])
+        && offsets.windows(2).all(|pair| {
+            let positions = &positions[pair[0] as usize..pair[1] as usize];
+            positions.windows(2).all(|pair| pair[0] < pair[1])
+                && positions.last().is_none_or(|&position| position < position_count)
+        });
+    if !is_valid {
+        return Err(invalid_data("corrupt index"));
+    }
+    Ok(())
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(reader: &mut impl Read) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn read_len(reader: &mut impl Read) -> io::Result<usize> {
+    usize::try_from(read_u64(reader)?).map_err(|_| invalid_data("index entry exceeds the address space"))
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, message)
+}
+
+fn gram_key(gram: &[u8]) -> u32 {
+    gram.iter().fold(0, |key, &byte| (key << 8) | byte as u32)
+}
+
+#[cfg(test)]
+#[path = "./index_test.rs"]
+mod index_test;
@@ -0,0 +1,199 @@
+use super::*;
+
+const SOURCE: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua";
+
+fn find_naive(source: &[u8], config: &BndmConfig) -> Vec<usize> {
+    find_overlapping_indexes(source, config).collect()
+}
+
+#[test]
+fn query_literal_pattern() {
+    let index = HaystackIndex::build(SOURCE);
+    let config = BndmConfig::new(b"dolor", None);
+
+    assert_eq!(index.query(&config), vec![12, 103]);
+}
+
+#[test]
+fn query_wildcard_pattern() {
+    let index = HaystackIndex::build(SOURCE);
+    let config = BndmConfig::new(b"d?lor", Some(b'?'));
+
+    assert_eq!(index.query(&config), vec![12, 103]);
+}
+
+#[test]
+fn query_wildcard_in_every_gram() {
+    let index = HaystackIndex::build(SOURCE);
+    let config = BndmConfig::new(b"i??i??i", Some(b'?'));
+
+    assert_eq!(index.query(&config), find_naive(SOURCE, &config));
+}
+
+#[test]
+fn query_short_pattern() {
+    let index = HaystackIndex::build(SOURCE);
+    let config = BndmConfig::new(b"or", None);
+
+    assert_eq!(index.query(&config), vec![1, 15, 76, 96, 106]);
+}
+
+#[test]
+fn query_no_match() {
+    let index = HaystackIndex::build(SOURCE);
+    let config = BndmConfig::new(b"dolore magna aliquam", None);
+
+    assert_eq!(index.query(&config), vec![]);
+}
+
+#[test]
+fn query_long_pattern() {
+    let index = HaystackIndex::build(SOURCE);
+    let config = BndmConfig::new(b"consectetur adipiscing elit, sed do eiusmod tempor incididunt ut ", None);
+
+    assert_eq!(index.query(&config), vec![28]);
+}
+
+#[test]
+fn query_overlapping_matches() {
+    let source = b"aaaaaa";
+    let index = HaystackIndex::build(source);
+    let config = BndmConfig::new(b"aaaa", None);
+
+    assert_eq!(index.query(&config), vec![0, 1, 2]);
+}
+
+#[test]
+fn query_same_as_scan() {
+    let index = HaystackIndex::build(SOURCE);
+
+    for pattern in [&b"re"[..], b"it", b"e ?", b"?t, ", b"m ?o", b"t?r", b"a"] {
+        let config = BndmConfig::new(pattern, Some(b'?'));
+        assert_eq!(index.query(&config), find_naive(SOURCE, &config));
+    }
+}
+
+#[test]
+fn query_empty_source() {
+    let index = HaystackIndex::build(b"");
+    let config = BndmConfig::new(b"dolor", None);
+
+    assert_eq!(index.query(&config), vec![]);
+    assert_eq!(index.source(), b"");
+}
+
+#[test]
+fn write_and_read_index() {
+    let mut file = Vec::new();
+    HaystackIndex::build(SOURCE).write_to(&mut file).unwrap();
+    let index = HaystackIndex::read_from(SOURCE, file.as_slice()).unwrap();
+    let config = BndmConfig::new(b"d?lor", Some(b'?'));
+
+    assert_eq!(index.query(&config), vec![12, 103]);
+}
+
+#[test]
+fn write_index_aligned_tables() {
+    let source = b"abcd";
+    let mut file = Vec::new();
+    HaystackIndex::build(source).write_to(&mut file).unwrap();
+
+    assert_eq!(file.len(), 40 + 8 + 3 * 8 + 2 * 8);
+}
+
+#[test]
+fn read_index_other_source_length() {
+    let mut file = Vec::new();
+    HaystackIndex::build(SOURCE).write_to(&mut file).unwrap();
+    let result = HaystackIndex::read_from(&SOURCE[1..], file.as_slice());
+
+    assert_eq!(result.err().map(|error| error.kind()), Some(ErrorKind::InvalidData));
+}
+
+#[test]
+fn read_index_invalid_magic() {
+    let mut file = Vec::new();
+    HaystackIndex::build(SOURCE).write_to(&mut file).unwrap();
+    file[0] = b'X';
+    let result = HaystackIndex::read_from(SOURCE, file.as_slice());
+
+    assert_eq!(result.err().map(|error| error.kind()), Some(ErrorKind::InvalidData));
+}
+
+#[test]
+fn read_index_corrupt_position() {
+    let mut file = Vec::new();
+    HaystackIndex::build(SOURCE).write_to(&mut file).unwrap();
+    let last = file.len() - 8;
+    file[last..].copy_from_slice(&u64::MAX.to_le_bytes());
+    let result = HaystackIndex::read_from(SOURCE, file.as_slice());
+
+    assert_eq!(result.err().map(|error| error.kind()), Some(ErrorKind::InvalidData));
+}
+
+#[test]
+fn read_index_truncated() {
+    let mut file = Vec::new();
+    HaystackIndex::build(SOURCE).write_to(&mut file).unwrap();
+    let result = HaystackIndex::read_from(SOURCE, &file[..file.len() - 1]);
+
+    assert_eq!(result.err().map(|error| error.kind()), Some(ErrorKind::UnexpectedEof));
+}
+
+// Copies the bytes into a buffer that is aligned to 8 bytes, like a memory-mapped file, and
+// returns the buffer with the start of the copy.
+fn aligned(bytes: &[u8]) -> (Vec<u8>, usize) {
+    let mut buffer = vec![0; bytes.len() + 8];
+    let start = buffer.as_ptr().align_offset(8);
+    buffer[start..start + bytes.len()].copy_from_slice(bytes);
+    (buffer, start)
+}
+
+#[test]
+fn index_from_bytes() {
+    let mut file = Vec::new();
+    HaystackIndex::build(SOURCE).write_to(&mut file).unwrap();
+    let (buffer, start) = aligned(&file);
+    let index = HaystackIndex::from_bytes(SOURCE, &buffer[start..start + file.len()]).unwrap();
+    let config = BndmConfig::new(b"d?lor", Some(b'?'));
+
+    assert_eq!(index.query(&config), vec![12, 103]);
+}
+
+#[test]
+fn index_from_misaligned_bytes() {
+    let mut file = Vec::new();
+    HaystackIndex::build(SOURCE).write_to(&mut file).unwrap();
+    let (buffer, start) = aligned(&[&[0], file.as_slice()].concat());
+    let result = HaystackIndex::from_bytes(SOURCE, &buffer[start + 1..start + 1 + file.len()]);
+
+    assert_eq!(result.err().map(|error| error.kind()), Some(ErrorKind::InvalidData));
+}
+
+#[test]
+fn index_from_truncated_bytes() {
+    let mut file = Vec::new();
+    HaystackIndex::build(SOURCE).write_to(&mut file).unwrap();
+    let (buffer, start) = aligned(&file);
+    let result = HaystackIndex::from_bytes(SOURCE, &buffer[start..start + file.len() - 8]);
+
+    assert_eq!(result.err().map(|error| error.kind()), Some(ErrorKind::UnexpectedEof));
+}
+
+#[test]
+fn read_index_unsorted_positions() {
+    let source = b"abcabcabc";
+    let mut file = Vec::new();
+    HaystackIndex::build(source).write_to(&mut file).unwrap();
+    // The gram "abc" occurs at 0, 3 and 6, and its positions are the first in the position table.
+    let positions = file.len() - 7 * 8;
+    file[positions..positions + 8].copy_from_slice(&3u64.to_le_bytes());
+    file[positions + 8..positions + 16].copy_from_slice(&0u64.to_le_bytes());
+
+    let result = HaystackIndex::read_from(source, file.as_slice());
+    assert_eq!(result.err().map(|error| error.kind()), Some(ErrorKind::InvalidData));
+
+    let (buffer, start) = aligned(&file);
+    let result = HaystackIndex::from_bytes(source, &buffer[start..start + file.len()]);
+    assert_eq!(result.err().map(|error| error.kind()), Some(ErrorKind::InvalidData));
+}
@@ -0,0 +1,145 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! A lazy iterator over the occurrences of a pattern.
+
+use std::iter::FusedIterator;
+
+use crate::{BndmConfig, find_pattern_from};
+
+/// The `MatchSemantics` enum describes where searching continues after an occurrence.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MatchSemantics {
+    /// Searching continues at the end of the occurrence, so occurrences do not overlap, e.g.
+    /// for tokenizing.
+    #[default]
+    NonOverlapping,
+
+    /// Searching continues at the next index, so overlapping occurrences are found as well,
+    /// e.g. for signature scanning and overlap analysis.
+    Overlapping,
+
+    /// Searching continues the given number of bytes after the start of the occurrence, e.g.
+    /// for records of a fixed size. A stride of zero is treated as one.
+    Stride(usize)
+}
+
+impl MatchSemantics {
+    fn advance(self, pattern_len: usize) -> usize {
+        match self {
+            MatchSemantics::NonOverlapping => pattern_len,
+            MatchSemantics::Overlapping => 1,
+            MatchSemantics::Stride(stride) => stride
+        }.max(1)
+    }
+}
+
+/// The `Matches` struct is an iterator over the indexes of the occurrences of a pattern in a
+/// source string.
+///
+/// Where searching continues after an occurrence depends on the `MatchSemantics` of the
+/// iterator. Every occurrence is searched for when the next item is requested.
+#[derive(Clone)]
+pub struct Matches<'a> {
+    source: &'a [u8],
+    config: &'a BndmConfig,
+    position: usize,
+    semantics: MatchSemantics
+}
+
+impl<'a> Iterator for Matches<'a> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let index = find_pattern_from(self.source, self.config, self.position);
+        self.position = match index {
+            Some(index) => index + self.semantics.advance(self.config.pattern.len()),
+            None => self.source.len()
+        };
+        index
+    }
+}
+
+impl FusedIterator for Matches<'_> {}
+
+impl BndmConfig {
+    /// Returns an iterator over the indexes of all occurrences of the pattern in the source
+    /// string.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source string to search for the pattern.
+    ///
+    /// # Returns
+    ///
+    /// * `Matches` - An iterator that yields the indexes of the occurrences in ascending order.
+    ///   Occurrences do not overlap.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::BndmConfig;
+    ///
+    /// let source = b"The quick brown fox jumps over the lazy dog";
+    /// let config = BndmConfig::new(b"o", None);
+    /// let mut matches = config.find_iter(source);
+    /// assert_eq!(matches.next(), Some(12));
+    /// assert_eq!(matches.collect::<Vec<_>>(), vec![17, 26, 41]);
+    /// ```
+    pub fn find_iter<'a>(&'a self, source: &'a [u8]) -> Matches<'a> {
+        self.find_iter_with(source, MatchSemantics::NonOverlapping)
+    }
+
+    /// Returns an iterator over the indexes of all occurrences of the pattern in the source
+    /// string, including overlapping occurrences.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source string to search for the pattern.
+    ///
+    /// # Returns
+    ///
+    /// * `Matches` - An iterator that yields the indexes of the occurrences in ascending order.
+    ///   After an occurrence, searching continues at the next index.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::BndmConfig;
+    ///
+    /// let config = BndmConfig::new(b"abab", None);
+    /// assert_eq!(config.find_overlapping_iter(b"abababab").collect::<Vec<_>>(), vec![0, 2, 4]);
+    /// ```
+    pub fn find_overlapping_iter<'a>(&'a self, source: &'a [u8]) -> Matches<'a> {
+        self.find_iter_with(source, MatchSemantics::Overlapping)
+    }
+
+    /// Returns an iterator over the indexes of the occurrences of the pattern in the source
+    /// string with the given semantics.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source string to search for the pattern.
+    /// * `semantics` - Where searching continues after an occurrence.
+    ///
+    /// # Returns
+    ///
+    /// * `Matches` - An iterator that yields the indexes of the occurrences in ascending order.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{BndmConfig, MatchSemantics};
+    ///
+    /// let source = b"ID?1ID?2xxID?3ID?4";
+    /// let config = BndmConfig::new(b"ID", None);
+    /// assert_eq!(config.find_iter_with(source, MatchSemantics::Stride(4)).collect::<Vec<_>>(), vec![0, 4, 10, 14]);
+    /// ```
+    pub fn find_iter_with<'a>(&'a self, source: &'a [u8], semantics: MatchSemantics) -> Matches<'a> {
+        Matches { source, config: self, position: 0, semantics }
+    }
+}
+
+#[cfg(test)]
+#[path = "./iter_test.rs"]
+mod iter_test;
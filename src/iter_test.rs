@@ -0,0 +1,143 @@
+use super::*;
+
+#[test]
+fn find_iter_no_match() {
+    let config = BndmConfig::new(b"cat", None);
+
+    assert_eq!(config.find_iter(b"The quick brown fox").next(), None);
+}
+
+#[test]
+fn find_iter_empty_pattern() {
+    let config = BndmConfig::new(b"", None);
+
+    assert_eq!(config.find_iter(b"abc").count(), 0);
+}
+
+#[test]
+fn find_iter_empty_source() {
+    let config = BndmConfig::new(b"?", Some(b'?'));
+
+    assert_eq!(config.find_iter(b"").count(), 0);
+}
+
+#[test]
+fn find_iter_non_overlapping() {
+    let config = BndmConfig::new(b"aa", None);
+
+    assert_eq!(config.find_iter(b"aaaaa").collect::<Vec<_>>(), vec![0, 2]);
+}
+
+#[test]
+fn find_iter_wildcard() {
+    let config = BndmConfig::new(b"?he", Some(b'?'));
+
+    assert_eq!(config.find_iter(b"The quick brown fox jumps over the lazy dog").collect::<Vec<_>>(), vec![0, 31]);
+}
+
+#[test]
+fn find_iter_single_wildcard() {
+    let config = BndmConfig::new(b"?", Some(b'?'));
+
+    assert_eq!(config.find_iter(b"abc").collect::<Vec<_>>(), vec![0, 1, 2]);
+}
+
+#[test]
+fn find_iter_long_pattern() {
+    let pattern = [b'a'; 70];
+    let config = BndmConfig::new(&pattern, None);
+
+    assert_eq!(config.find_iter(&[b'a'; 150]).collect::<Vec<_>>(), vec![0, 70]);
+}
+
+#[test]
+fn find_iter_is_fused() {
+    let config = BndmConfig::new(b"b", None);
+    let mut matches = config.find_iter(b"ab");
+
+    assert_eq!(matches.next(), Some(1));
+    assert_eq!(matches.next(), None);
+    assert_eq!(matches.next(), None);
+}
+
+#[test]
+fn find_iter_matches_find_all_cb() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let config = BndmConfig::new(b"?o", Some(b'?'));
+    let mut indexes = Vec::new();
+    crate::find_all_cb(source, &config, |index| indexes.push(index));
+
+    assert_eq!(config.find_iter(source).collect::<Vec<_>>(), indexes);
+}
+
+#[test]
+fn find_overlapping_iter_periodic_pattern() {
+    let config = BndmConfig::new(b"abab", None);
+
+    assert_eq!(config.find_overlapping_iter(b"ababab").collect::<Vec<_>>(), vec![0, 2]);
+}
+
+#[test]
+fn find_overlapping_iter_repeated_byte() {
+    let config = BndmConfig::new(b"aa", None);
+
+    assert_eq!(config.find_overlapping_iter(b"aaaaa").collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+}
+
+#[test]
+fn find_overlapping_iter_wildcard() {
+    let config = BndmConfig::new(b"a?a", Some(b'?'));
+
+    assert_eq!(config.find_overlapping_iter(b"abacada").collect::<Vec<_>>(), vec![0, 2, 4]);
+}
+
+#[test]
+fn find_overlapping_iter_long_pattern() {
+    let pattern = [b'a'; 70];
+    let config = BndmConfig::new(&pattern, None);
+
+    assert_eq!(config.find_overlapping_iter(&[b'a'; 73]).collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+}
+
+#[test]
+fn find_overlapping_iter_empty_pattern() {
+    let config = BndmConfig::new(b"", None);
+
+    assert_eq!(config.find_overlapping_iter(b"abc").count(), 0);
+}
+
+#[test]
+fn find_iter_with_default_semantics() {
+    let config = BndmConfig::new(b"aa", None);
+
+    assert_eq!(config.find_iter_with(b"aaaaa", MatchSemantics::default()).collect::<Vec<_>>(), vec![0, 2]);
+}
+
+#[test]
+fn find_iter_with_overlapping() {
+    let config = BndmConfig::new(b"aa", None);
+
+    assert_eq!(config.find_iter_with(b"aaaaa", MatchSemantics::Overlapping).collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+}
+
+#[test]
+fn find_iter_with_stride() {
+    let config = BndmConfig::new(b"a", None);
+
+    assert_eq!(config.find_iter_with(b"aaaaaaa", MatchSemantics::Stride(3)).collect::<Vec<_>>(), vec![0, 3, 6]);
+    assert_eq!(config.find_iter_with(b"aaab", MatchSemantics::Stride(0)).collect::<Vec<_>>(), vec![0, 1, 2]);
+}
+
+#[test]
+fn find_iter_with_stride_shorter_than_pattern() {
+    let config = BndmConfig::new(b"aaa", None);
+
+    assert_eq!(config.find_iter_with(b"aaaaaa", MatchSemantics::Stride(2)).collect::<Vec<_>>(), vec![0, 2]);
+}
+
+#[test]
+fn find_iter_with_stride_skips_matches() {
+    let config = BndmConfig::new(b"?b", Some(b'?'));
+
+    assert_eq!(config.find_iter_with(b"abxbabab", MatchSemantics::Stride(5)).collect::<Vec<_>>(), vec![0, 6]);
+}
@@ -57,22 +57,173 @@
 //! assert_eq!(index, Some(20));
 //! ```
 
+mod advisor;
+mod archive;
+mod arena;
+mod bits;
+mod borrowed;
+mod builder;
+mod c64;
+mod cache;
+mod capture;
+mod carve;
+mod clamav;
+mod class;
+mod condition;
+#[cfg(feature = "coredump")]
+mod coredump;
+mod diagnostics;
+mod entropy;
+mod equivalence;
+mod error;
+mod export;
+mod ext;
+mod fixed;
+mod gap;
+mod hex;
+mod highlight;
+mod incremental;
+mod index;
+mod iter;
+#[cfg(feature = "magic")]
+mod magic;
+mod masks;
+mod matched;
+pub mod memmem;
+mod multi;
+mod policy;
+mod pool;
+mod prefilter;
+mod proximity;
+mod replace;
+mod searcher;
+#[cfg(feature = "serde")]
+mod serialize;
+mod sid;
+mod split;
+mod syntax;
+mod task;
+mod text;
+mod throughput;
+mod variants;
+mod wide;
+mod xor;
+mod yara;
+mod zip;
+
+pub use advisor::{HaystackProfile, Recommendation, Strategy, Warning, advise};
+pub use archive::{ArchiveBuffer, ArchiveError, ArchivedConfig, ConfigArchive, write_archive};
+pub use arena::{ConfigArena, ConfigHandle};
+pub use bits::{BitOrder, BitPattern};
+pub use borrowed::BndmConfigRef;
+pub use builder::BndmConfigBuilder;
+pub use c64::{
+    C64Memory, OpcodeSignatureOptions, Relocation, TEXT_ENCODINGS, TextEncoding, TextMatch, encode_c64_text, find_c64_text,
+    find_relocated, opcode_signature
+};
+pub use cache::ConfigCache;
+pub use capture::CapturePattern;
+pub use carve::{CARVE_PRESETS, CarvePreset, GZIP, JPEG, PDF, PNG, ZIP, carve};
+pub use clamav::{ClamAvError, ClamAvSignature};
+pub use class::{ByteClass, PatternBuilder};
+pub use condition::{Comparison, Condition, ConditionError};
+#[cfg(feature = "coredump")]
+pub use coredump::{CoreDump, CoreDumpError, MemoryRegion, Permissions, RegionMatch};
+pub use diagnostics::Diagnostic;
+pub use entropy::{EntropyFilter, block_entropy};
+pub use equivalence::Equivalence;
+pub use error::BndmError;
+pub use export::ImportError;
+pub use ext::FindBndm;
+pub use fixed::FixedConfig;
+pub use gap::{GapPattern, GapPatternError};
+pub use hex::HexPatternError;
+pub use highlight::{Highlight, highlight_ranges};
+pub use incremental::IncrementalSearch;
+pub use index::HaystackIndex;
+pub use iter::{MatchSemantics, Matches};
+#[cfg(feature = "macros")]
+pub use bndm_macros::pattern;
+#[cfg(feature = "macros")]
+extern crate self as bndm;
+#[cfg(feature = "magic")]
+pub use magic::{EmbeddedFile, MAGIC_NUMBERS, Magic, MagicSet, find_embedded, identify};
+pub use masks::{MaskTable, MaskTableError};
+pub use matched::{Match, find_all_matches, find_match};
+pub use multi::{MultiBndmConfig, MultiMatch};
+pub use policy::WildcardPolicy;
+pub use pool::{ScanJob, ScanPart, ScanPool};
+pub use prefilter::ChunkFilter;
+pub use proximity::{SequenceMatch, find_near, find_near_ordered, find_sequence};
+pub use replace::{replace_all, replace_with};
+pub use searcher::{DynSearcher, StreamState};
+pub use sid::{SidHeader, SidMatch, SidType, find_sid_headers};
+pub use split::{Split, SplitN, split, splitn};
+pub use syntax::PatternSyntaxError;
+pub use task::ScanTask;
+pub use text::{find_pattern_char_index, find_pattern_str};
+pub use throughput::ThroughputEstimate;
+pub use variants::{Variant, VariantMatch, VariantSet};
+pub use wide::{Alphabet, AlphabetConfig, WideConfig, WideElement, find_pattern_wide};
+pub use xor::{XorMatch, XorPattern, find_xor_keys};
+pub use yara::{YaraHexError, YaraHexString};
+pub use zip::{ZipEntry, recover_zip_entries};
+
 use std::cmp::min;
+use std::fmt;
+use std::ops::{ControlFlow, Range};
+use std::sync::Arc;
+
+#[cfg(feature = "log")]
+macro_rules! log_debug {
+    ($($arg:tt)*) => { log::debug!($($arg)*) }
+}
+
+#[cfg(not(feature = "log"))]
+macro_rules! log_debug {
+    ($($arg:tt)*) => {}
+}
 
 const MASKS_TABLE_SIZE: usize = 256;
 const WORD_SIZE_IN_BITS: usize = usize::BITS as usize;
 
 /// The `BndmConfig` struct is used to store the pattern and the bitmasks.
+#[derive(Clone, PartialEq, Eq)]
 pub struct BndmConfig {
-    /// An array of bitmasks, one for each possible byte value.
-    pub masks: [usize; MASKS_TABLE_SIZE],
+    /// An array of bitmasks, one for each possible byte value. The array is allocated on
+    /// the heap and shared by the clones of the config, so that configs are cheap to move,
+    /// to store in collections and to hand out to worker threads.
+    pub masks: Arc<[usize; MASKS_TABLE_SIZE]>,
 
     /// An optional wildcard character. If provided, this character in the pattern
     /// can match any character in the text.
     pub wildcard: Option<u8>,
 
     /// The pattern to search for in the text.
-    pub pattern: Vec<u8>
+    pub pattern: Vec<u8>,
+
+    /// An optional equivalence of bytes. If provided, a byte in the pattern matches any
+    /// byte in the text that is in the same equivalence class.
+    pub equivalence: Option<Equivalence>,
+
+    /// An optional class of bytes for every position of the pattern. If provided, a position
+    /// matches any byte of its class, and the wildcard and the equivalence are not used.
+    pub classes: Option<Vec<ByteClass>>
+}
+
+/// Formats the pattern and the settings of the configuration. The bitmasks, the equivalence
+/// and the classes of bytes are summarized instead of listed entry by entry.
+impl fmt::Debug for BndmConfig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BndmConfig")
+            .field("pattern", &format_args!("b\"{}\"", self.pattern.escape_ascii()))
+            .field("wildcard", &self.wildcard)
+            .field("filter_len", &self.filter_len())
+            .field("masks", &format_args!("[{} of {} non-zero]", self.masks.iter().filter(|&&mask| mask != 0).count(), MASKS_TABLE_SIZE))
+            .field("equivalence", &self.equivalence.is_some())
+            .field("classes", &self.classes.is_some())
+            .finish()
+    }
 }
 
 impl BndmConfig {
@@ -82,7 +233,7 @@ impl BndmConfig {
     ///
     /// * `search_pattern` - The pattern to search for in the text.
     /// * `wildcard` - An optional wildcard character. If provided, this character in the pattern
-    ///                can match any character in the text.
+    ///   can match any character in the text.
     ///
     /// # Returns
     ///
@@ -112,12 +263,310 @@ impl BndmConfig {
     pub fn new(search_pattern: &[u8], wildcard: Option<u8>) -> BndmConfig {
         let len = get_pattern_length_within_cpu_word(search_pattern.len());
 
-        BndmConfig {
-            masks: generate_masks(&search_pattern[..len], wildcard),
+        let config = BndmConfig {
+            masks: Arc::new(generate_masks(&search_pattern[..len], wildcard)),
+            wildcard,
+            pattern: search_pattern.to_owned(),
+            equivalence: None,
+            classes: None
+        };
+        log_compiled(&config, "");
+        config
+    }
+
+    /// Creates a new `BndmConfig` instance in which the ASCII letters of the pattern match
+    /// both cases of the letter in the text.
+    ///
+    /// # Arguments
+    ///
+    /// * `search_pattern` - The pattern to search for in the text.
+    /// * `wildcard` - An optional wildcard character. If provided, this character in the pattern
+    ///   can match any character in the text.
+    ///
+    /// # Returns
+    ///
+    /// * `BndmConfig` - A new `BndmConfig` instance.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{BndmConfig, find_pattern};
+    ///
+    /// let config = BndmConfig::new_nocase(b"error", None);
+    /// assert_eq!(find_pattern(b"[12:00:01] ERROR: disk full", &config), Some(11));
+    /// ```
+    pub fn new_nocase(search_pattern: &[u8], wildcard: Option<u8>) -> BndmConfig {
+        BndmConfig::with_equivalence(search_pattern, wildcard, &Equivalence::ascii_case_insensitive())
+    }
+
+    /// Creates a new `BndmConfig` instance in which every byte of the pattern matches any
+    /// byte of its equivalence class.
+    ///
+    /// # Arguments
+    ///
+    /// * `search_pattern` - The pattern to search for in the text.
+    /// * `wildcard` - An optional wildcard character. If provided, this character in the pattern
+    ///   can match any character in the text.
+    /// * `equivalence` - The equivalence classes of the bytes.
+    ///
+    /// # Returns
+    ///
+    /// * `BndmConfig` - A new `BndmConfig` instance.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{BndmConfig, Equivalence, find_pattern};
+    ///
+    /// let equivalence = Equivalence::identity().with_class(b"-_ ");
+    /// let config = BndmConfig::with_equivalence(b"user_name", None, &equivalence);
+    /// assert_eq!(find_pattern(b"first user-name", &config), Some(6));
+    /// ```
+    pub fn with_equivalence(search_pattern: &[u8], wildcard: Option<u8>, equivalence: &Equivalence) -> BndmConfig {
+        let len = get_pattern_length_within_cpu_word(search_pattern.len());
+
+        let config = BndmConfig {
+            masks: Arc::new(generate_equivalence_masks(&search_pattern[..len], wildcard, equivalence)),
+            wildcard,
+            pattern: search_pattern.to_owned(),
+            equivalence: Some(equivalence.clone()),
+            classes: None
+        };
+        log_compiled(&config, " with equivalence");
+        config
+    }
+
+    /// Creates a new `BndmConfig` instance from a precomputed mask table, e.g. a table with
+    /// custom classes of bytes per position or a table generated by external tooling.
+    ///
+    /// The mask table is used to scan the text; the pattern is used for the bytes beyond the
+    /// positions covered by the table. Every byte of the pattern that is not the wildcard must
+    /// match its position in the table, so the table can only widen the set of bytes that
+    /// match a position.
+    ///
+    /// # Arguments
+    ///
+    /// * `search_pattern` - The pattern to search for in the text.
+    /// * `wildcard` - An optional wildcard character. If provided, this character in the pattern
+    ///   can match any character in the text.
+    /// * `mask_table` - The mask table for the first positions of the pattern.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<BndmConfig, MaskTableError>` - Returns the configuration, or an error if the
+    ///   mask table does not cover the pattern or is inconsistent with it.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{BndmConfig, MaskTable, find_pattern};
+    ///
+    /// // Match any decimal digit at the second position.
+    /// let mut masks = *MaskTable::new(b"v0.", None).unwrap().masks();
+    /// (b'1'..=b'9').for_each(|digit| masks[digit as usize] |= 0b010);
+    ///
+    /// let mask_table = MaskTable::from_masks(masks, 3).unwrap();
+    /// let config = BndmConfig::from_mask_table(b"v0.", None, &mask_table).unwrap();
+    /// assert_eq!(find_pattern(b"version v7.2", &config), Some(8));
+    /// ```
+    pub fn from_mask_table(search_pattern: &[u8], wildcard: Option<u8>, mask_table: &MaskTable) -> Result<BndmConfig, MaskTableError> {
+        let len = get_pattern_length_within_cpu_word(search_pattern.len());
+        if mask_table.len() != len {
+            return Err(MaskTableError::LengthMismatch(mask_table.len(), len));
+        }
+        if let Some(position) = search_pattern[..len].iter().enumerate()
+            .position(|(position, &byte)| wildcard != Some(byte) && !mask_table.matches(byte, position)) {
+            return Err(MaskTableError::InconsistentPattern(position));
+        }
+
+        let config = BndmConfig {
+            masks: Arc::new(*mask_table.masks()),
             wildcard,
-            pattern: search_pattern.to_owned()
+            pattern: search_pattern.to_owned(),
+            equivalence: None,
+            classes: None
+        };
+        log_compiled(&config, " from mask table");
+        Ok(config)
+    }
+
+    /// Returns the number of bytes at the start of the pattern that are scanned with the
+    /// bitmasks. The remaining bytes of the pattern are verified per candidate.
+    pub fn filter_len(&self) -> usize {
+        get_pattern_length_within_cpu_word(self.pattern.len())
+    }
+
+    /// Returns whether the pattern has a position that matches any byte, i.e. whether
+    /// `wildcard_positions` is not empty.
+    pub fn uses_wildcard(&self) -> bool {
+        !self.wildcard_positions().is_empty()
+    }
+
+    /// Returns the positions of the wildcard characters in the pattern, in ascending order.
+    /// If the pattern has a class of bytes for every position, the positions whose class
+    /// contains every byte are returned.
+    pub fn wildcard_positions(&self) -> Vec<usize> {
+        match &self.classes {
+            Some(classes) => classes.iter().enumerate()
+                .filter(|(_, class)| class.len() == MASKS_TABLE_SIZE)
+                .map(|(position, _)| position)
+                .collect(),
+            None => self.pattern.iter().enumerate()
+                .filter(|&(_, &byte)| self.wildcard == Some(byte))
+                .map(|(position, _)| position)
+                .collect()
+        }
+    }
+
+    /// Returns whether every position of the pattern only matches the byte of the pattern,
+    /// i.e. the pattern has no wildcards, equivalence classes or custom classes of bytes.
+    pub fn is_pure_literal(&self) -> bool {
+        (0..self.pattern.len()).all(|position| self.is_literal_at(position))
+    }
+
+    /// Estimates how selective the pattern is, as the fraction of byte values that do not
+    /// match any position of the scanned part of the pattern.
+    ///
+    /// A byte that matches no position lets BNDM shift the window past it, so a value close
+    /// to 1.0 indicates a fast scan, and a value of 0.0 indicates that every byte can be part
+    /// of an occurrence, e.g. because the pattern contains a wildcard.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::BndmConfig;
+    ///
+    /// let config = BndmConfig::new(b"jumps", None);
+    /// assert_eq!(config.selectivity_estimate(), 251.0 / 256.0);
+    /// assert_eq!(BndmConfig::new(b"ju??s", Some(b'?')).selectivity_estimate(), 0.0);
+    /// ```
+    pub fn selectivity_estimate(&self) -> f64 {
+        if self.pattern.is_empty() {
+            return 0.0;
+        }
+        self.masks.iter().filter(|&&mask| mask == 0).count() as f64 / MASKS_TABLE_SIZE as f64
+    }
+
+    /// Returns the index of the first occurrence of the pattern in the source, or `None` if
+    /// the pattern is not found. This is the same as `find_pattern`.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::BndmConfig;
+    ///
+    /// let config = BndmConfig::new(b"ju??s", Some(b'?'));
+    /// assert_eq!(config.find(b"The quick brown fox jumps over the lazy dog"), Some(20));
+    /// assert!(config.is_match(b"jumps"));
+    /// assert_eq!(config.count(b"jumps, jumbs and jokes"), 2);
+    /// ```
+    pub fn find(&self, source: &[u8]) -> Option<usize> {
+        find_pattern(source, self)
+    }
+
+    /// Returns the index of the last occurrence of the pattern in the source, or `None` if
+    /// the pattern is not found. This is the same as `rfind_pattern`.
+    pub fn rfind(&self, source: &[u8]) -> Option<usize> {
+        rfind_pattern(source, self)
+    }
+
+    /// Returns the index of the first occurrence of the pattern that starts at or after the
+    /// given index. This is the same as `find_pattern_from`.
+    pub fn find_from(&self, source: &[u8], start_index: usize) -> Option<usize> {
+        find_pattern_from(source, self, start_index)
+    }
+
+    /// Returns whether the pattern occurs in the source. This is the same as `contains`.
+    pub fn is_match(&self, source: &[u8]) -> bool {
+        contains(source, self)
+    }
+
+    /// Returns the indexes of all non-overlapping occurrences of the pattern in the source.
+    /// This is the same as `find_all`.
+    pub fn find_all(&self, source: &[u8]) -> Vec<usize> {
+        find_all(source, self)
+    }
+
+    /// Returns the number of non-overlapping occurrences of the pattern in the source. This
+    /// is the same as `count_matches`.
+    pub fn count(&self, source: &[u8]) -> usize {
+        count_matches(source, self)
+    }
+
+    pub(crate) fn view(&self) -> PatternView<'_> {
+        PatternView {
+            masks: &self.masks,
+            wildcard: self.wildcard,
+            pattern: &self.pattern,
+            equivalence: self.equivalence.as_ref(),
+            classes: self.classes.as_deref()
+        }
+    }
+
+    /// Returns whether the given position of the pattern only matches the byte of the pattern,
+    /// i.e. it is not a wildcard and no other byte is equivalent to it.
+    pub(crate) fn is_literal_at(&self, position: usize) -> bool {
+        let len = get_pattern_length_within_cpu_word(self.pattern.len());
+        if position < len {
+            let bit = 1 << (len - 1 - position);
+            return self.masks.iter().filter(|&&mask| mask & bit != 0).count() == 1;
+        }
+
+        if let Some(classes) = &self.classes {
+            return classes[position].len() == 1;
+        }
+
+        let pattern_byte = self.pattern[position];
+        self.wildcard != Some(pattern_byte) &&
+            self.equivalence.as_ref().is_none_or(|equivalence| equivalence.class_size(pattern_byte) == 1)
+    }
+}
+
+/// A borrowed view of the preprocessed pattern, so patterns that are not stored in a
+/// `BndmConfig` can be scanned as well.
+#[derive(Clone, Copy)]
+pub(crate) struct PatternView<'a> {
+    pub(crate) masks: &'a [usize; MASKS_TABLE_SIZE],
+    pub(crate) wildcard: Option<u8>,
+    pub(crate) pattern: &'a [u8],
+    pub(crate) equivalence: Option<&'a Equivalence>,
+    pub(crate) classes: Option<&'a [ByteClass]>
+}
+
+impl PatternView<'_> {
+    /// Returns whether the byte of the pattern matches the byte of the text.
+    fn matches_byte(&self, pattern_byte: u8, byte: u8) -> bool {
+        self.wildcard == Some(pattern_byte) || match self.equivalence {
+            Some(equivalence) => equivalence.is_equivalent(pattern_byte, byte),
+            None => pattern_byte == byte
+        }
+    }
+
+    /// Returns whether the byte of the text matches the given position of the pattern, without
+    /// using the bitmasks.
+    fn matches_position(&self, position: usize, byte: u8) -> bool {
+        match self.classes {
+            Some(classes) => classes[position].contains(byte),
+            None => self.matches_byte(self.pattern[position], byte)
+        }
+    }
+
+    /// Returns whether the byte of the text matches the pattern at the given position, using the
+    /// bitmasks for the positions they cover.
+    fn allows(&self, position: usize, byte: u8) -> bool {
+        let filter_len = get_pattern_length_within_cpu_word(self.pattern.len());
+        if position < filter_len {
+            self.masks[byte as usize] & (1 << (filter_len - 1 - position)) != 0
+        } else {
+            self.matches_position(position, byte)
         }
     }
+
+    /// Returns whether the pattern matches the window, which must have the length of the
+    /// pattern.
+    fn matches_window(&self, window: &[u8]) -> bool {
+        !self.pattern.is_empty() && window.iter().enumerate().all(|(position, &byte)| self.allows(position, byte))
+    }
 }
 
 /// Searches for the pattern in the source string using the BNDM algorithm.
@@ -131,12 +580,12 @@ impl BndmConfig {
 ///
 /// * `source` - The source string to search for the pattern.
 /// * `config` - The configuration for the BNDM search, which includes the pattern and the
-///              bitmasks.
+///   bitmasks.
 ///
 /// # Returns
 ///
 /// * `Option<usize>` - Returns the index of the first occurrence of the pattern in the text,
-///                     or `None` if the pattern is not found.
+///   or `None` if the pattern is not found.
 ///
 /// # Usage
 ///
@@ -164,85 +613,812 @@ impl BndmConfig {
 /// assert_eq!(index, Some(20));
 /// ```
 pub fn find_pattern(source: &[u8], config: &BndmConfig) -> Option<usize> {
-    match config.pattern.len() {
-        0 => None,
-        1 => config.wildcard
-            .map_or(false, |w| w == config.pattern[0]).then_some(0)
-            .or_else(|| source.iter().position(|&s| s == config.pattern[0])),
-        _ => find_pattern_bndm(source, config)
-    }
+    find_first(source, config).inspect(|&index| log_match(config, index))
 }
 
-fn find_pattern_bndm(source: &[u8], config: &BndmConfig) -> Option<usize> {
-    if config.pattern.len() > source.len() {
-        return None;
-    }
-
-    let len = get_pattern_length_within_cpu_word(config.pattern.len()) - 1;
-    let end = source.len() - config.pattern.len();
-    let df = 1 << len;
-    let mut i = 0;
-
-    while i <= end {
-        let mut j = len;
-        let mut last = len;
+/// Checks whether the pattern occurs in the source string.
+///
+/// The search stops at the first occurrence, so the cost is the same as for `find_pattern`.
+///
+/// # Arguments
+///
+/// * `source` - The source string to search for the pattern.
+/// * `config` - The configuration for the BNDM search, which includes the pattern and the
+///   bitmasks.
+///
+/// # Returns
+///
+/// * `bool` - Returns `true` if the pattern occurs in the source string, `false` otherwise.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{BndmConfig, contains};
+///
+/// let source = b"The quick brown fox jumps over the lazy dog";
+/// assert!(contains(source, &BndmConfig::new(b"l?zy", Some(b'?'))));
+/// assert!(!contains(source, &BndmConfig::new(b"cat", None)));
+/// ```
+pub fn contains(source: &[u8], config: &BndmConfig) -> bool {
+    find_pattern(source, config).is_some()
+}
 
-        let mut d = get_mask(source, config, i + j);
-        d = (d << 1) & get_mask(source, config, i + j - 1);
-        while d != 0 {
-            j -= 1;
-            if d & df != 0 {
-                if j == 0 {
-                    if find_remaining(source, config, i + WORD_SIZE_IN_BITS) {
-                        return Some(i);
-                    }
-                    j += 1;
-                }
-                last = j;
-            }
-            d = (d << 1) & get_mask(source, config, i + j - 1);
-        }
+/// Checks whether the pattern occurs at the given index of the source string.
+///
+/// Only the bytes at the index are compared, so the source string is not scanned. This is
+/// useful for validating candidate indexes that have been found in another way.
+///
+/// # Arguments
+///
+/// * `source` - The source string to check.
+/// * `config` - The configuration for the BNDM search, which includes the pattern and the
+///   bitmasks.
+/// * `index` - The index in the source string at which the pattern should start.
+///
+/// # Returns
+///
+/// * `bool` - Returns `true` if the pattern occurs at the index, `false` otherwise or if the
+///   pattern does not fit in the source string at the index.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{BndmConfig, matches_at};
+///
+/// let source = b"The quick brown fox jumps over the lazy dog";
+/// let config = BndmConfig::new(b"f?x", Some(b'?'));
+/// assert!(matches_at(source, &config, 16));
+/// assert!(!matches_at(source, &config, 17));
+/// ```
+pub fn matches_at(source: &[u8], config: &BndmConfig, index: usize) -> bool {
+    index.checked_add(config.pattern.len())
+        .and_then(|end| source.get(index..end))
+        .is_some_and(|window| config.view().matches_window(window))
+}
 
-        i += last;
-    }
-    None
+/// Checks whether the source string starts with the pattern.
+///
+/// # Arguments
+///
+/// * `source` - The source string to check.
+/// * `config` - The configuration for the BNDM search, which includes the pattern and the
+///   bitmasks.
+///
+/// # Returns
+///
+/// * `bool` - Returns `true` if the pattern occurs at the start of the source string, `false`
+///   otherwise.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{BndmConfig, starts_with};
+///
+/// let config = BndmConfig::new(b"\x89PNG\r\n?\n", Some(b'?'));
+/// assert!(starts_with(b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR", &config));
+/// ```
+pub fn starts_with(source: &[u8], config: &BndmConfig) -> bool {
+    matches_at(source, config, 0)
 }
 
-fn get_mask(source: &[u8], config: &BndmConfig, index: usize) -> usize {
-    unsafe {
-        *config.masks.get_unchecked(*source.get_unchecked(index) as usize)
-    }
+/// Checks whether the source string ends with the pattern.
+///
+/// # Arguments
+///
+/// * `source` - The source string to check.
+/// * `config` - The configuration for the BNDM search, which includes the pattern and the
+///   bitmasks.
+///
+/// # Returns
+///
+/// * `bool` - Returns `true` if the pattern occurs at the end of the source string, `false`
+///   otherwise.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{BndmConfig, ends_with};
+///
+/// let config = BndmConfig::new(b"IEND????", Some(b'?'));
+/// assert!(ends_with(b"\x00\x00\x00\x00IEND\xae\x42\x60\x82", &config));
+/// ```
+pub fn ends_with(source: &[u8], config: &BndmConfig) -> bool {
+    source.len().checked_sub(config.pattern.len()).is_some_and(|index| matches_at(source, config, index))
 }
 
-/// Checks if the remaining part of the pattern matches the source string.
+/// Searches for the last occurrence of the pattern in the source string.
 ///
-/// This function is used when the pattern is longer than the CPU word size.
-/// It checks the remaining part of the pattern (after the first CPU word size characters)
-/// against the corresponding part of the source string.
+/// The source string is scanned backwards from its end with the BNDM algorithm, so the search
+/// stops as soon as the last occurrence is found. Building the bitmasks of the reversed
+/// pattern takes a fixed amount of time per call, which is negligible for large sources.
 ///
 /// # Arguments
 ///
 /// * `source` - The source string to search for the pattern.
 /// * `config` - The configuration for the BNDM search, which includes the pattern and the
-///              wildcard character.
-/// * `start_index` - The index in the source string from where the remaining part of the
-///                   pattern should be checked.
+///   bitmasks.
 ///
 /// # Returns
 ///
-/// * `bool` - Returns `true` if the remaining part of the pattern matches the corresponding part of the source string, `false` otherwise.
-fn find_remaining(source: &[u8], config: &BndmConfig, start_index: usize) -> bool {
-    config.pattern.iter().skip(WORD_SIZE_IN_BITS).enumerate().all(|(index, &pattern_byte)| unsafe {
-        *source.get_unchecked(start_index + index) == pattern_byte || config.wildcard.map_or(false, |w| pattern_byte == w)
-    })
+/// * `Option<usize>` - Returns the index of the last occurrence of the pattern in the text,
+///   or `None` if the pattern is not found.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{BndmConfig, rfind_pattern};
+///
+/// let source = b"The quick brown fox jumps over the lazy dog";
+/// let config = BndmConfig::new(b"?o", Some(b'?'));
+/// let index = rfind_pattern(source, &config);
+/// assert_eq!(index, Some(40));
+/// ```
+pub fn rfind_pattern(source: &[u8], config: &BndmConfig) -> Option<usize> {
+    rfind_last_in_view(source, &config.view()).inspect(|&index| log_match(config, index))
 }
 
-fn get_pattern_length_within_cpu_word(search_pattern_length: usize) -> usize {
-    min(search_pattern_length, WORD_SIZE_IN_BITS)
+/// Searches for the first occurrence of the pattern that starts at or after the given index.
+///
+/// This makes it possible to resume searching after a previous occurrence without slicing the
+/// source and adjusting the returned index manually.
+///
+/// # Arguments
+///
+/// * `source` - The source string to search for the pattern.
+/// * `config` - The configuration for the BNDM search, which includes the pattern and the
+///   bitmasks.
+/// * `start_index` - The index in the source string from where to start searching.
+///
+/// # Returns
+///
+/// * `Option<usize>` - Returns the index in the source string of the first occurrence of the
+///   pattern at or after the start index, or `None` if the pattern is not found or the start
+///   index is at or beyond the end of the source.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{BndmConfig, find_pattern_from};
+///
+/// let source = b"The quick brown fox jumps over the lazy dog";
+/// let config = BndmConfig::new(b"o", None);
+/// let index = find_pattern_from(source, &config, 13);
+/// assert_eq!(index, Some(17));
+/// ```
+pub fn find_pattern_from(source: &[u8], config: &BndmConfig, start_index: usize) -> Option<usize> {
+    find_unlogged_from(source, config, start_index).inspect(|&index| log_match(config, index))
 }
 
-fn calculate_wildcard_mask(search_pattern: &[u8], wildcard: Option<u8>) -> usize {
-    wildcard.map_or(0, |wildcard| search_pattern.iter()
+/// Searches for the pattern in the given range of the source string.
+///
+/// Only matches that lie fully within the range are reported, so a section of the source can
+/// be scanned without slicing it and adjusting the returned index. A range that exceeds the
+/// source is clamped to the source length.
+///
+/// # Arguments
+///
+/// * `source` - The source string to search for the pattern.
+/// * `config` - The configuration for the BNDM search, which includes the pattern and the
+///   bitmasks.
+/// * `range` - The range of the source string to search in.
+///
+/// # Returns
+///
+/// * `Option<usize>` - Returns the index in the source string of the first occurrence of the
+///   pattern within the range, or `None` if the pattern is not found.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{BndmConfig, find_pattern_in_range};
+///
+/// let source = b"The quick brown fox jumps over the lazy dog";
+/// let config = BndmConfig::new(b"o", None);
+/// let index = find_pattern_in_range(source, &config, 13..30);
+/// assert_eq!(index, Some(17));
+/// ```
+pub fn find_pattern_in_range(source: &[u8], config: &BndmConfig, range: Range<usize>) -> Option<usize> {
+    find_pattern_in_ranges(source, config, &[range])
+}
+
+/// Searches for the pattern in the given ranges of the source string.
+///
+/// Only matches that lie fully within a range are reported. This makes it possible to skip
+/// irrelevant regions of the source, such as unallocated clusters or non-executable sections,
+/// without slicing the source and adjusting the returned index for every range manually. The
+/// ranges may be given in any order and may overlap. Ranges that exceed the source are
+/// clamped to the source length.
+///
+/// # Arguments
+///
+/// * `source` - The source string to search for the pattern.
+/// * `config` - The configuration for the BNDM search, which includes the pattern and the
+///   bitmasks.
+/// * `ranges` - The ranges of the source string to search in.
+///
+/// # Returns
+///
+/// * `Option<usize>` - Returns the lowest index in the source string of an occurrence of the
+///   pattern within the ranges, or `None` if the pattern is not found.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{BndmConfig, find_pattern_in_ranges};
+///
+/// let source = b"The quick brown fox jumps over the lazy dog";
+/// let config = BndmConfig::new(b"o", None);
+/// let index = find_pattern_in_ranges(source, &config, &[20..30, 0..10]);
+/// assert_eq!(index, Some(26));
+/// ```
+pub fn find_pattern_in_ranges(source: &[u8], config: &BndmConfig, ranges: &[Range<usize>]) -> Option<usize> {
+    // The first match of the range with the lowest start is never preceded by a match of a
+    // later range, as such a match would lie fully within the earlier range as well.
+    sorted_ranges(source, ranges).into_iter()
+        .find_map(|range| find_first(&source[range.clone()], config).map(|index| range.start + index))
+        .inspect(|&index| log_match(config, index))
+}
+
+/// Searches for all occurrences of the pattern in the given ranges of the source string.
+///
+/// Only matches that lie fully within a range are reported, as with `find_pattern_in_ranges`.
+/// The ranges may be given in any order and may overlap; occurrences in overlapping ranges are
+/// reported once. Occurrences do not overlap, as with `find_all`.
+///
+/// # Arguments
+///
+/// * `source` - The source string to search for the pattern.
+/// * `config` - The configuration for the BNDM search, which includes the pattern and the
+///   bitmasks.
+/// * `ranges` - The ranges of the source string to search in.
+///
+/// # Returns
+///
+/// * `Vec<usize>` - Returns the indexes in the source string of all occurrences of the pattern
+///   within the ranges, in ascending order.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{BndmConfig, find_all_in_ranges};
+///
+/// let source = b"The quick brown fox jumps over the lazy dog";
+/// let config = BndmConfig::new(b"o", None);
+/// let indexes = find_all_in_ranges(source, &config, &[20..30, 0..20, 10..15]);
+/// assert_eq!(indexes, vec![12, 17, 26]);
+/// ```
+pub fn find_all_in_ranges(source: &[u8], config: &BndmConfig, ranges: &[Range<usize>]) -> Vec<usize> {
+    let mut indexes = Vec::new();
+    let mut next_index = 0;
+    for range in sorted_ranges(source, ranges) {
+        let mut start_index = range.start.max(next_index);
+        while let Some(index) = source.get(start_index..range.end).and_then(|range| find_first(range, config)) {
+            let index = start_index + index;
+            log_match(config, index);
+            indexes.push(index);
+            start_index = index + config.pattern.len();
+            next_index = start_index;
+        }
+    }
+    indexes
+}
+
+/// Searches for all occurrences of the pattern in the source string and calls the callback
+/// with the index of every occurrence.
+///
+/// Occurrences do not overlap; after an occurrence is found, searching continues at the end of
+/// the occurrence. The search does not allocate any memory, which makes it suitable for
+/// real-time threads and memory-constrained environments.
+///
+/// # Arguments
+///
+/// * `source` - The source string to search for the pattern.
+/// * `config` - The configuration for the BNDM search, which includes the pattern and the
+///   bitmasks.
+/// * `callback` - The callback that is called with the index of every occurrence, in
+///   ascending order.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{BndmConfig, find_all_cb};
+///
+/// let source = b"The quick brown fox jumps over the lazy dog";
+/// let config = BndmConfig::new(b"o", None);
+/// let mut count = 0;
+/// find_all_cb(source, &config, |_| count += 1);
+/// assert_eq!(count, 4);
+/// ```
+pub fn find_all_cb(source: &[u8], config: &BndmConfig, callback: impl FnMut(usize)) {
+    find_indexes(source, config).for_each(callback);
+}
+
+/// Searches for the occurrences of the pattern in the source string and calls the visitor with
+/// the index of every occurrence until the visitor breaks.
+///
+/// Occurrences do not overlap, and the search does not allocate any memory, as with
+/// `find_all_cb`. Returning `ControlFlow::Break` from the visitor stops the search, so the
+/// rest of the source string is not scanned.
+///
+/// # Arguments
+///
+/// * `source` - The source string to search for the pattern.
+/// * `config` - The configuration for the BNDM search, which includes the pattern and the
+///   bitmasks.
+/// * `visitor` - The visitor that is called with the index of every occurrence, in ascending
+///   order, and returns whether to continue.
+///
+/// # Returns
+///
+/// * `ControlFlow<()>` - Returns `ControlFlow::Break` if the visitor stopped the search,
+///   `ControlFlow::Continue` otherwise.
+///
+/// # Usage
+///
+/// ```rust
+/// use std::ops::ControlFlow;
+/// use bndm::{BndmConfig, for_each_match};
+///
+/// let source = b"The quick brown fox jumps over the lazy dog";
+/// let config = BndmConfig::new(b"o", None);
+/// let mut indexes = Vec::new();
+/// let result = for_each_match(source, &config, |index| {
+///     indexes.push(index);
+///     if index > 15 { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+/// });
+/// assert_eq!(result, ControlFlow::Break(()));
+/// assert_eq!(indexes, vec![12, 17]);
+/// ```
+pub fn for_each_match(source: &[u8], config: &BndmConfig, visitor: impl FnMut(usize) -> ControlFlow<()>) -> ControlFlow<()> {
+    find_indexes(source, config).try_for_each(visitor)
+}
+
+/// Searches for all occurrences of the pattern in the source string.
+///
+/// Occurrences do not overlap; after an occurrence is found, searching continues at the end of
+/// the occurrence.
+///
+/// # Arguments
+///
+/// * `source` - The source string to search for the pattern.
+/// * `config` - The configuration for the BNDM search, which includes the pattern and the
+///   bitmasks.
+///
+/// # Returns
+///
+/// * `Vec<usize>` - Returns the indexes of all occurrences of the pattern in ascending order.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{BndmConfig, find_all};
+///
+/// let source = b"The quick brown fox jumps over the lazy dog";
+/// let config = BndmConfig::new(b"?o", Some(b'?'));
+/// assert_eq!(find_all(source, &config), vec![11, 16, 25, 40]);
+/// ```
+pub fn find_all(source: &[u8], config: &BndmConfig) -> Vec<usize> {
+    find_indexes(source, config).collect()
+}
+
+/// Searches for at most the given number of occurrences of the pattern in the source string.
+///
+/// The search stops as soon as the maximum number of occurrences has been found, so the rest
+/// of the source string is not scanned. Occurrences do not overlap, as with `find_all`.
+///
+/// # Arguments
+///
+/// * `source` - The source string to search for the pattern.
+/// * `config` - The configuration for the BNDM search, which includes the pattern and the
+///   bitmasks.
+/// * `max` - The maximum number of occurrences to search for.
+///
+/// # Returns
+///
+/// * `Vec<usize>` - Returns the indexes of the first occurrences of the pattern in ascending
+///   order, at most `max` of them.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{BndmConfig, find_up_to};
+///
+/// let source = b"The quick brown fox jumps over the lazy dog";
+/// let config = BndmConfig::new(b"o", None);
+/// assert_eq!(find_up_to(source, &config, 3), vec![12, 17, 26]);
+/// ```
+pub fn find_up_to(source: &[u8], config: &BndmConfig, max: usize) -> Vec<usize> {
+    find_indexes(source, config).take(max).collect()
+}
+
+/// Searches for all occurrences of the pattern in the source string and appends their indexes
+/// to the given vector.
+///
+/// Reusing the vector for many searches, after clearing it, avoids allocating a new vector for
+/// every search. Occurrences do not overlap, as with `find_all`.
+///
+/// # Arguments
+///
+/// * `source` - The source string to search for the pattern.
+/// * `config` - The configuration for the BNDM search, which includes the pattern and the
+///   bitmasks.
+/// * `indexes` - The vector to append the indexes of the occurrences to, in ascending order.
+///
+/// # Returns
+///
+/// * `usize` - Returns the number of indexes that have been appended.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{BndmConfig, find_all_into};
+///
+/// let config = BndmConfig::new(b"o", None);
+/// let mut indexes = Vec::with_capacity(16);
+/// for source in [&b"The quick brown fox"[..], b"jumps over the lazy dog"] {
+///     indexes.clear();
+///     find_all_into(source, &config, &mut indexes);
+///     assert_eq!(indexes.len(), 2);
+/// }
+/// ```
+pub fn find_all_into(source: &[u8], config: &BndmConfig, indexes: &mut Vec<usize>) -> usize {
+    let len = indexes.len();
+    indexes.extend(find_indexes(source, config));
+    indexes.len() - len
+}
+
+/// Searches for the occurrences of the pattern in the source string and stores their indexes
+/// in the given slice, until the slice is full.
+///
+/// The search does not allocate any memory and stops as soon as the slice is full.
+/// Occurrences do not overlap, as with `find_all`.
+///
+/// # Arguments
+///
+/// * `source` - The source string to search for the pattern.
+/// * `config` - The configuration for the BNDM search, which includes the pattern and the
+///   bitmasks.
+/// * `indexes` - The slice to store the indexes of the occurrences in, in ascending order.
+///
+/// # Returns
+///
+/// * `usize` - Returns the number of indexes that have been stored at the start of the slice.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{BndmConfig, find_all_into_slice};
+///
+/// let source = b"The quick brown fox jumps over the lazy dog";
+/// let config = BndmConfig::new(b"o", None);
+/// let mut indexes = [0; 3];
+/// assert_eq!(find_all_into_slice(source, &config, &mut indexes), 3);
+/// assert_eq!(indexes, [12, 17, 26]);
+/// ```
+pub fn find_all_into_slice(source: &[u8], config: &BndmConfig, indexes: &mut [usize]) -> usize {
+    indexes.iter_mut().zip(find_indexes(source, config))
+        .map(|(slot, index)| *slot = index)
+        .count()
+}
+
+/// Counts the occurrences of the pattern in the source string without storing their indexes.
+///
+/// Occurrences do not overlap, so the count equals the number of indexes returned by
+/// `find_all`.
+///
+/// # Arguments
+///
+/// * `source` - The source string to search for the pattern.
+/// * `config` - The configuration for the BNDM search, which includes the pattern and the
+///   bitmasks.
+///
+/// # Returns
+///
+/// * `usize` - Returns the number of occurrences of the pattern.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{BndmConfig, count_matches};
+///
+/// let source = b"The quick brown fox jumps over the lazy dog";
+/// let config = BndmConfig::new(b"o", None);
+/// assert_eq!(count_matches(source, &config), 4);
+/// ```
+pub fn count_matches(source: &[u8], config: &BndmConfig) -> usize {
+    find_indexes(source, config).count()
+}
+
+/// Searches for the n-th occurrence of the pattern in the source string without storing the
+/// indexes of the preceding occurrences.
+///
+/// Occurrences are counted from zero, like `Iterator::nth`, and do not overlap, so the result
+/// equals the n-th index returned by `find_all`.
+///
+/// # Arguments
+///
+/// * `source` - The source string to search for the pattern.
+/// * `config` - The configuration for the BNDM search, which includes the pattern and the
+///   bitmasks.
+/// * `n` - The number of occurrences to skip.
+///
+/// # Returns
+///
+/// * `Option<usize>` - Returns the index of the n-th occurrence of the pattern, or `None` if
+///   there are not more than `n` occurrences.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{BndmConfig, find_nth};
+///
+/// let source = b"The quick brown fox jumps over the lazy dog";
+/// let config = BndmConfig::new(b"o", None);
+/// assert_eq!(find_nth(source, &config, 0), Some(12));
+/// assert_eq!(find_nth(source, &config, 2), Some(26));
+/// assert_eq!(find_nth(source, &config, 4), None);
+/// ```
+pub fn find_nth(source: &[u8], config: &BndmConfig, n: usize) -> Option<usize> {
+    find_indexes(source, config).nth(n)
+}
+
+/// Searches for the first occurrence of the pattern that is accepted by the verifier.
+///
+/// Every candidate occurrence found by BNDM is passed to the verifier together with the whole
+/// source string, so the verifier can inspect the context of the candidate, e.g. to validate a
+/// checksum or the structure of a header. Searching continues after a rejected candidate at the
+/// next index, so overlapping candidates are verified as well.
+///
+/// # Arguments
+///
+/// * `source` - The source string to search for the pattern.
+/// * `config` - The configuration for the BNDM search, which includes the pattern and the
+///   bitmasks.
+/// * `verify` - The verifier that is called with the source string and the index of a
+///   candidate, and returns whether the candidate is accepted.
+///
+/// # Returns
+///
+/// * `Option<usize>` - Returns the index of the first accepted occurrence of the pattern, or
+///   `None` if no candidate is accepted.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{BndmConfig, find_pattern_verified};
+///
+/// let source = b"PK\x03\x04\x00\x00 PK\x03\x04\x14\x00";
+/// let config = BndmConfig::new(b"PK\x03\x04", None);
+/// let index = find_pattern_verified(source, &config, |source, index| source.get(index + 4) == Some(&0x14));
+/// assert_eq!(index, Some(7));
+/// ```
+pub fn find_pattern_verified(source: &[u8], config: &BndmConfig, mut verify: impl FnMut(&[u8], usize) -> bool) -> Option<usize> {
+    find_verified_from(source, config, 0, &mut verify).inspect(|&index| log_match(config, index))
+}
+
+/// Searches for all occurrences of the pattern that are accepted by the verifier.
+///
+/// Accepted occurrences do not overlap; after an accepted occurrence, searching continues at
+/// the end of the occurrence, and after a rejected candidate at the next index.
+///
+/// # Arguments
+///
+/// * `source` - The source string to search for the pattern.
+/// * `config` - The configuration for the BNDM search, which includes the pattern and the
+///   bitmasks.
+/// * `verify` - The verifier that is called with the source string and the index of a
+///   candidate, and returns whether the candidate is accepted.
+///
+/// # Returns
+///
+/// * `Vec<usize>` - Returns the indexes of all accepted occurrences in ascending order.
+pub fn find_all_verified(source: &[u8], config: &BndmConfig, mut verify: impl FnMut(&[u8], usize) -> bool) -> Vec<usize> {
+    let mut indexes = Vec::new();
+    let mut start_index = 0;
+
+    while let Some(index) = find_verified_from(source, config, start_index, &mut verify) {
+        log_match(config, index);
+        indexes.push(index);
+        start_index = index + config.pattern.len();
+    }
+    indexes
+}
+
+fn find_first(source: &[u8], config: &BndmConfig) -> Option<usize> {
+    find_first_in_view(source, &config.view())
+}
+
+pub(crate) fn find_first_in_view(source: &[u8], config: &PatternView) -> Option<usize> {
+    match config.pattern.len() {
+        0 => None,
+        1 => config.wildcard
+            .is_some_and(|w| w == config.pattern[0]).then_some(0)
+            .or_else(|| source.iter().position(|&s| config.masks[s as usize] != 0)),
+        _ => find_pattern_bndm(source, config)
+    }
+}
+
+pub(crate) fn rfind_last_in_view(source: &[u8], config: &PatternView) -> Option<usize> {
+    match config.pattern.len() {
+        0 => None,
+        1 => source.iter().rposition(|&s| config.masks[s as usize] != 0),
+        _ => rfind_pattern_bndm(source, config)
+    }
+}
+
+fn sorted_ranges(source: &[u8], ranges: &[Range<usize>]) -> Vec<Range<usize>> {
+    let mut ranges: Vec<_> = ranges.iter()
+        .map(|range| range.start..min(range.end, source.len()))
+        .filter(|range| range.start < range.end)
+        .collect();
+    ranges.sort_unstable_by_key(|range| range.start);
+    ranges
+}
+
+#[cfg_attr(not(feature = "log"), allow(unused_variables))]
+pub(crate) fn log_match(config: &BndmConfig, index: usize) {
+    log_debug!("pattern \"{}\" matched at offset {}", config.pattern.escape_ascii(), index);
+}
+
+#[cfg_attr(not(feature = "log"), allow(unused_variables))]
+pub(crate) fn log_compiled(config: &BndmConfig, details: &str) {
+    log_debug!("compiled pattern \"{}\" ({} bytes, wildcard {:?}){}", config.pattern.escape_ascii(), config.pattern.len(),
+        config.wildcard, details);
+}
+
+fn find_verified_from(source: &[u8], config: &BndmConfig, mut start_index: usize, verify: &mut impl FnMut(&[u8], usize) -> bool) -> Option<usize> {
+    while start_index < source.len() {
+        let index = start_index + find_first(&source[start_index..], config)?;
+        if verify(source, index) {
+            return Some(index);
+        }
+        start_index = index + 1;
+    }
+    None
+}
+
+pub(crate) fn find_indexes<'a>(source: &'a [u8], config: &'a BndmConfig) -> impl Iterator<Item = usize> + 'a {
+    std::iter::successors(find_pattern_from(source, config, 0),
+        move |&index| find_pattern_from(source, config, index + config.pattern.len()))
+}
+
+pub(crate) fn find_overlapping_indexes<'a>(source: &'a [u8], config: &'a BndmConfig) -> impl Iterator<Item = usize> + 'a {
+    std::iter::successors(find_pattern_from(source, config, 0),
+        move |&index| find_pattern_from(source, config, index + 1))
+}
+
+// Finds overlapping occurrences without logging them, for callers that rebase or filter the
+// indexes before logging the matches they report.
+pub(crate) fn find_overlapping_indexes_unlogged<'a>(source: &'a [u8], config: &'a BndmConfig) -> impl Iterator<Item = usize> + 'a {
+    std::iter::successors(find_unlogged_from(source, config, 0),
+        move |&index| find_unlogged_from(source, config, index + 1))
+}
+
+fn find_unlogged_from(source: &[u8], config: &BndmConfig, start_index: usize) -> Option<usize> {
+    (start_index < source.len())
+        .then(|| find_first(&source[start_index..], config).map(|index| start_index + index))
+        .flatten()
+}
+
+fn find_pattern_bndm(source: &[u8], config: &PatternView) -> Option<usize> {
+    if config.pattern.len() > source.len() {
+        return None;
+    }
+
+    scan_bndm(source.len(), config.pattern.len(),
+        |index| get_mask(source, config.masks, index),
+        |index| find_remaining(source, config, index + WORD_SIZE_IN_BITS))
+}
+
+fn rfind_pattern_bndm(source: &[u8], config: &PatternView) -> Option<usize> {
+    if config.pattern.len() > source.len() {
+        return None;
+    }
+
+    let masks = generate_reverse_masks(config);
+    let last = source.len() - 1;
+    let remaining = config.pattern.len().saturating_sub(WORD_SIZE_IN_BITS);
+
+    scan_bndm(source.len(), config.pattern.len(),
+        |index| get_mask(source, &masks, last - index),
+        |index| {
+            let start = source.len() - index - config.pattern.len();
+            source[start..start + remaining].iter().enumerate()
+                .all(|(position, &byte)| config.allows(position, byte))
+        })
+        .map(|index| source.len() - index - config.pattern.len())
+}
+
+/// Scans a text for the first window that matches the bitmasks using the BNDM algorithm.
+///
+/// # Arguments
+///
+/// * `source_len` - The length of the text.
+/// * `pattern_len` - The length of the pattern, which must be at least 2 and at most the length
+///   of the text.
+/// * `mask_at` - Returns the bitmask of the byte of the text at the given index.
+/// * `is_match` - Verifies the part of the pattern that is not covered by the bitmasks for the
+///   window that starts at the given index.
+///
+/// # Returns
+///
+/// * `Option<usize>` - Returns the index of the first matching window, or `None` if no window
+///   matches.
+#[inline(always)]
+fn scan_bndm(source_len: usize, pattern_len: usize, mask_at: impl Fn(usize) -> usize, is_match: impl FnMut(usize) -> bool) -> Option<usize> {
+    scan_bndm_with_shifts(source_len, pattern_len, mask_at, is_match, |_| {})
+}
+
+// Scans like `scan_bndm` and calls `on_shift` with every shift of the window, for measuring
+// how the scan advances. The callback is inlined, so `scan_bndm` pays nothing for it.
+pub(crate) fn scan_bndm_with_shifts(source_len: usize, pattern_len: usize, mask_at: impl Fn(usize) -> usize,
+                                    mut is_match: impl FnMut(usize) -> bool, mut on_shift: impl FnMut(usize)) -> Option<usize> {
+    let len = get_pattern_length_within_cpu_word(pattern_len) - 1;
+    let end = source_len - pattern_len;
+    let df = 1 << len;
+    let mut i = 0;
+
+    while i <= end {
+        let mut j = len;
+        let mut last = len;
+
+        let mut d = mask_at(i + j);
+        d = (d << 1) & mask_at(i + j - 1);
+        while d != 0 {
+            j -= 1;
+            if d & df != 0 {
+                if j == 0 {
+                    if is_match(i) {
+                        return Some(i);
+                    }
+                    j += 1;
+                }
+                last = j;
+            }
+            d = (d << 1) & mask_at(i + j - 1);
+        }
+
+        on_shift(last);
+        i += last;
+    }
+    None
+}
+
+fn get_mask(source: &[u8], masks: &[usize; MASKS_TABLE_SIZE], index: usize) -> usize {
+    unsafe {
+        *masks.get_unchecked(*source.get_unchecked(index) as usize)
+    }
+}
+
+/// Checks if the remaining part of the pattern matches the source string.
+///
+/// This function is used when the pattern is longer than the CPU word size.
+/// It checks the remaining part of the pattern (after the first CPU word size characters)
+/// against the corresponding part of the source string.
+///
+/// # Arguments
+///
+/// * `source` - The source string to search for the pattern.
+/// * `config` - The configuration for the BNDM search, which includes the pattern and the
+///   wildcard character.
+/// * `start_index` - The index in the source string from where the remaining part of the
+///   pattern should be checked.
+///
+/// # Returns
+///
+/// * `bool` - Returns `true` if the remaining part of the pattern matches the corresponding part of the source string, `false` otherwise.
+fn find_remaining(source: &[u8], config: &PatternView, start_index: usize) -> bool {
+    (WORD_SIZE_IN_BITS..config.pattern.len()).all(|position| unsafe {
+        config.matches_position(position, *source.get_unchecked(start_index + position - WORD_SIZE_IN_BITS))
+    })
+}
+
+fn get_pattern_length_within_cpu_word(search_pattern_length: usize) -> usize {
+    min(search_pattern_length, WORD_SIZE_IN_BITS)
+}
+
+fn calculate_wildcard_mask(search_pattern: &[u8], wildcard: Option<u8>) -> usize {
+    wildcard.map_or(0, |wildcard| search_pattern.iter()
         .fold(0, |mask, &pattern_byte| (mask << 1) | (pattern_byte == wildcard) as usize))
 }
 
@@ -256,6 +1432,38 @@ fn generate_masks(search_pattern: &[u8], wildcard: Option<u8>) -> [usize; MASKS_
     masks
 }
 
+/// Generates the bitmasks of the reversed pattern, which cover the last bytes of the pattern
+/// that fit in a CPU word.
+///
+/// The bitmasks are derived from the bitmasks of the pattern where they cover the same
+/// position, so custom bitmasks are reversed as well.
+fn generate_reverse_masks(config: &PatternView) -> [usize; MASKS_TABLE_SIZE] {
+    let pattern_len = config.pattern.len();
+    let filter_len = get_pattern_length_within_cpu_word(pattern_len);
+    let mut masks = [0; MASKS_TABLE_SIZE];
+
+    for (index, position) in (pattern_len - filter_len..pattern_len).rev().enumerate() {
+        let bit = 1 << (filter_len - 1 - index);
+        for (byte, mask) in masks.iter_mut().enumerate() {
+            if config.allows(position, byte as u8) {
+                *mask |= bit;
+            }
+        }
+    }
+    masks
+}
+
+fn generate_equivalence_masks(search_pattern: &[u8], wildcard: Option<u8>, equivalence: &Equivalence) -> [usize; MASKS_TABLE_SIZE] {
+    let default_mask = calculate_wildcard_mask(search_pattern, wildcard);
+    let mut class_masks = [0; MASKS_TABLE_SIZE];
+
+    search_pattern.iter().rev().enumerate()
+        .filter(|&(_, &pattern_byte)| wildcard != Some(pattern_byte))
+        .for_each(|(i, &pattern_byte)| class_masks[equivalence.fold(pattern_byte) as usize] |= 1 << i);
+
+    std::array::from_fn(|byte| default_mask | class_masks[equivalence.fold(byte as u8) as usize])
+}
+
 #[cfg(test)]
 #[path = "./bndm_test.rs"]
 mod bndm_test;
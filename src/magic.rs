@@ -0,0 +1,212 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! A set of magic numbers of common file formats for identifying and detecting files.
+
+use std::sync::OnceLock;
+
+use crate::{BndmConfig, MultiBndmConfig, matches_at};
+
+/// A magic number that identifies a file format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Magic {
+    /// The name of the file format.
+    pub format: &'static str,
+
+    /// The common file extension of the file format.
+    pub extension: &'static str,
+
+    /// The offset of the signature from the start of the file.
+    pub offset: usize,
+
+    /// The signature of the file format.
+    pub signature: &'static [u8],
+
+    /// An optional wildcard character in the signature.
+    pub wildcard: Option<u8>
+}
+
+const fn magic(format: &'static str, extension: &'static str, offset: usize, signature: &'static [u8], wildcard: Option<u8>) -> Magic {
+    Magic { format, extension, offset, signature, wildcard }
+}
+
+/// A file that has been detected inside a source, e.g. inside a firmware or disk image.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EmbeddedFile<'a> {
+    /// The magic number of the file format.
+    pub magic: &'a Magic,
+
+    /// The index in the source at which the file starts.
+    pub offset: usize,
+
+    /// The confidence of the detection, between 0.0 and 1.0. Short signatures, such as `MZ` or
+    /// `BM`, occur by chance much more often than long signatures and get a lower confidence.
+    pub confidence: f32
+}
+
+/// The magic numbers of common file formats, with the most specific signatures first.
+pub const MAGIC_NUMBERS: &[Magic] = &[
+    magic("SQLite", "sqlite", 0, b"SQLite format 3\x00", None),
+    magic("PNG", "png", 0, b"\x89PNG\r\n\x1a\n", None),
+    magic("RAR", "rar", 0, b"Rar!\x1a\x07", None),
+    magic("7-Zip", "7z", 0, b"7z\xbc\xaf\x27\x1c", None),
+    magic("XZ", "xz", 0, b"\xfd7zXZ\x00", None),
+    magic("WAVE", "wav", 0, b"RIFF????WAVE", Some(b'?')),
+    magic("AVI", "avi", 0, b"RIFF????AVI ", Some(b'?')),
+    magic("GIF", "gif", 0, b"GIF8?a", Some(b'?')),
+    magic("PDF", "pdf", 0, b"%PDF-", None),
+    magic("ISO 9660", "iso", 0x8001, b"CD001", None),
+    magic("MP4", "mp4", 4, b"ftyp", None),
+    magic("ZIP", "zip", 0, b"PK\x03\x04", None),
+    magic("ELF", "elf", 0, b"\x7fELF", None),
+    magic("Mach-O", "macho", 0, b"\xcf\xfa\xed\xfe", None),
+    magic("Java class", "class", 0, b"\xca\xfe\xba\xbe", None),
+    magic("TIFF", "tif", 0, b"II*\x00", None),
+    magic("TIFF", "tif", 0, b"MM\x00*", None),
+    magic("Ogg", "ogg", 0, b"OggS", None),
+    magic("FLAC", "flac", 0, b"fLaC", None),
+    magic("PSID", "sid", 0, b"PSID", None),
+    magic("RSID", "sid", 0, b"RSID", None),
+    magic("JPEG", "jpg", 0, b"\xff\xd8\xff", None),
+    magic("GZIP", "gz", 0, b"\x1f\x8b\x08", None),
+    magic("BZIP2", "bz2", 0, b"BZh", None),
+    magic("MP3", "mp3", 0, b"ID3", None),
+    magic("MS-DOS executable", "exe", 0, b"MZ", None),
+    magic("BMP", "bmp", 0, b"BM", None)
+];
+
+/// The `MagicSet` struct stores a set of magic numbers together with their compiled
+/// configurations, and a `MultiBndmConfig` of all signatures for detecting embedded files in a
+/// single pass.
+pub struct MagicSet {
+    magics: Vec<(Magic, BndmConfig)>,
+    signatures: MultiBndmConfig
+}
+
+impl MagicSet {
+    /// Creates a new `MagicSet` instance for the given magic numbers.
+    ///
+    /// # Arguments
+    ///
+    /// * `magics` - The magic numbers, with the most specific signatures first.
+    ///
+    /// # Returns
+    ///
+    /// * `MagicSet` - A new `MagicSet` instance.
+    pub fn new(magics: &[Magic]) -> MagicSet {
+        let magics: Vec<(Magic, BndmConfig)> = magics.iter()
+            .map(|&magic| (magic, BndmConfig::new(magic.signature, magic.wildcard)))
+            .collect();
+        let signatures = MultiBndmConfig::new(magics.iter().map(|(_, config)| config.clone()).collect());
+
+        MagicSet { magics, signatures }
+    }
+
+    /// Returns the built-in set of magic numbers of common file formats.
+    pub fn builtin() -> &'static MagicSet {
+        static BUILTIN: OnceLock<MagicSet> = OnceLock::new();
+        BUILTIN.get_or_init(|| MagicSet::new(MAGIC_NUMBERS))
+    }
+
+    /// Returns an iterator over the magic numbers and their compiled configurations.
+    pub fn iter(&self) -> impl Iterator<Item = &(Magic, BndmConfig)> {
+        self.magics.iter()
+    }
+
+    /// Identifies the file format of the bytes by their magic number.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes of the file, starting at the start of the file.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&Magic>` - Returns the first magic number in the set whose signature matches
+    ///   at its offset, or `None` if the file format is not recognized.
+    pub fn identify(&self, bytes: &[u8]) -> Option<&Magic> {
+        self.magics.iter()
+            .find(|(magic, config)| matches_at(bytes, config, magic.offset))
+            .map(|(magic, _)| magic)
+    }
+
+    /// Searches for files that are embedded inside the source by their magic numbers.
+    ///
+    /// The source is scanned once for all signatures together.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source to search for embedded files.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<EmbeddedFile>` - Returns the detected files ordered by their offset. Files at the
+    ///   same offset are ordered like the magic numbers in the set.
+    pub fn find_embedded<'a>(&'a self, source: &[u8]) -> Vec<EmbeddedFile<'a>> {
+        let mut files: Vec<(usize, EmbeddedFile)> = self.signatures.find_all(source).into_iter()
+            .filter_map(|signature| {
+                let magic = &self.magics[signature.pattern].0;
+                let offset = signature.offset.checked_sub(magic.offset)?;
+                Some((signature.pattern, EmbeddedFile { magic, offset, confidence: confidence(magic) }))
+            })
+            .collect();
+
+        files.sort_by_key(|&(pattern, file)| (file.offset, pattern));
+        files.into_iter().map(|(_, file)| file).collect()
+    }
+}
+
+/// Searches for files that are embedded inside the source by the built-in magic numbers.
+///
+/// # Arguments
+///
+/// * `source` - The source to search for embedded files.
+///
+/// # Returns
+///
+/// * `Vec<EmbeddedFile<'static>>` - Returns the detected files ordered by their offset.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::find_embedded;
+///
+/// let source = b"\x00\x00\x00\x00PK\x03\x04\x14\x00\x00\x00\x08\x00\x00\x00\x1f\x8b\x08\x00";
+/// let files = find_embedded(source);
+/// let formats: Vec<(&str, usize)> = files.iter().map(|file| (file.magic.format, file.offset)).collect();
+/// assert_eq!(formats, vec![("ZIP", 4), ("GZIP", 16)]);
+/// ```
+pub fn find_embedded(source: &[u8]) -> Vec<EmbeddedFile<'static>> {
+    MagicSet::builtin().find_embedded(source)
+}
+
+/// Identifies the file format of the bytes by the built-in magic numbers.
+///
+/// # Arguments
+///
+/// * `bytes` - The bytes of the file, starting at the start of the file.
+///
+/// # Returns
+///
+/// * `Option<&'static Magic>` - Returns the magic number of the file format, or `None` if the
+///   file format is not recognized.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::identify;
+///
+/// let magic = identify(b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR");
+/// assert_eq!(magic.map(|magic| magic.format), Some("PNG"));
+/// ```
+pub fn identify(bytes: &[u8]) -> Option<&'static Magic> {
+    MagicSet::builtin().identify(bytes)
+}
+
+fn confidence(magic: &Magic) -> f32 {
+    let literal_len = magic.signature.iter().filter(|&&byte| magic.wildcard != Some(byte)).count();
+    (literal_len as f32 / 8.0).min(1.0)
+}
+
+#[cfg(test)]
+#[path = "./magic_test.rs"]
+mod magic_test;
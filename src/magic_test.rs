@@ -0,0 +1,90 @@
+use super::*;
+
+#[test]
+fn identify_png() {
+    let magic = identify(b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR");
+
+    assert_eq!(magic.map(|magic| magic.format), Some("PNG"));
+}
+
+#[test]
+fn identify_with_wildcard() {
+    let magic = identify(b"RIFF\x24\x08\x00\x00WAVEfmt ");
+
+    assert_eq!(magic.map(|magic| magic.format), Some("WAVE"));
+}
+
+#[test]
+fn identify_at_offset() {
+    let magic = identify(b"\x00\x00\x00\x18ftypmp42");
+
+    assert_eq!(magic.map(|magic| magic.extension), Some("mp4"));
+}
+
+#[test]
+fn identify_specific_before_generic() {
+    let magic = identify(b"GIF89a\x01\x00");
+
+    assert_eq!(magic.map(|magic| magic.format), Some("GIF"));
+}
+
+#[test]
+fn identify_unknown() {
+    let magic = identify(b"The quick brown fox jumps over the lazy dog");
+
+    assert_eq!(magic, None);
+}
+
+#[test]
+fn identify_truncated() {
+    let magic = identify(b"\x89PN");
+
+    assert_eq!(magic, None);
+}
+
+#[test]
+fn identify_custom_set() {
+    let magics = [Magic { format: "C64 PRG", extension: "prg", offset: 0, signature: b"\x01\x08", wildcard: None }];
+    let set = MagicSet::new(&magics);
+
+    assert_eq!(set.identify(b"\x01\x08\x0b\x08"), Some(&magics[0]));
+    assert_eq!(set.identify(b"\x89PNG\r\n\x1a\n"), None);
+}
+
+#[test]
+fn find_embedded_files() {
+    let mut source = b"firmware header ".to_vec();
+    source.extend_from_slice(b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR");
+    source.extend_from_slice(b"padding");
+    source.extend_from_slice(b"\x7fELF\x02\x01\x01");
+    let files = find_embedded(&source);
+    let formats: Vec<(&str, usize)> = files.iter().map(|file| (file.magic.format, file.offset)).collect();
+
+    assert_eq!(formats, vec![("PNG", 16), ("ELF", 39)]);
+}
+
+#[test]
+fn find_embedded_at_offset() {
+    let mut source = vec![0; 10];
+    source.extend_from_slice(b"\x00\x00\x00\x18ftypisom");
+    let files = find_embedded(&source);
+
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].magic.format, "MP4");
+    assert_eq!(files[0].offset, 10);
+}
+
+#[test]
+fn find_embedded_confidence() {
+    let files = find_embedded(b"..MZ..\x89PNG\r\n\x1a\n..RIFF\x00\x00\x00\x00WAVE");
+    let confidences: Vec<(&str, f32)> = files.iter().map(|file| (file.magic.format, file.confidence)).collect();
+
+    assert_eq!(confidences, vec![("MS-DOS executable", 0.25), ("PNG", 1.0), ("WAVE", 1.0)]);
+}
+
+#[test]
+fn find_embedded_nothing() {
+    let files = find_embedded(b"The quick brown fox jumps over the lazy dog");
+
+    assert_eq!(files, vec![]);
+}
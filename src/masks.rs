@@ -0,0 +1,207 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! The bitmasks that BNDM uses to scan a text, for building custom matchers and analyzing
+//! patterns.
+
+use std::error::Error;
+use std::fmt;
+
+use crate::{MASKS_TABLE_SIZE, PatternView, WORD_SIZE_IN_BITS, generate_masks, get_pattern_length_within_cpu_word};
+
+/// The `MaskTable` struct holds a bitmask for every byte value.
+///
+/// Bit `len - 1 - i` of the mask of a byte is set if the byte matches position `i` of the
+/// pattern, where `len` is the number of positions of the pattern covered by the table. Only
+/// the first `usize::BITS` bytes of a pattern are covered.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MaskTable {
+    masks: [usize; MASKS_TABLE_SIZE],
+    len: usize
+}
+
+/// The error type for building mask tables.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MaskTableError {
+    /// The pattern is empty.
+    EmptyPattern,
+
+    /// The number of positions is zero or exceeds the word size.
+    InvalidLength(usize),
+
+    /// A mask has bits set beyond the number of positions; the byte of the mask is given.
+    UnusedBits(u8),
+
+    /// A position is not matched by any byte; the position is given.
+    UnmatchedPosition(usize),
+
+    /// The number of positions of the table differs from the number of positions of the
+    /// pattern that are scanned with the table; both numbers are given.
+    LengthMismatch(usize, usize),
+
+    /// The byte of the pattern at the given position does not match the table.
+    InconsistentPattern(usize)
+}
+
+impl fmt::Display for MaskTableError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MaskTableError::EmptyPattern => write!(f, "pattern is empty"),
+            MaskTableError::InvalidLength(len) => write!(f, "invalid number of positions {len}"),
+            MaskTableError::UnusedBits(byte) => write!(f, "mask of byte {byte:#04x} has bits set beyond the positions"),
+            MaskTableError::UnmatchedPosition(position) => write!(f, "position {position} is not matched by any byte"),
+            MaskTableError::LengthMismatch(table_len, pattern_len) =>
+                write!(f, "table has {table_len} positions, but the pattern needs {pattern_len}"),
+            MaskTableError::InconsistentPattern(position) => write!(f, "pattern byte at position {position} does not match the table")
+        }
+    }
+}
+
+impl Error for MaskTableError {}
+
+impl MaskTable {
+    /// Creates a new `MaskTable` instance for the pattern.
+    ///
+    /// # Arguments
+    ///
+    /// * `search_pattern` - The pattern to generate the bitmasks for.
+    /// * `wildcard` - An optional wildcard character. If provided, this character in the pattern
+    ///   can match any character in the text.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<MaskTable, MaskTableError>` - Returns the mask table, or an error if the pattern
+    ///   is empty.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::MaskTable;
+    ///
+    /// let table = MaskTable::new(b"ju??s", Some(b'?')).unwrap();
+    /// assert_eq!(table.len(), 5);
+    /// assert_eq!(table.mask(b'j'), 0b10110);
+    /// assert_eq!(table.positions(b'u').collect::<Vec<_>>(), vec![1, 2, 3]);
+    /// assert_eq!(table.bytes_at(0), vec![b'j']);
+    /// ```
+    pub fn new(search_pattern: &[u8], wildcard: Option<u8>) -> Result<MaskTable, MaskTableError> {
+        if search_pattern.is_empty() {
+            return Err(MaskTableError::EmptyPattern);
+        }
+
+        let len = get_pattern_length_within_cpu_word(search_pattern.len());
+        Ok(MaskTable {
+            masks: generate_masks(&search_pattern[..len], wildcard),
+            len
+        })
+    }
+
+    /// Creates a new `MaskTable` instance from precomputed bitmasks.
+    ///
+    /// # Arguments
+    ///
+    /// * `masks` - The bitmask of every byte value, in which bit `len - 1 - i` is set if the
+    ///   byte matches position `i`.
+    /// * `len` - The number of positions covered by the masks, between 1 and the word size.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<MaskTable, MaskTableError>` - Returns the mask table, or an error if the number
+    ///   of positions is invalid, a mask has bits set beyond the positions, or a position is not
+    ///   matched by any byte.
+    pub fn from_masks(masks: [usize; MASKS_TABLE_SIZE], len: usize) -> Result<MaskTable, MaskTableError> {
+        if len == 0 || len > WORD_SIZE_IN_BITS {
+            return Err(MaskTableError::InvalidLength(len));
+        }
+        if let Some(byte) = masks.iter().position(|&mask| len < WORD_SIZE_IN_BITS && mask >> len != 0) {
+            return Err(MaskTableError::UnusedBits(byte as u8));
+        }
+
+        let table = MaskTable { masks, len };
+        match (0..len).find(|&position| table.is_unmatched(position)) {
+            Some(position) => Err(MaskTableError::UnmatchedPosition(position)),
+            None => Ok(table)
+        }
+    }
+
+    /// Returns the number of positions of the pattern covered by the table.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the table covers no positions. A table that is built from a pattern is
+    /// never empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the bitmask of the byte.
+    pub fn mask(&self, byte: u8) -> usize {
+        self.masks[byte as usize]
+    }
+
+    /// Returns the bitmasks of all byte values.
+    pub fn masks(&self) -> &[usize; MASKS_TABLE_SIZE] {
+        &self.masks
+    }
+
+    /// Returns whether the byte matches the given position of the pattern.
+    pub fn matches(&self, byte: u8, position: usize) -> bool {
+        position < self.len && self.mask(byte) & (1 << (self.len - 1 - position)) != 0
+    }
+
+    /// Returns the positions of the pattern that the byte matches, in ascending order.
+    pub fn positions(&self, byte: u8) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len).filter(move |&position| self.matches(byte, position))
+    }
+
+    /// Returns the byte values that match the given position of the pattern, in ascending
+    /// order.
+    pub fn bytes_at(&self, position: usize) -> Vec<u8> {
+        (0..=u8::MAX).filter(|&byte| self.matches(byte, position)).collect()
+    }
+
+    fn is_unmatched(&self, position: usize) -> bool {
+        !(0..=u8::MAX).any(|byte| self.matches(byte, position))
+    }
+
+    /// Returns the number of byte values that match at least one position of the pattern. The
+    /// fewer bytes match, the further BNDM can shift on average.
+    pub fn matching_byte_count(&self) -> usize {
+        self.masks.iter().filter(|&&mask| mask != 0).count()
+    }
+}
+
+/// Checks a configuration that was loaded from outside the crate: no mask may have bits set
+/// beyond the scanned part of the pattern, as the scan relies on that to stay within the text,
+/// and the pattern and the classes of bytes must agree with the masks.
+pub(crate) fn validate_view(config: &PatternView) -> Result<(), MaskTableError> {
+    let len = get_pattern_length_within_cpu_word(config.pattern.len());
+    if len == 0 {
+        return match config.masks.iter().position(|&mask| mask != 0) {
+            Some(byte) => Err(MaskTableError::UnusedBits(byte as u8)),
+            None => Ok(())
+        };
+    }
+
+    // A position of an empty class of bytes is matched by no byte at all.
+    let table = match MaskTable::from_masks(*config.masks, len) {
+        Ok(table) => table,
+        Err(MaskTableError::UnmatchedPosition(_)) => MaskTable { masks: *config.masks, len },
+        Err(error) => return Err(error)
+    };
+
+    let is_consistent = |position: usize, byte: u8| match config.classes {
+        Some(classes) => classes.get(position).is_some_and(|class| byte == class.bytes().next().unwrap_or(0) &&
+            (position >= len || class.is_empty() || table.matches(byte, position))),
+        None => position >= len || config.wildcard == Some(byte) || table.matches(byte, position)
+    };
+    match config.pattern.iter().enumerate().position(|(position, &byte)| !is_consistent(position, byte)) {
+        Some(position) => Err(MaskTableError::InconsistentPattern(position)),
+        None => Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "./masks_test.rs"]
+mod masks_test;
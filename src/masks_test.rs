@@ -0,0 +1,129 @@
+use super::*;
+use crate::BndmConfig;
+
+#[test]
+fn new_empty_pattern() {
+    assert_eq!(MaskTable::new(b"", None), Err(MaskTableError::EmptyPattern));
+    assert_eq!(MaskTableError::EmptyPattern.to_string(), "pattern is empty");
+}
+
+#[test]
+fn new_matches_config_masks() {
+    let table = MaskTable::new(b"ju??s", Some(b'?')).unwrap();
+    let config = BndmConfig::new(b"ju??s", Some(b'?'));
+
+    assert_eq!(table.masks(), &*config.masks);
+}
+
+#[test]
+fn positions_of_repeated_byte() {
+    let table = MaskTable::new(b"abab", None).unwrap();
+
+    assert_eq!(table.mask(b'a'), 0b1010);
+    assert_eq!(table.positions(b'a').collect::<Vec<_>>(), vec![0, 2]);
+    assert_eq!(table.positions(b'c').count(), 0);
+}
+
+#[test]
+fn bytes_at_wildcard_position() {
+    let table = MaskTable::new(b"a?", Some(b'?')).unwrap();
+
+    assert_eq!(table.bytes_at(1).len(), 256);
+    assert_eq!(table.bytes_at(2), vec![]);
+    assert_eq!(table.matching_byte_count(), 256);
+}
+
+#[test]
+fn matching_byte_count_literal() {
+    let table = MaskTable::new(b"hello", None).unwrap();
+
+    assert_eq!(table.matching_byte_count(), 4);
+    assert!(!table.is_empty());
+}
+
+#[test]
+fn len_limited_to_word_size() {
+    let table = MaskTable::new(&[b'a'; 100], None).unwrap();
+
+    assert_eq!(table.len(), usize::BITS as usize);
+    assert_eq!(table.mask(b'a'), usize::MAX);
+}
+
+#[test]
+fn from_masks_invalid_length() {
+    assert_eq!(MaskTable::from_masks([1; 256], 0), Err(MaskTableError::InvalidLength(0)));
+    assert_eq!(MaskTable::from_masks([1; 256], 65), Err(MaskTableError::InvalidLength(65)));
+}
+
+#[test]
+fn from_masks_unused_bits() {
+    let mut masks = [0b11; 256];
+    masks[0x41] = 0b111;
+
+    assert_eq!(MaskTable::from_masks(masks, 2), Err(MaskTableError::UnusedBits(0x41)));
+}
+
+#[test]
+fn from_masks_unmatched_position() {
+    let mut masks = [0; 256];
+    masks[b'a' as usize] = 0b101;
+
+    assert_eq!(MaskTable::from_masks(masks, 3), Err(MaskTableError::UnmatchedPosition(1)));
+}
+
+#[test]
+fn from_masks_full_word() {
+    let table = MaskTable::from_masks([usize::MAX; 256], usize::BITS as usize).unwrap();
+
+    assert_eq!(table.bytes_at(0).len(), 256);
+}
+
+#[test]
+fn config_from_mask_table_length_mismatch() {
+    let table = MaskTable::new(b"abc", None).unwrap();
+
+    assert_eq!(BndmConfig::from_mask_table(b"abcd", None, &table).err(), Some(MaskTableError::LengthMismatch(3, 4)));
+}
+
+#[test]
+fn config_from_mask_table_inconsistent_pattern() {
+    let table = MaskTable::new(b"abc", None).unwrap();
+
+    assert_eq!(BndmConfig::from_mask_table(b"abd", None, &table).err(), Some(MaskTableError::InconsistentPattern(2)));
+    assert!(BndmConfig::from_mask_table(b"a?c", Some(b'?'), &table).is_ok());
+}
+
+#[test]
+fn config_from_mask_table_with_classes() {
+    let mut masks = *MaskTable::new(b"x", None).unwrap().masks();
+    masks[b'y' as usize] = 1;
+    let table = MaskTable::from_masks(masks, 1).unwrap();
+    let config = BndmConfig::from_mask_table(b"x", None, &table).unwrap();
+
+    assert_eq!(crate::find_pattern(b"abyx", &config), Some(2));
+}
+
+#[test]
+fn config_from_mask_table_long_pattern() {
+    let pattern = [b'a'; 70];
+    let table = MaskTable::from_masks([usize::MAX; 256], usize::BITS as usize).unwrap();
+    let config = BndmConfig::from_mask_table(&pattern, None, &table).unwrap();
+    let mut source = vec![b'z'; 64];
+    source.extend([b'a'; 6]);
+
+    assert_eq!(crate::find_pattern(&source, &config), Some(0));
+
+    source[66] = b'b';
+    assert_eq!(crate::find_pattern(&source, &config), None);
+}
+
+#[test]
+fn index_query_with_mask_table_classes() {
+    let mut masks = *MaskTable::new(b"v0.1", None).unwrap().masks();
+    (b'1'..=b'9').for_each(|digit| masks[digit as usize] |= 0b0101);
+    let table = MaskTable::from_masks(masks, 4).unwrap();
+    let config = BndmConfig::from_mask_table(b"v0.1", None, &table).unwrap();
+    let index = crate::HaystackIndex::build(b"v0.1 v1.2 v0.9");
+
+    assert_eq!(index.query(&config), vec![0, 5, 10]);
+}
@@ -0,0 +1,90 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Matches that describe the range of an occurrence and the bytes matched by wildcards.
+
+use std::ops::Range;
+
+use crate::{BndmConfig, find_indexes, find_pattern};
+
+/// A match of a pattern in a source string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Match {
+    /// The range of the match in the source.
+    pub range: Range<usize>,
+
+    /// The indexes in the source of the bytes that were matched by a wildcard, in ascending
+    /// order.
+    pub wildcard_indexes: Vec<usize>
+}
+
+impl Match {
+    fn new(pattern_len: usize, wildcard_positions: &[usize], start: usize) -> Match {
+        Match {
+            range: start..start + pattern_len,
+            wildcard_indexes: wildcard_positions.iter().map(|position| start + position).collect()
+        }
+    }
+
+    /// Returns the index of the first byte of the match in the source.
+    pub fn start(&self) -> usize {
+        self.range.start
+    }
+
+    /// Returns the index after the last byte of the match in the source.
+    pub fn end(&self) -> usize {
+        self.range.end
+    }
+}
+
+/// Searches for the first occurrence of the pattern in the source string and describes it.
+///
+/// # Arguments
+///
+/// * `source` - The source string to search for the pattern.
+/// * `config` - The configuration for the BNDM search, which includes the pattern and the
+///   bitmasks.
+///
+/// # Returns
+///
+/// * `Option<Match>` - Returns the range of the first occurrence and the indexes of the bytes
+///   matched by wildcards, or `None` if the pattern is not found.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{BndmConfig, find_match};
+///
+/// let source = b"The quick brown fox jumps over the lazy dog";
+/// let config = BndmConfig::new(b"ju??s", Some(b'?'));
+/// let found = find_match(source, &config).unwrap();
+/// assert_eq!(found.range, 20..25);
+/// assert_eq!(found.wildcard_indexes, vec![22, 23]);
+/// assert_eq!(&source[found.range], b"jumps");
+/// ```
+pub fn find_match(source: &[u8], config: &BndmConfig) -> Option<Match> {
+    find_pattern(source, config).map(|index| Match::new(config.pattern.len(), &config.wildcard_positions(), index))
+}
+
+/// Searches for all occurrences of the pattern in the source string and describes them.
+///
+/// Occurrences do not overlap, as with `find_all`.
+///
+/// # Arguments
+///
+/// * `source` - The source string to search for the pattern.
+/// * `config` - The configuration for the BNDM search, which includes the pattern and the
+///   bitmasks.
+///
+/// # Returns
+///
+/// * `Vec<Match>` - Returns the ranges of all occurrences and the indexes of the bytes matched
+///   by wildcards, in ascending order.
+pub fn find_all_matches(source: &[u8], config: &BndmConfig) -> Vec<Match> {
+    let wildcard_positions = config.wildcard_positions();
+    find_indexes(source, config).map(|index| Match::new(config.pattern.len(), &wildcard_positions, index)).collect()
+}
+
+#[cfg(test)]
+#[path = "./matched_test.rs"]
+mod matched_test;
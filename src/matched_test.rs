@@ -0,0 +1,48 @@
+use super::*;
+
+#[test]
+fn find_match_literal() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let config = BndmConfig::new(b"fox", None);
+
+    assert_eq!(find_match(source, &config), Some(Match { range: 16..19, wildcard_indexes: vec![] }));
+}
+
+#[test]
+fn find_match_wildcard() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let config = BndmConfig::new(b"?o?", Some(b'?'));
+    let found = find_match(source, &config).unwrap();
+
+    assert_eq!(found.start(), 11);
+    assert_eq!(found.end(), 14);
+    assert_eq!(found.wildcard_indexes, vec![11, 13]);
+}
+
+#[test]
+fn find_match_no_match() {
+    let config = BndmConfig::new(b"cat", None);
+
+    assert_eq!(find_match(b"The quick brown fox", &config), None);
+}
+
+#[test]
+fn find_all_matches_wildcard() {
+    let source = b"a1b2a3";
+    let config = BndmConfig::new(b"a?", Some(b'?'));
+
+    assert_eq!(find_all_matches(source, &config), vec![
+        Match { range: 0..2, wildcard_indexes: vec![1] },
+        Match { range: 4..6, wildcard_indexes: vec![5] }
+    ]);
+}
+
+#[test]
+fn find_all_matches_long_pattern() {
+    let mut pattern = vec![b'x'; 70];
+    pattern[68] = b'?';
+    let source = vec![b'x'; 75];
+    let config = BndmConfig::new(&pattern, Some(b'?'));
+
+    assert_eq!(find_all_matches(&source, &config), vec![Match { range: 0..70, wildcard_indexes: vec![68] }]);
+}
@@ -0,0 +1,226 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Substring search with the same shape as the `memmem` module of the `memchr` crate, so that
+//! call sites can switch between both engines by changing the import.
+//!
+//! As with `memmem`, an empty needle matches at every position of the haystack, and the
+//! iterators report occurrences that do not overlap. The `FinderBuilder` can additionally set
+//! a wildcard character.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use bndm::memmem;
+//!
+//! let haystack = b"foo bar foo baz foo";
+//! assert_eq!(memmem::find(haystack, b"foo"), Some(0));
+//! assert_eq!(memmem::rfind(haystack, b"foo"), Some(16));
+//!
+//! let finder = memmem::Finder::new("ba");
+//! assert_eq!(finder.find_iter(haystack).collect::<Vec<_>>(), vec![4, 12]);
+//! ```
+
+use std::borrow::Cow;
+use std::iter::FusedIterator;
+
+use crate::BndmConfigRef;
+
+/// Returns the index of the first occurrence of the needle in the haystack, or `None` if the
+/// needle is not found.
+pub fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    Finder::new(needle).find(haystack)
+}
+
+/// Returns the index of the last occurrence of the needle in the haystack, or `None` if the
+/// needle is not found.
+pub fn rfind(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    FinderRev::new(needle).rfind(haystack)
+}
+
+/// Returns an iterator over the indexes of all non-overlapping occurrences of the needle in
+/// the haystack, in ascending order.
+pub fn find_iter<'h, 'n, N: ?Sized + AsRef<[u8]>>(haystack: &'h [u8], needle: &'n N) -> FindIter<'h, 'n> {
+    FindIter::new(haystack, Cow::Owned(Finder::new(needle)))
+}
+
+/// Returns an iterator over the indexes of all non-overlapping occurrences of the needle in
+/// the haystack, in descending order.
+pub fn rfind_iter<'h, 'n, N: ?Sized + AsRef<[u8]>>(haystack: &'h [u8], needle: &'n N) -> FindRevIter<'h, 'n> {
+    FindRevIter::new(haystack, Cow::Owned(FinderRev::new(needle)))
+}
+
+/// The `Finder` struct searches for the first occurrences of a needle.
+#[derive(Clone, Debug)]
+pub struct Finder<'n> {
+    config: BndmConfigRef<'n>
+}
+
+impl<'n> Finder<'n> {
+    /// Creates a new `Finder` instance for the needle.
+    pub fn new<N: ?Sized + AsRef<[u8]>>(needle: &'n N) -> Finder<'n> {
+        FinderBuilder::new().build_forward(needle)
+    }
+
+    /// Returns the index of the first occurrence of the needle in the haystack, or `None` if
+    /// the needle is not found.
+    pub fn find(&self, haystack: &[u8]) -> Option<usize> {
+        match self.needle().is_empty() {
+            true => Some(0),
+            false => self.config.find(haystack)
+        }
+    }
+
+    /// Returns an iterator over the indexes of all non-overlapping occurrences of the needle
+    /// in the haystack, in ascending order.
+    pub fn find_iter<'a, 'h>(&'a self, haystack: &'h [u8]) -> FindIter<'h, 'a> {
+        FindIter::new(haystack, Cow::Borrowed(self))
+    }
+
+    /// Returns the needle.
+    pub fn needle(&self) -> &[u8] {
+        self.config.pattern()
+    }
+
+    /// Converts the finder into one that owns the needle.
+    pub fn into_owned(self) -> Finder<'static> {
+        Finder { config: self.config.into_owned() }
+    }
+}
+
+/// The `FinderRev` struct searches for the last occurrences of a needle.
+#[derive(Clone, Debug)]
+pub struct FinderRev<'n> {
+    config: BndmConfigRef<'n>
+}
+
+impl<'n> FinderRev<'n> {
+    /// Creates a new `FinderRev` instance for the needle.
+    pub fn new<N: ?Sized + AsRef<[u8]>>(needle: &'n N) -> FinderRev<'n> {
+        FinderBuilder::new().build_reverse(needle)
+    }
+
+    /// Returns the index of the last occurrence of the needle in the haystack, or `None` if
+    /// the needle is not found.
+    pub fn rfind(&self, haystack: &[u8]) -> Option<usize> {
+        match self.needle().is_empty() {
+            true => Some(haystack.len()),
+            false => self.config.rfind(haystack)
+        }
+    }
+
+    /// Returns an iterator over the indexes of all non-overlapping occurrences of the needle
+    /// in the haystack, in descending order.
+    pub fn rfind_iter<'a, 'h>(&'a self, haystack: &'h [u8]) -> FindRevIter<'h, 'a> {
+        FindRevIter::new(haystack, Cow::Borrowed(self))
+    }
+
+    /// Returns the needle.
+    pub fn needle(&self) -> &[u8] {
+        self.config.pattern()
+    }
+
+    /// Converts the finder into one that owns the needle.
+    pub fn into_owned(self) -> FinderRev<'static> {
+        FinderRev { config: self.config.into_owned() }
+    }
+}
+
+/// The `FinderBuilder` struct builds finders with additional settings.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::memmem::FinderBuilder;
+///
+/// let finder = FinderBuilder::new().wildcard(b'?').build_forward(b"ju??s");
+/// assert_eq!(finder.find(b"The quick brown fox jumps over the lazy dog"), Some(20));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct FinderBuilder {
+    wildcard: Option<u8>
+}
+
+impl FinderBuilder {
+    /// Creates a new `FinderBuilder` instance with the default settings.
+    pub fn new() -> FinderBuilder {
+        FinderBuilder::default()
+    }
+
+    /// Sets the wildcard character, which matches any byte of the haystack.
+    pub fn wildcard(&mut self, wildcard: u8) -> &mut FinderBuilder {
+        self.wildcard = Some(wildcard);
+        self
+    }
+
+    /// Builds a `Finder` instance for the needle.
+    pub fn build_forward<'n, N: ?Sized + AsRef<[u8]>>(&self, needle: &'n N) -> Finder<'n> {
+        Finder { config: BndmConfigRef::new(needle.as_ref(), self.wildcard) }
+    }
+
+    /// Builds a `FinderRev` instance for the needle.
+    pub fn build_reverse<'n, N: ?Sized + AsRef<[u8]>>(&self, needle: &'n N) -> FinderRev<'n> {
+        FinderRev { config: BndmConfigRef::new(needle.as_ref(), self.wildcard) }
+    }
+}
+
+/// An iterator over the indexes of the non-overlapping occurrences of a needle, in ascending
+/// order.
+#[derive(Clone, Debug)]
+pub struct FindIter<'h, 'n> {
+    haystack: &'h [u8],
+    finder: Cow<'n, Finder<'n>>,
+    position: usize
+}
+
+impl<'h, 'n> FindIter<'h, 'n> {
+    fn new(haystack: &'h [u8], finder: Cow<'n, Finder<'n>>) -> FindIter<'h, 'n> {
+        FindIter { haystack, finder, position: 0 }
+    }
+}
+
+impl Iterator for FindIter<'_, '_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let index = self.position + self.finder.find(self.haystack.get(self.position..)?)?;
+        self.position = index + self.finder.needle().len().max(1);
+        Some(index)
+    }
+}
+
+impl FusedIterator for FindIter<'_, '_> {}
+
+/// An iterator over the indexes of the non-overlapping occurrences of a needle, in descending
+/// order.
+#[derive(Clone, Debug)]
+pub struct FindRevIter<'h, 'n> {
+    haystack: &'h [u8],
+    finder: Cow<'n, FinderRev<'n>>,
+    end: Option<usize>
+}
+
+impl<'h, 'n> FindRevIter<'h, 'n> {
+    fn new(haystack: &'h [u8], finder: Cow<'n, FinderRev<'n>>) -> FindRevIter<'h, 'n> {
+        FindRevIter { haystack, finder, end: Some(haystack.len()) }
+    }
+}
+
+impl Iterator for FindRevIter<'_, '_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let index = self.finder.rfind(&self.haystack[..self.end?]);
+        self.end = match self.finder.needle().is_empty() {
+            true => index.and_then(|index| index.checked_sub(1)),
+            false => index
+        };
+        index
+    }
+}
+
+impl FusedIterator for FindRevIter<'_, '_> {}
+
+#[cfg(test)]
+#[path = "./memmem_test.rs"]
+mod memmem_test;
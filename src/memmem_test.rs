@@ -0,0 +1,93 @@
+use super::*;
+
+fn naive_find_iter(haystack: &[u8], needle: &[u8]) -> Vec<usize> {
+    let mut indexes = Vec::new();
+    let mut position = 0;
+    while position + needle.len() <= haystack.len() {
+        if &haystack[position..position + needle.len()] == needle {
+            indexes.push(position);
+            position += needle.len().max(1);
+        } else {
+            position += 1;
+        }
+    }
+    indexes
+}
+
+fn naive_rfind_iter(haystack: &[u8], needle: &[u8]) -> Vec<usize> {
+    let mut indexes = Vec::new();
+    let mut end = haystack.len();
+    while end >= needle.len() {
+        if &haystack[end - needle.len()..end] == needle {
+            indexes.push(end - needle.len());
+            if needle.is_empty() {
+                if end == 0 {
+                    break;
+                }
+                end -= 1;
+            } else {
+                end -= needle.len();
+            }
+        } else {
+            end -= 1;
+        }
+    }
+    indexes
+}
+
+#[test]
+fn matches_naive_search() {
+    let mut seed = 0x2545f491u32;
+    let mut next = move |bound: u32| {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        (seed % bound) as usize
+    };
+
+    for _ in 0..2000 {
+        let haystack: Vec<u8> = (0..next(150)).map(|_| b"ab"[next(2)]).collect();
+        let needle: Vec<u8> = (0..next(80)).map(|_| b"ab"[next(2)]).collect();
+        let forward = naive_find_iter(&haystack, &needle);
+        let reverse = naive_rfind_iter(&haystack, &needle);
+
+        assert_eq!(find(&haystack, &needle), forward.first().copied());
+        assert_eq!(rfind(&haystack, &needle), reverse.first().copied());
+        assert_eq!(find_iter(&haystack, &needle).collect::<Vec<_>>(), forward);
+        assert_eq!(rfind_iter(&haystack, &needle).collect::<Vec<_>>(), reverse);
+    }
+}
+
+#[test]
+fn empty_needle() {
+    assert_eq!(find(b"abc", b""), Some(0));
+    assert_eq!(rfind(b"abc", b""), Some(3));
+    assert_eq!(find_iter(b"abc", b"").collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+    assert_eq!(rfind_iter(b"abc", b"").collect::<Vec<_>>(), vec![3, 2, 1, 0]);
+    assert_eq!(find_iter(b"", b"").collect::<Vec<_>>(), vec![0]);
+}
+
+#[test]
+fn finders_own_needle() {
+    let finder = {
+        let needle = String::from("fox");
+        Finder::new(&needle).into_owned()
+    };
+    let finder_rev = FinderRev::new("o").into_owned();
+    let haystack = b"The quick brown fox jumps over the lazy dog";
+
+    assert_eq!(finder.needle(), b"fox");
+    assert_eq!(finder.find(haystack), Some(16));
+    assert_eq!(finder_rev.rfind_iter(haystack).collect::<Vec<_>>(), vec![41, 26, 17, 12]);
+}
+
+#[test]
+fn builder_wildcard() {
+    let mut builder = FinderBuilder::new();
+    builder.wildcard(b'?');
+    let haystack = b"The quick brown fox jumps over the lazy dog";
+
+    assert_eq!(builder.build_forward("?o").find_iter(haystack).collect::<Vec<_>>(), vec![11, 16, 25, 40]);
+    assert_eq!(builder.build_reverse("?o").rfind(haystack), Some(40));
+    assert_eq!(FinderBuilder::new().build_forward("?o").find(haystack), None);
+}
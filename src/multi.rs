@@ -0,0 +1,162 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Searching for several patterns in a single pass over the text.
+//!
+//! The bitmasks of the patterns are superimposed over the length of the shortest pattern, so
+//! a single BNDM scan finds every window in which one of the patterns may start. Every such
+//! window is then verified against each pattern.
+
+use crate::{BndmConfig, MASKS_TABLE_SIZE, get_mask, matches_at, scan_bndm};
+
+/// A match of one of the patterns of a `MultiBndmConfig`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MultiMatch {
+    /// The index of the pattern in the configuration.
+    pub pattern: usize,
+
+    /// The index in the text at which the pattern starts.
+    pub offset: usize
+}
+
+/// The `MultiBndmConfig` struct stores several patterns together with the superimposed
+/// bitmasks that are used to search for all of them at once.
+#[derive(Clone, Debug)]
+pub struct MultiBndmConfig {
+    configs: Vec<BndmConfig>,
+    masks: Box<[usize; MASKS_TABLE_SIZE]>,
+    filter_len: usize
+}
+
+impl MultiBndmConfig {
+    /// Creates a new `MultiBndmConfig` instance for the patterns.
+    ///
+    /// The text is scanned with the first bytes of every pattern, up to the filter length of
+    /// the shortest pattern, so a short pattern makes the scan advance slower for all patterns.
+    /// Empty patterns never match.
+    ///
+    /// # Arguments
+    ///
+    /// * `configs` - The configurations of the patterns.
+    ///
+    /// # Returns
+    ///
+    /// * `MultiBndmConfig` - A new `MultiBndmConfig` instance.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{BndmConfig, MultiBndmConfig, MultiMatch};
+    ///
+    /// let config = MultiBndmConfig::new(vec![BndmConfig::new(b"fox", None), BndmConfig::new(b"o?er", Some(b'?'))]);
+    /// assert_eq!(config.find_all(b"The quick brown fox jumps over the lazy dog"), vec![
+    ///     MultiMatch { pattern: 0, offset: 16 },
+    ///     MultiMatch { pattern: 1, offset: 26 }
+    /// ]);
+    /// ```
+    pub fn new(configs: Vec<BndmConfig>) -> MultiBndmConfig {
+        let filter_len = configs.iter()
+            .filter(|config| !config.pattern.is_empty())
+            .map(BndmConfig::filter_len)
+            .min()
+            .unwrap_or(0);
+
+        let mut masks = Box::new([0; MASKS_TABLE_SIZE]);
+        for config in configs.iter().filter(|config| !config.pattern.is_empty()) {
+            let shift = config.filter_len() - filter_len;
+            masks.iter_mut().zip(config.masks.iter()).for_each(|(mask, &config_mask)| *mask |= config_mask >> shift);
+        }
+
+        MultiBndmConfig { configs, masks, filter_len }
+    }
+
+    /// Returns the configurations of the patterns.
+    pub fn configs(&self) -> &[BndmConfig] {
+        &self.configs
+    }
+
+    /// Searches for the occurrences of all patterns in the source string.
+    ///
+    /// The occurrences of a single pattern do not overlap, as with `find_all`, but the
+    /// occurrences of different patterns may overlap.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source string to search for the patterns.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<MultiMatch>` - Returns the matches ordered by offset, and by pattern for matches
+    ///   at the same offset.
+    pub fn find_all(&self, source: &[u8]) -> Vec<MultiMatch> {
+        let mut next_offsets = vec![0; self.configs.len()];
+        let mut matches = Vec::new();
+        self.scan(source, |pattern, offset| {
+            if offset >= next_offsets[pattern] {
+                next_offsets[pattern] = offset + self.configs[pattern].pattern.len();
+                matches.push(MultiMatch { pattern, offset });
+            }
+        });
+        matches
+    }
+
+    /// Searches for all occurrences of all patterns in the source string, including
+    /// occurrences that overlap.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source string to search for the patterns.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<MultiMatch>` - Returns the matches ordered by offset, and by pattern for matches
+    ///   at the same offset.
+    pub fn find_all_overlapping(&self, source: &[u8]) -> Vec<MultiMatch> {
+        let mut matches = Vec::new();
+        self.scan(source, |pattern, offset| matches.push(MultiMatch { pattern, offset }));
+        matches
+    }
+
+    /// Counts the occurrences of every pattern in the source string, including occurrences
+    /// that overlap, without collecting them.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source string to search for the patterns.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<usize>` - Returns the number of occurrences of every pattern, in the order of the
+    ///   configurations.
+    pub fn count_overlapping(&self, source: &[u8]) -> Vec<usize> {
+        let mut counts = vec![0; self.configs.len()];
+        self.scan(source, |pattern, _| counts[pattern] += 1);
+        counts
+    }
+
+    fn scan(&self, source: &[u8], mut on_match: impl FnMut(usize, usize)) {
+        if self.filter_len == 0 || self.filter_len > source.len() {
+            return;
+        }
+
+        let mut verify = |index: usize| {
+            self.configs.iter().enumerate()
+                .filter(|(_, config)| !config.pattern.is_empty() && matches_at(source, config, index))
+                .for_each(|(pattern, _)| on_match(pattern, index));
+            false
+        };
+
+        match self.filter_len {
+            1 => (0..source.len())
+                .filter(|&index| self.masks[source[index] as usize] != 0)
+                .for_each(|index| { verify(index); }),
+            _ => {
+                scan_bndm(source.len(), self.filter_len, |index| get_mask(source, &self.masks, index), verify);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "./multi_test.rs"]
+mod multi_test;
@@ -0,0 +1,68 @@
+use super::*;
+use crate::{ByteClass, Equivalence, PatternBuilder, find_indexes, find_overlapping_indexes};
+
+const SOURCE: &[u8] = b"The quick brown fox jumps over the lazy dog";
+
+fn expected_matches(source: &[u8], configs: &[BndmConfig], overlapping: bool) -> Vec<MultiMatch> {
+    let mut matches: Vec<MultiMatch> = configs.iter().enumerate().flat_map(|(pattern, config)| {
+        let indexes: Vec<usize> = match overlapping {
+            true => find_overlapping_indexes(source, config).collect(),
+            false => find_indexes(source, config).collect()
+        };
+        indexes.into_iter().map(move |offset| MultiMatch { pattern, offset })
+    }).collect();
+    matches.sort_by_key(|multi_match| (multi_match.offset, multi_match.pattern));
+    matches
+}
+
+#[test]
+fn find_all_of_different_lengths() {
+    let configs = vec![
+        BndmConfig::new(b"the", None),
+        BndmConfig::new(b"o", None),
+        BndmConfig::new_nocase(b"THE QUICK", None),
+        BndmConfig::new(&[b'?'; 70], Some(b'?'))
+    ];
+    let config = MultiBndmConfig::new(configs.clone());
+
+    assert_eq!(config.find_all(SOURCE), expected_matches(SOURCE, &configs, false));
+    assert_eq!(config.find_all_overlapping(SOURCE), expected_matches(SOURCE, &configs, true));
+    assert_eq!(config.count_overlapping(SOURCE), vec![1, 4, 1, 0]);
+}
+
+#[test]
+fn find_all_overlapping_occurrences() {
+    let source = b"aaaaabab";
+    let configs = vec![
+        BndmConfig::new(b"aa", None),
+        BndmConfig::new(b"aab", None),
+        PatternBuilder::new().literal(b"a").class(ByteClass::from_bytes(b"ab")).literal(b"a").build(),
+        BndmConfig::with_equivalence(b"bb", None, &Equivalence::identity().with_class(b"ab"))
+    ];
+    let config = MultiBndmConfig::new(configs.clone());
+
+    assert_eq!(config.find_all(source), expected_matches(source, &configs, false));
+    assert_eq!(config.find_all_overlapping(source), expected_matches(source, &configs, true));
+}
+
+#[test]
+fn find_all_long_patterns() {
+    let mut source = vec![b'x'; 300];
+    source[100..180].copy_from_slice(&[b'a'; 80]);
+    source[200..270].copy_from_slice(&[b'b'; 70]);
+    let configs = vec![BndmConfig::new(&[b'a'; 80], None), BndmConfig::new(&[b'b'; 66], None), BndmConfig::new(&[b'a'; 65], None)];
+    let config = MultiBndmConfig::new(configs.clone());
+
+    assert_eq!(config.find_all_overlapping(&source), expected_matches(&source, &configs, true));
+    assert_eq!(config.count_overlapping(&source), vec![1, 5, 16]);
+}
+
+#[test]
+fn find_all_without_patterns() {
+    let config = MultiBndmConfig::new(vec![BndmConfig::new(b"", None)]);
+
+    assert_eq!(config.find_all(SOURCE), vec![]);
+    assert_eq!(config.count_overlapping(SOURCE), vec![0]);
+    assert_eq!(MultiBndmConfig::new(Vec::new()).find_all(SOURCE), vec![]);
+    assert_eq!(MultiBndmConfig::new(vec![BndmConfig::new(b"fox", None)]).find_all(b"fo"), vec![]);
+}
@@ -0,0 +1,115 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Policies for the bytes of a pattern that match more than one byte of the text.
+//!
+//! A policy is compiled into the fields that `BndmConfig` already has for this, i.e. the
+//! wildcard, the equivalence and the byte classes, and is not stored in the configuration.
+//! Storing the policy as well would give every configuration two descriptions of the same
+//! matching rules that could disagree, since the fields of `BndmConfig` are public, so
+//! `BndmConfig::new` and the other constructors with an optional wildcard remain the primary
+//! constructors rather than shims over `BndmConfig::with_policy`.
+
+use crate::{BndmConfig, ByteClass, Equivalence};
+
+/// The `WildcardPolicy` enum describes which bytes of a pattern match other bytes than
+/// themselves.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum WildcardPolicy {
+    /// Every byte of the pattern only matches itself.
+    #[default]
+    None,
+
+    /// The given byte matches any byte of the text.
+    SingleByte(u8),
+
+    /// Every byte of the set matches any byte of the text.
+    Set(Vec<u8>),
+
+    /// Every byte matches any byte of its equivalence class.
+    Classes(Box<Equivalence>),
+
+    /// Every byte of the list in the pattern is a wildcard that matches the bytes of its own
+    /// class, e.g. `?` for any byte and `#` for an ASCII digit. Other bytes only match
+    /// themselves.
+    Symbols(Vec<(u8, ByteClass)>)
+}
+
+impl From<Option<u8>> for WildcardPolicy {
+    fn from(wildcard: Option<u8>) -> WildcardPolicy {
+        wildcard.map_or(WildcardPolicy::None, WildcardPolicy::SingleByte)
+    }
+}
+
+impl From<u8> for WildcardPolicy {
+    fn from(wildcard: u8) -> WildcardPolicy {
+        WildcardPolicy::SingleByte(wildcard)
+    }
+}
+
+impl From<Equivalence> for WildcardPolicy {
+    fn from(equivalence: Equivalence) -> WildcardPolicy {
+        WildcardPolicy::Classes(Box::new(equivalence))
+    }
+}
+
+impl BndmConfig {
+    /// Creates a new `BndmConfig` instance with the given wildcard policy.
+    ///
+    /// For `WildcardPolicy::Set`, the bytes of the set in the pattern are compiled into byte
+    /// classes that match any byte, and the pattern of the configuration is kept as given.
+    ///
+    /// # Arguments
+    ///
+    /// * `search_pattern` - The pattern to search for in the text.
+    /// * `policy` - The policy for the bytes of the pattern that match other bytes.
+    ///
+    /// # Returns
+    ///
+    /// * `BndmConfig` - A new `BndmConfig` instance.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{BndmConfig, ByteClass, WildcardPolicy, find_pattern};
+    ///
+    /// let source = b"The quick brown fox jumps over the lazy dog";
+    /// let config = BndmConfig::with_policy(b"ju?*s", &WildcardPolicy::Set(b"?*".to_vec()));
+    /// assert_eq!(find_pattern(source, &config), Some(20));
+    ///
+    /// let config = BndmConfig::with_policy(b"ju??s", &Some(b'?').into());
+    /// assert_eq!(find_pattern(source, &config), Some(20));
+    ///
+    /// let symbols = WildcardPolicy::Symbols(vec![(b'?', ByteClass::any()), (b'#', ByteClass::range(b'0'..=b'9'))]);
+    /// let config = BndmConfig::with_policy(b"?-##", &symbols);
+    /// assert_eq!(find_pattern(b"A-1x B-42", &config), Some(5));
+    /// ```
+    pub fn with_policy(search_pattern: &[u8], policy: &WildcardPolicy) -> BndmConfig {
+        match policy {
+            WildcardPolicy::None => BndmConfig::new(search_pattern, None),
+            WildcardPolicy::SingleByte(wildcard) => BndmConfig::new(search_pattern, Some(*wildcard)),
+            WildcardPolicy::Set(wildcards) if wildcards.is_empty() => BndmConfig::new(search_pattern, None),
+            WildcardPolicy::Set(wildcards) => {
+                let classes: Vec<ByteClass> = search_pattern.iter()
+                    .map(|&byte| if wildcards.contains(&byte) { ByteClass::any() } else { ByteClass::byte(byte) })
+                    .collect();
+                let mut config = BndmConfig::with_classes(&classes);
+                config.pattern = search_pattern.to_vec();
+                config
+            }
+            WildcardPolicy::Classes(equivalence) => BndmConfig::with_equivalence(search_pattern, None, equivalence),
+            WildcardPolicy::Symbols(symbols) => {
+                let classes: Vec<ByteClass> = search_pattern.iter()
+                    .map(|&byte| symbols.iter()
+                        .find(|&&(symbol, _)| symbol == byte)
+                        .map_or(ByteClass::byte(byte), |&(_, class)| class))
+                    .collect();
+                BndmConfig::with_classes(&classes)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "./policy_test.rs"]
+mod policy_test;
@@ -0,0 +1,84 @@
+use super::*;
+use crate::find_pattern;
+
+#[test]
+fn policy_from_option() {
+    assert_eq!(WildcardPolicy::from(None), WildcardPolicy::None);
+    assert_eq!(WildcardPolicy::from(Some(b'?')), WildcardPolicy::SingleByte(b'?'));
+    assert_eq!(WildcardPolicy::from(b'?'), WildcardPolicy::SingleByte(b'?'));
+    assert_eq!(WildcardPolicy::default(), WildcardPolicy::None);
+}
+
+#[test]
+fn with_policy_none() {
+    let config = BndmConfig::with_policy(b"ju??s", &WildcardPolicy::None);
+
+    assert_eq!(config.wildcard, None);
+    assert_eq!(find_pattern(b"jumps ju??s", &config), Some(6));
+}
+
+#[test]
+fn with_policy_single_byte() {
+    let config = BndmConfig::with_policy(b"ju??s", &WildcardPolicy::SingleByte(b'?'));
+
+    assert_eq!(config.masks, BndmConfig::new(b"ju??s", Some(b'?')).masks);
+}
+
+#[test]
+fn with_policy_set() {
+    let config = BndmConfig::with_policy(b"*u?p.", &WildcardPolicy::Set(b"?*.".to_vec()));
+
+    assert_eq!(config.pattern, b"*u?p.");
+    assert_eq!(config.wildcard_positions(), vec![0, 2, 4]);
+    assert_eq!(find_pattern(b"The quick brown fox jumps over the lazy dog", &config), Some(20));
+}
+
+#[test]
+fn with_policy_empty_set() {
+    let config = BndmConfig::with_policy(b"a?", &WildcardPolicy::Set(Vec::new()));
+
+    assert_eq!(find_pattern(b"ab a?", &config), Some(3));
+}
+
+#[test]
+fn with_policy_set_long_pattern() {
+    let mut pattern = vec![b'a'; 70];
+    pattern[68] = b'*';
+    let config = BndmConfig::with_policy(&pattern, &WildcardPolicy::Set(b"?*".to_vec()));
+    let mut source = vec![b'a'; 70];
+    source[68] = b'x';
+
+    assert_eq!(find_pattern(&source, &config), Some(0));
+}
+
+#[test]
+fn with_policy_classes() {
+    let policy = WildcardPolicy::from(Equivalence::latin1_diacritics());
+    let config = BndmConfig::with_policy(b"cafe", &policy);
+
+    assert_eq!(find_pattern(b"caf\xe9", &config), Some(0));
+}
+
+#[test]
+fn with_policy_symbols() {
+    let policy = WildcardPolicy::Symbols(vec![(b'?', ByteClass::any()), (b'#', ByteClass::range(b'0'..=b'9'))]);
+    let config = BndmConfig::with_policy(b"v#.?", &policy);
+
+    assert_eq!(find_pattern(b"vx.1 v2.x", &config), Some(5));
+    assert_eq!(config.wildcard_positions(), vec![3]);
+}
+
+#[test]
+fn with_policy_symbols_distinct_classes() {
+    let policy = WildcardPolicy::Symbols(vec![(b'L', ByteClass::range(b'a'..=b'z')), (b'D', ByteClass::range(b'0'..=b'9'))]);
+    let config = BndmConfig::with_policy(b"LD", &policy);
+
+    assert_eq!(find_pattern(b"1a A1 a1", &config), Some(6));
+}
+
+#[test]
+fn with_policy_symbols_empty() {
+    let config = BndmConfig::with_policy(b"a?c", &WildcardPolicy::Symbols(Vec::new()));
+
+    assert_eq!(find_pattern(b"abc a?c", &config), Some(4));
+}
@@ -0,0 +1,246 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! A reusable pool of worker threads for scanning large sources in parallel.
+
+use std::ops::Range;
+use std::sync::{Arc, Mutex, mpsc};
+use std::thread::{self, JoinHandle};
+
+use crate::{BndmConfig, find_overlapping_indexes_unlogged, log_match};
+
+type Task = Box<dyn FnOnce() + Send>;
+
+/// The `ScanPool` struct owns a set of worker threads that can be reused for many scan jobs.
+///
+/// Every job splits its source into one part per worker thread and scans the parts in parallel.
+/// Dropping the pool, or calling `ScanPool::shutdown`, lets the workers finish all submitted
+/// jobs before the threads are joined.
+pub struct ScanPool {
+    sender: Option<mpsc::Sender<Task>>,
+    workers: Vec<JoinHandle<()>>
+}
+
+/// The `ScanJob` struct is a handle to a scan job that has been submitted to a `ScanPool`.
+///
+/// The job is an iterator over its parts, which yields the result of every part as soon as
+/// the part has been scanned, in the order in which the parts complete.
+pub struct ScanJob {
+    receiver: mpsc::Receiver<ScanPart>,
+    parts: usize,
+    received: usize
+}
+
+/// The `ScanPart` struct holds the result of scanning one part of the source of a `ScanJob`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScanPart {
+    /// The number of the part, counting the parts from the start of the source.
+    pub part: usize,
+
+    /// The range of the source in which the occurrences of this part start.
+    pub range: Range<usize>,
+
+    /// The indexes of the occurrences that start in the range, in ascending order.
+    pub indexes: Vec<usize>
+}
+
+impl ScanPool {
+    /// Creates a new `ScanPool` instance with the given number of worker threads.
+    ///
+    /// # Arguments
+    ///
+    /// * `threads` - The number of worker threads; a number of zero is treated as one.
+    ///
+    /// # Returns
+    ///
+    /// * `ScanPool` - A new `ScanPool` instance.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use bndm::{BndmConfig, ScanPool};
+    ///
+    /// let pool = ScanPool::new(4);
+    /// let source: Arc<[u8]> = Arc::from(&b"The quick brown fox jumps over the lazy dog"[..]);
+    /// let config = Arc::new(BndmConfig::new(b"o", None));
+    ///
+    /// let mut indexes = pool.submit(source, config).wait();
+    /// indexes.sort_unstable();
+    /// assert_eq!(indexes, vec![12, 17, 26, 41]);
+    ///
+    /// pool.shutdown();
+    /// ```
+    pub fn new(threads: usize) -> ScanPool {
+        let (sender, receiver) = mpsc::channel::<Task>();
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        let workers = (0..threads.max(1)).map(|_| {
+            let receiver = Arc::clone(&receiver);
+            thread::spawn(move || loop {
+                let task = receiver.lock().map_err(|_| ()).and_then(|receiver| receiver.recv().map_err(|_| ()));
+                match task {
+                    Ok(task) => task(),
+                    Err(_) => break
+                }
+            })
+        }).collect();
+
+        ScanPool { sender: Some(sender), workers }
+    }
+
+    /// Returns the number of worker threads of the pool.
+    pub fn threads(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Submits a job that searches for all occurrences of the pattern in the source string.
+    ///
+    /// Occurrences may overlap, and occurrences that cross the boundary between two parts are
+    /// found as well. The indexes are delivered per part in the order in which the parts
+    /// complete, either one part at a time by iterating over the job, or all at once with
+    /// `ScanJob::wait`, unless the result is retrieved with `ScanJob::wait_ordered`.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source string to search for the pattern.
+    /// * `config` - The configuration for the BNDM search, which includes the pattern and the
+    ///   bitmasks.
+    ///
+    /// # Returns
+    ///
+    /// * `ScanJob` - A handle to wait for the result of the job.
+    pub fn submit<S: AsRef<[u8]> + Send + Sync + ?Sized + 'static>(&self, source: Arc<S>, config: Arc<BndmConfig>) -> ScanJob {
+        let source_len = (*source).as_ref().len();
+        let part_len = source_len.div_ceil(self.threads()).max(1);
+        let (sender, receiver) = mpsc::channel();
+        let mut parts = 0;
+
+        for (part, start) in (0..source_len).step_by(part_len).enumerate() {
+            let source = Arc::clone(&source);
+            let config = Arc::clone(&config);
+            let sender = sender.clone();
+
+            self.execute(Box::new(move || {
+                let source = (*source).as_ref();
+                let end = (start + part_len).min(source.len());
+                let scan_end = (end + config.pattern.len().saturating_sub(1)).min(source.len());
+                let indexes = find_overlapping_indexes_unlogged(&source[start..scan_end], &config)
+                    .map(|index| start + index)
+                    .take_while(|&index| index < end)
+                    .inspect(|&index| log_match(&config, index))
+                    .collect();
+                let _ = sender.send(ScanPart { part, range: start..end, indexes });
+            }));
+            parts += 1;
+        }
+
+        ScanJob { receiver, parts, received: 0 }
+    }
+
+    /// Shuts down the pool after all submitted jobs have been completed.
+    pub fn shutdown(self) {
+        drop(self);
+    }
+
+    fn execute(&self, task: Task) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(task);
+        }
+    }
+}
+
+impl Drop for ScanPool {
+    fn drop(&mut self) {
+        drop(self.sender.take());
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl ScanJob {
+    /// Waits for the job to complete and returns the indexes of all occurrences.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<usize>` - Returns the indexes of all occurrences. The indexes of every part are
+    ///   in ascending order, but the parts are in the order in which they completed.
+    pub fn wait(self) -> Vec<usize> {
+        self.flat_map(|part| part.indexes).collect()
+    }
+
+    /// Waits for the job to complete and returns the indexes of all occurrences in ascending
+    /// order, regardless of the order in which the parts completed.
+    ///
+    /// The parts cover consecutive ranges of the source, so the result is assembled by
+    /// concatenating the parts in the order of the source, without sorting.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<usize>` - Returns the indexes of all occurrences in ascending order.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use bndm::{BndmConfig, ScanPool};
+    ///
+    /// let pool = ScanPool::new(4);
+    /// let source: Arc<[u8]> = Arc::from(&b"The quick brown fox jumps over the lazy dog"[..]);
+    /// let config = Arc::new(BndmConfig::new(b"o", None));
+    /// assert_eq!(pool.submit(source, config).wait_ordered(), vec![12, 17, 26, 41]);
+    /// ```
+    pub fn wait_ordered(self) -> Vec<usize> {
+        let mut parts = vec![Vec::new(); self.parts];
+        for part in self {
+            parts[part.part] = part.indexes;
+        }
+        parts.concat()
+    }
+
+    /// Returns the number of parts the source of the job has been split into.
+    pub fn parts(&self) -> usize {
+        self.parts
+    }
+}
+
+impl Iterator for ScanJob {
+    type Item = ScanPart;
+
+    /// Waits for the next part to complete and returns its result, or `None` when all parts
+    /// have been delivered.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use bndm::{BndmConfig, ScanPool};
+    ///
+    /// let pool = ScanPool::new(2);
+    /// let source: Arc<[u8]> = Arc::from(&b"The quick brown fox jumps over the lazy dog"[..]);
+    /// let config = Arc::new(BndmConfig::new(b"o", None));
+    ///
+    /// let mut parts: Vec<_> = pool.submit(source, config).collect();
+    /// parts.sort_by_key(|part| part.part);
+    /// assert_eq!(parts[0].range, 0..22);
+    /// assert_eq!(parts[0].indexes, vec![12, 17]);
+    /// assert_eq!(parts[1].indexes, vec![26, 41]);
+    /// ```
+    fn next(&mut self) -> Option<ScanPart> {
+        if self.received == self.parts {
+            return None;
+        }
+        let part = self.receiver.recv().ok()?;
+        self.received += 1;
+        Some(part)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.parts - self.received))
+    }
+}
+
+#[cfg(test)]
+#[path = "./pool_test.rs"]
+mod pool_test;
@@ -0,0 +1,132 @@
+use super::*;
+
+const SOURCE: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua";
+
+fn scan(pool: &ScanPool, source: &[u8], pattern: &[u8], wildcard: Option<u8>) -> Vec<usize> {
+    let source: Arc<[u8]> = Arc::from(source);
+    let config = Arc::new(BndmConfig::new(pattern, wildcard));
+    let mut indexes = pool.submit(source, config).wait();
+    indexes.sort_unstable();
+    indexes
+}
+
+#[test]
+fn submit_finds_all_occurrences() {
+    let pool = ScanPool::new(4);
+
+    assert_eq!(scan(&pool, SOURCE, b"or", None), vec![1, 15, 76, 96, 106]);
+}
+
+#[test]
+fn submit_occurrence_across_parts() {
+    let pool = ScanPool::new(8);
+
+    assert_eq!(scan(&pool, SOURCE, b"consectetur adipiscing elit", None), vec![28]);
+}
+
+#[test]
+fn submit_overlapping_occurrences() {
+    let pool = ScanPool::new(3);
+
+    assert_eq!(scan(&pool, b"aaaaaa", b"aa", None), vec![0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn submit_with_wildcard() {
+    let pool = ScanPool::new(2);
+
+    assert_eq!(scan(&pool, SOURCE, b"d?lor", Some(b'?')), vec![12, 103]);
+}
+
+#[test]
+fn submit_more_threads_than_bytes() {
+    let pool = ScanPool::new(16);
+
+    assert_eq!(scan(&pool, b"abc", b"bc", None), vec![1]);
+}
+
+#[test]
+fn submit_empty_source() {
+    let pool = ScanPool::new(2);
+
+    assert_eq!(scan(&pool, b"", b"abc", None), vec![]);
+}
+
+#[test]
+fn submit_multiple_jobs() {
+    let pool = ScanPool::new(2);
+    let source: Arc<Vec<u8>> = Arc::new(SOURCE.to_vec());
+    let jobs: Vec<ScanJob> = [&b"dolor"[..], b"elit", b"magna"].iter()
+        .map(|pattern| pool.submit(Arc::clone(&source), Arc::new(BndmConfig::new(pattern, None))))
+        .collect();
+    let results: Vec<Vec<usize>> = jobs.into_iter().map(|job| {
+        let mut indexes = job.wait();
+        indexes.sort_unstable();
+        indexes
+    }).collect();
+
+    assert_eq!(results, vec![vec![12, 103], vec![51], vec![110]]);
+}
+
+#[test]
+fn new_with_zero_threads() {
+    let pool = ScanPool::new(0);
+
+    assert_eq!(pool.threads(), 1);
+    assert_eq!(scan(&pool, SOURCE, b"sit", None), vec![18]);
+    pool.shutdown();
+}
+
+#[test]
+fn wait_ordered_ascending_indexes() {
+    let pool = ScanPool::new(7);
+    let source: Arc<[u8]> = Arc::from(SOURCE);
+    let config = Arc::new(BndmConfig::new(b"?", Some(b'?')));
+
+    assert_eq!(pool.submit(source, config).wait_ordered(), (0..SOURCE.len()).collect::<Vec<_>>());
+}
+
+#[test]
+fn wait_ordered_occurrence_across_parts() {
+    let pool = ScanPool::new(5);
+    let source: Arc<[u8]> = Arc::from(SOURCE);
+    let config = Arc::new(BndmConfig::new(b"o?", Some(b'?')));
+
+    assert_eq!(pool.submit(source, config).wait_ordered(), vec![1, 13, 15, 29, 62, 69, 76, 96, 104, 106]);
+}
+
+#[test]
+fn wait_ordered_empty_source() {
+    let pool = ScanPool::new(2);
+    let source: Arc<[u8]> = Arc::from(&b""[..]);
+    let config = Arc::new(BndmConfig::new(b"abc", None));
+
+    assert_eq!(pool.submit(source, config).wait_ordered(), vec![]);
+}
+
+#[test]
+fn parts_delivered_as_completed() {
+    let pool = ScanPool::new(3);
+    let source: Arc<[u8]> = Arc::from(SOURCE);
+    let config = Arc::new(BndmConfig::new(b"o?", Some(b'?')));
+    let job = pool.submit(source, config);
+    assert_eq!(job.parts(), 3);
+
+    let mut parts: Vec<ScanPart> = job.collect();
+    parts.sort_by_key(|part| part.part);
+
+    assert_eq!(parts.iter().map(|part| part.range.clone()).collect::<Vec<_>>(), vec![0..41, 41..82, 82..SOURCE.len()]);
+    assert!(parts.iter().all(|part| part.indexes.iter().all(|index| part.range.contains(index))));
+    assert_eq!(parts.into_iter().flat_map(|part| part.indexes).collect::<Vec<_>>(), vec![1, 13, 15, 29, 62, 69, 76, 96, 104, 106]);
+}
+
+#[test]
+fn parts_of_empty_source() {
+    let pool = ScanPool::new(2);
+    let source: Arc<[u8]> = Arc::from(&b""[..]);
+    let config = Arc::new(BndmConfig::new(b"abc", None));
+    let mut job = pool.submit(source, config);
+
+    assert_eq!(job.parts(), 0);
+    assert_eq!(job.next(), None);
+}
@@ -0,0 +1,142 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! A gram filter for quickly rejecting chunks of a text that cannot contain any of a set of
+//! patterns.
+
+use std::ops::Range;
+
+use crate::BndmConfig;
+use crate::advisor::typical_byte_frequency;
+
+const BIGRAM_TABLE_SIZE: usize = 1 << 16;
+
+/// The `ChunkFilter` struct stores a selective gram of every pattern in a set of patterns.
+///
+/// For every pattern, the literal 2-byte gram with the least common bytes is selected, or the
+/// least common literal byte if the pattern has no literal 2-byte gram. A chunk that does not
+/// contain any of the selected grams cannot contain any of the patterns, so it can be skipped
+/// before running the full search. This is most effective when scanning many chunks with a low
+/// hit rate.
+pub struct ChunkFilter {
+    bigrams: Box<[u64; BIGRAM_TABLE_SIZE / 64]>,
+    bytes: [u64; 4],
+    accepts_all: bool,
+    max_pattern_len: usize
+}
+
+impl ChunkFilter {
+    /// Creates a new `ChunkFilter` instance for a set of patterns.
+    ///
+    /// # Arguments
+    ///
+    /// * `configs` - The configurations of the patterns.
+    ///
+    /// # Returns
+    ///
+    /// * `ChunkFilter` - A new `ChunkFilter` instance. If a pattern consists of wildcards only,
+    ///   the filter accepts all chunks.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{BndmConfig, ChunkFilter};
+    ///
+    /// let config_fox = BndmConfig::new(b"fox", None);
+    /// let config_cat = BndmConfig::new(b"c?t", Some(b'?'));
+    /// let filter = ChunkFilter::new(&[&config_fox, &config_cat]);
+    /// assert!(filter.may_contain(b"The quick brown fox"));
+    /// assert!(filter.may_contain(b"The cut"));
+    /// assert!(!filter.may_contain(b"jumps over a lazy dog"));
+    /// ```
+    pub fn new(configs: &[&BndmConfig]) -> ChunkFilter {
+        let mut filter = ChunkFilter {
+            bigrams: Box::new([0; BIGRAM_TABLE_SIZE / 64]),
+            bytes: [0; 4],
+            accepts_all: false,
+            max_pattern_len: configs.iter().map(|config| config.pattern.len()).max().unwrap_or(0)
+        };
+
+        for config in configs.iter().filter(|config| !config.pattern.is_empty()) {
+            let is_literal = |position: usize| config.is_literal_at(position);
+            let bigram = config.pattern.windows(2).enumerate()
+                .filter(|&(position, _)| is_literal(position) && is_literal(position + 1))
+                .map(|(_, gram)| gram)
+                .max_by(|first, second| bigram_frequency(second).total_cmp(&bigram_frequency(first)));
+
+            match bigram {
+                Some(gram) => set_bit(&mut filter.bigrams[..], bigram_key(gram[0], gram[1])),
+                None => match config.pattern.iter().enumerate()
+                    .filter(|&(position, _)| is_literal(position))
+                    .map(|(_, byte)| byte)
+                    .max_by(|&&first, &&second| typical_byte_frequency(second).total_cmp(&typical_byte_frequency(first))) {
+                    Some(&byte) => set_bit(&mut filter.bytes, byte as usize),
+                    None => filter.accepts_all = true
+                }
+            }
+        }
+        filter
+    }
+
+    /// Checks whether a chunk may contain any of the patterns.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunk` - The chunk of the text to check.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Returns `false` if the chunk cannot contain any of the patterns, `true`
+    ///   otherwise.
+    pub fn may_contain(&self, chunk: &[u8]) -> bool {
+        self.accepts_all
+            || chunk.iter().any(|&byte| is_bit_set(&self.bytes, byte as usize))
+            || chunk.windows(2).any(|gram| is_bit_set(&self.bigrams[..], bigram_key(gram[0], gram[1])))
+    }
+
+    /// Splits the source string into chunks and returns the ranges of the chunks that may
+    /// contain any of the patterns.
+    ///
+    /// Every chunk is extended with the length of the longest pattern minus one, so occurrences
+    /// that cross the boundary between two chunks are not missed. The ranges can be passed to
+    /// `find_pattern_in_ranges` or `find_all_in_ranges`.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source string to split into chunks.
+    /// * `chunk_size` - The size of the chunks; a size of zero is treated as one.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<Range<usize>>` - Returns the ranges of the chunks that may contain any of the
+    ///   patterns.
+    pub fn candidate_chunks(&self, source: &[u8], chunk_size: usize) -> Vec<Range<usize>> {
+        let chunk_size = chunk_size.max(1);
+        let overlap = self.max_pattern_len.saturating_sub(1);
+
+        (0..source.len()).step_by(chunk_size)
+            .map(|start| start..(start + chunk_size + overlap).min(source.len()))
+            .filter(|range| self.may_contain(&source[range.clone()]))
+            .collect()
+    }
+}
+
+fn bigram_frequency(gram: &[u8]) -> f64 {
+    typical_byte_frequency(gram[0]) * typical_byte_frequency(gram[1])
+}
+
+fn bigram_key(first: u8, second: u8) -> usize {
+    ((first as usize) << 8) | second as usize
+}
+
+fn set_bit(bits: &mut [u64], index: usize) {
+    bits[index / 64] |= 1 << (index % 64);
+}
+
+fn is_bit_set(bits: &[u64], index: usize) -> bool {
+    bits[index / 64] & (1 << (index % 64)) != 0
+}
+
+#[cfg(test)]
+#[path = "./prefilter_test.rs"]
+mod prefilter_test;
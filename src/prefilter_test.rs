@@ -0,0 +1,62 @@
+use super::*;
+use crate::find_pattern_in_ranges;
+
+#[test]
+fn may_contain_literal_pattern() {
+    let config = BndmConfig::new(b"jumps", None);
+    let filter = ChunkFilter::new(&[&config]);
+
+    assert!(filter.may_contain(b"The quick brown fox jumps"));
+    assert!(!filter.may_contain(b"The quick brown fox"));
+}
+
+#[test]
+fn may_contain_wildcard_between_every_byte() {
+    let config = BndmConfig::new(b"a?B?c", Some(b'?'));
+    let filter = ChunkFilter::new(&[&config]);
+
+    assert!(filter.may_contain(b"xxBxx"));
+    assert!(!filter.may_contain(b"xxxxx"));
+}
+
+#[test]
+fn may_contain_wildcards_only() {
+    let config = BndmConfig::new(b"???", Some(b'?'));
+    let filter = ChunkFilter::new(&[&config]);
+
+    assert!(filter.may_contain(b""));
+}
+
+#[test]
+fn may_contain_no_patterns() {
+    let filter = ChunkFilter::new(&[]);
+
+    assert!(!filter.may_contain(b"The quick brown fox jumps over the lazy dog"));
+}
+
+#[test]
+fn may_contain_selects_rare_gram() {
+    let config = BndmConfig::new(b"\x00\x00\x4d\x5a\x00\x00", None);
+    let filter = ChunkFilter::new(&[&config]);
+
+    assert!(!filter.may_contain(&[0; 64]));
+}
+
+#[test]
+fn candidate_chunks_with_overlap() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let config = BndmConfig::new(b"fox", None);
+    let filter = ChunkFilter::new(&[&config]);
+    let chunks = filter.candidate_chunks(source, 8);
+
+    assert_eq!(chunks, vec![16..26]);
+    assert_eq!(find_pattern_in_ranges(source, &config, &chunks), Some(16));
+}
+
+#[test]
+fn candidate_chunks_empty_source() {
+    let config = BndmConfig::new(b"fox", None);
+    let filter = ChunkFilter::new(&[&config]);
+
+    assert_eq!(filter.candidate_chunks(b"", 8), vec![]);
+}
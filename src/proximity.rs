@@ -0,0 +1,190 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Proximity search for patterns that occur near each other in a text.
+
+use std::ops::Range;
+
+use crate::{BndmConfig, find_overlapping_indexes, find_pattern_from};
+
+/// A match of a sequence of patterns.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SequenceMatch {
+    /// The range in the text from the start of the first pattern to the end of the last pattern.
+    pub span: Range<usize>,
+
+    /// The start indexes of the individual patterns, in the order of the sequence.
+    pub offsets: Vec<usize>
+}
+
+/// Searches for occurrences of two patterns that lie within a maximum distance of each other,
+/// in either order.
+///
+/// The distance is measured between the start indexes of both occurrences. Every pair of
+/// occurrences within the distance is reported, ordered by the index of the first pattern.
+///
+/// # Arguments
+///
+/// * `source` - The source string to search for the patterns.
+/// * `config_a` - The configuration for the first pattern.
+/// * `config_b` - The configuration for the second pattern.
+/// * `max_distance` - The maximum distance between the start indexes of both occurrences.
+///
+/// # Returns
+///
+/// * `Vec<(usize, usize)>` - Returns the pairs of indexes of the first and the second pattern
+///   that lie within the maximum distance.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{BndmConfig, find_near};
+///
+/// let source = b"The quick brown fox jumps over the lazy dog";
+/// let config_a = BndmConfig::new(b"dog", None);
+/// let config_b = BndmConfig::new(b"the", None);
+/// let pairs = find_near(source, &config_a, &config_b, 10);
+/// assert_eq!(pairs, vec![(40, 31)]);
+/// ```
+pub fn find_near(source: &[u8], config_a: &BndmConfig, config_b: &BndmConfig, max_distance: usize) -> Vec<(usize, usize)> {
+    collect_pairs(source, config_a, config_b, |index_a, index_b| index_a.abs_diff(index_b) <= max_distance,
+        |index_a| index_a.saturating_sub(max_distance))
+}
+
+/// Searches for occurrences of two patterns where the second pattern starts at or after the
+/// first pattern, within a maximum distance.
+///
+/// The distance is measured between the start indexes of both occurrences. Every pair of
+/// occurrences within the distance is reported, ordered by the index of the first pattern.
+///
+/// # Arguments
+///
+/// * `source` - The source string to search for the patterns.
+/// * `config_a` - The configuration for the pattern that should occur first.
+/// * `config_b` - The configuration for the pattern that should follow.
+/// * `max_distance` - The maximum distance between the start indexes of both occurrences.
+///
+/// # Returns
+///
+/// * `Vec<(usize, usize)>` - Returns the pairs of indexes of the first and the second pattern
+///   that lie within the maximum distance.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{BndmConfig, find_near_ordered};
+///
+/// let source = b"The quick brown fox jumps over the lazy dog";
+/// let config_a = BndmConfig::new(b"fox", None);
+/// let config_b = BndmConfig::new(b"o", None);
+/// let pairs = find_near_ordered(source, &config_a, &config_b, 12);
+/// assert_eq!(pairs, vec![(16, 17), (16, 26)]);
+/// ```
+pub fn find_near_ordered(source: &[u8], config_a: &BndmConfig, config_b: &BndmConfig, max_distance: usize) -> Vec<(usize, usize)> {
+    collect_pairs(source, config_a, config_b, |index_a, index_b| index_b >= index_a && index_b - index_a <= max_distance,
+        |index_a| index_a)
+}
+
+/// Searches for places where a list of patterns occur in order.
+///
+/// Each pattern in the sequence should start at or after the end of the previous pattern. For
+/// every occurrence of the first pattern, the earliest occurrences of the following patterns
+/// are taken, which results in the shortest possible span for that occurrence. Sequences do not
+/// overlap; searching continues after the end of the last reported span.
+///
+/// # Arguments
+///
+/// * `source` - The source string to search for the patterns.
+/// * `configs` - The configurations of the patterns, in the order they should occur.
+/// * `max_span` - An optional maximum length of the span from the start of the first pattern
+///   to the end of the last pattern. If `None`, the gaps between the patterns are unbounded.
+///
+/// # Returns
+///
+/// * `Vec<SequenceMatch>` - Returns the spans and the individual indexes of the sequences found.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{BndmConfig, SequenceMatch, find_sequence};
+///
+/// let source = b"The quick brown fox jumps over the lazy dog";
+/// let config_fox = BndmConfig::new(b"fox", None);
+/// let config_over = BndmConfig::new(b"over", None);
+/// let config_dog = BndmConfig::new(b"dog", None);
+/// let matches = find_sequence(source, &[&config_fox, &config_over, &config_dog], Some(30));
+/// assert_eq!(matches, vec![SequenceMatch { span: 16..43, offsets: vec![16, 26, 40] }]);
+/// ```
+pub fn find_sequence(source: &[u8], configs: &[&BndmConfig], max_span: Option<usize>) -> Vec<SequenceMatch> {
+    let mut matches = Vec::new();
+    let Some((first_config, next_configs)) = configs.split_first() else {
+        return matches;
+    };
+    if max_span.is_some_and(|max_span| first_config.pattern.len() > max_span) {
+        return matches;
+    }
+
+    let mut start_index = 0;
+    while let Some(first_index) = find_pattern_from(source, first_config, start_index) {
+        let limit = max_span.map_or(source.len(), |max_span| first_index.saturating_add(max_span).min(source.len()));
+        match match_sequence(source, first_index, first_config, next_configs, limit) {
+            Ok(sequence) => {
+                start_index = sequence.span.end;
+                matches.push(sequence);
+            }
+            Err(Some(min_end)) => {
+                start_index = (first_index + 1).max(min_end.saturating_sub(max_span.unwrap_or(0)));
+            }
+            Err(None) => break
+        }
+    }
+    matches
+}
+
+// Takes the earliest occurrences of the following patterns that end at or before the limit.
+// If a pattern does not occur within the limit, the error holds the lowest end that a sequence
+// starting at a later occurrence of the first pattern can have, or `None` if the pattern does
+// not occur in the rest of the source at all. Later occurrences of the first pattern never
+// find earlier occurrences of the following patterns, which makes this bound hold for them.
+fn match_sequence(source: &[u8], first_index: usize, first_config: &BndmConfig, next_configs: &[&BndmConfig],
+                  limit: usize) -> Result<SequenceMatch, Option<usize>> {
+    let mut offsets = vec![first_index];
+    let mut end = first_index + first_config.pattern.len();
+
+    for (position, config) in next_configs.iter().enumerate() {
+        match find_pattern_from(&source[..limit], config, end) {
+            Some(index) => {
+                offsets.push(index);
+                end = index + config.pattern.len();
+            }
+            None => {
+                let unscanned = end.max((limit + 1).saturating_sub(config.pattern.len()));
+                let index = find_pattern_from(source, config, unscanned).ok_or(None)?;
+                let remaining_len: usize = next_configs[position..].iter().map(|config| config.pattern.len()).sum();
+                return Err(Some(index + remaining_len));
+            }
+        }
+    }
+    Ok(SequenceMatch { span: first_index..end, offsets })
+}
+
+fn collect_pairs(source: &[u8], config_a: &BndmConfig, config_b: &BndmConfig,
+                 is_near: impl Fn(usize, usize) -> bool, window_start: impl Fn(usize) -> usize) -> Vec<(usize, usize)> {
+    let indexes_b: Vec<usize> = find_overlapping_indexes(source, config_b).collect();
+    let mut pairs = Vec::new();
+    let mut first = 0;
+
+    for index_a in find_overlapping_indexes(source, config_a) {
+        while first < indexes_b.len() && indexes_b[first] < window_start(index_a) {
+            first += 1;
+        }
+        pairs.extend(indexes_b[first..].iter()
+            .take_while(|&&index_b| is_near(index_a, index_b))
+            .map(|&index_b| (index_a, index_b)));
+    }
+    pairs
+}
+
+#[cfg(test)]
+#[path = "./proximity_test.rs"]
+mod proximity_test;
@@ -0,0 +1,165 @@
+use super::*;
+
+#[test]
+fn find_near_either_order() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let config_a = BndmConfig::new(b"dog", None);
+    let config_b = BndmConfig::new(b"the", None);
+    let pairs = find_near(source, &config_a, &config_b, 10);
+
+    assert_eq!(pairs, vec![(40, 31)]);
+}
+
+#[test]
+fn find_near_out_of_distance() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let config_a = BndmConfig::new(b"dog", None);
+    let config_b = BndmConfig::new(b"the", None);
+    let pairs = find_near(source, &config_a, &config_b, 8);
+
+    assert_eq!(pairs, vec![]);
+}
+
+#[test]
+fn find_near_multiple_pairs() {
+    let source = b"abc--xyz--abc-----------xyz";
+    let config_a = BndmConfig::new(b"abc", None);
+    let config_b = BndmConfig::new(b"xyz", None);
+    let pairs = find_near(source, &config_a, &config_b, 5);
+
+    assert_eq!(pairs, vec![(0, 5), (10, 5)]);
+}
+
+#[test]
+fn find_near_with_wildcard() {
+    let source = b"\x7fELF....................GNU";
+    let config_a = BndmConfig::new(b"\x7fEL?", Some(b'?'));
+    let config_b = BndmConfig::new(b"GNU", None);
+    let pairs = find_near(source, &config_a, &config_b, 24);
+
+    assert_eq!(pairs, vec![(0, 24)]);
+}
+
+#[test]
+fn find_near_ordered_only_after() {
+    let source = b"abc--xyz--abc-----------xyz";
+    let config_a = BndmConfig::new(b"abc", None);
+    let config_b = BndmConfig::new(b"xyz", None);
+    let pairs = find_near_ordered(source, &config_a, &config_b, 5);
+
+    assert_eq!(pairs, vec![(0, 5)]);
+}
+
+#[test]
+fn find_near_ordered_overlapping_occurrences() {
+    let source = b"aaaa";
+    let config_a = BndmConfig::new(b"aa", None);
+    let config_b = BndmConfig::new(b"a", None);
+    let pairs = find_near_ordered(source, &config_a, &config_b, 1);
+
+    assert_eq!(pairs, vec![(0, 0), (0, 1), (1, 1), (1, 2), (2, 2), (2, 3)]);
+}
+
+#[test]
+fn find_near_no_match() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let config_a = BndmConfig::new(b"cat", None);
+    let config_b = BndmConfig::new(b"the", None);
+    let pairs = find_near(source, &config_a, &config_b, 100);
+
+    assert_eq!(pairs, vec![]);
+}
+
+#[test]
+fn find_sequence_in_order() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let config_fox = BndmConfig::new(b"fox", None);
+    let config_over = BndmConfig::new(b"over", None);
+    let config_dog = BndmConfig::new(b"dog", None);
+    let matches = find_sequence(source, &[&config_fox, &config_over, &config_dog], None);
+
+    assert_eq!(matches, vec![SequenceMatch { span: 16..43, offsets: vec![16, 26, 40] }]);
+}
+
+#[test]
+fn find_sequence_wrong_order() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let config_fox = BndmConfig::new(b"fox", None);
+    let config_dog = BndmConfig::new(b"dog", None);
+    let matches = find_sequence(source, &[&config_dog, &config_fox], None);
+
+    assert_eq!(matches, vec![]);
+}
+
+#[test]
+fn find_sequence_exceeding_span() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let config_fox = BndmConfig::new(b"fox", None);
+    let config_dog = BndmConfig::new(b"dog", None);
+    let matches = find_sequence(source, &[&config_fox, &config_dog], Some(26));
+
+    assert_eq!(matches, vec![]);
+}
+
+#[test]
+fn find_sequence_multiple_with_span() {
+    let source = b"AB..C.....A..B.C..A...........B.C";
+    let config_a = BndmConfig::new(b"A", None);
+    let config_b = BndmConfig::new(b"B", None);
+    let config_c = BndmConfig::new(b"C", None);
+    let matches = find_sequence(source, &[&config_a, &config_b, &config_c], Some(6));
+
+    assert_eq!(matches, vec![
+        SequenceMatch { span: 0..5, offsets: vec![0, 1, 4] },
+        SequenceMatch { span: 10..16, offsets: vec![10, 13, 15] }
+    ]);
+}
+
+#[test]
+fn find_sequence_patterns_do_not_overlap() {
+    let source = b"abcd";
+    let config_abc = BndmConfig::new(b"abc", None);
+    let config_cd = BndmConfig::new(b"cd", None);
+    let matches = find_sequence(source, &[&config_abc, &config_cd], None);
+
+    assert_eq!(matches, vec![]);
+}
+
+#[test]
+fn find_sequence_with_wildcard() {
+    let source = b"MZ\x90\x00....PE\x00\x00";
+    let config_mz = BndmConfig::new(b"MZ??", Some(b'?'));
+    let config_pe = BndmConfig::new(b"PE\x00\x00", None);
+    let matches = find_sequence(source, &[&config_mz, &config_pe], Some(64));
+
+    assert_eq!(matches, vec![SequenceMatch { span: 0..12, offsets: vec![0, 8] }]);
+}
+
+#[test]
+fn find_sequence_no_patterns() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let matches = find_sequence(source, &[], None);
+
+    assert_eq!(matches, vec![]);
+}
+
+#[test]
+fn find_sequence_skips_first_occurrences_out_of_span() {
+    let source = b"AAAAAAAAAA......B.......AB";
+    let config_a = BndmConfig::new(b"A", None);
+    let config_b = BndmConfig::new(b"B", None);
+    let matches = find_sequence(source, &[&config_a, &config_b], Some(3));
+
+    assert_eq!(matches, vec![SequenceMatch { span: 24..26, offsets: vec![24, 25] }]);
+}
+
+#[test]
+fn find_sequence_first_pattern_longer_than_span() {
+    let source = b"abcd";
+    let config_abc = BndmConfig::new(b"abc", None);
+    let config_d = BndmConfig::new(b"d", None);
+
+    assert_eq!(find_sequence(source, &[&config_abc, &config_d], Some(2)), vec![]);
+    assert_eq!(find_sequence(source, &[&config_abc, &config_d], Some(4)),
+        vec![SequenceMatch { span: 0..4, offsets: vec![0, 3] }]);
+}
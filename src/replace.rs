@@ -0,0 +1,85 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Replacement of the occurrences of a pattern.
+
+use crate::{BndmConfig, find_indexes};
+
+/// Replaces all occurrences of the pattern in the source string with the replacement.
+///
+/// Occurrences do not overlap; after an occurrence is replaced, searching continues at the end
+/// of the occurrence in the source string, so the replacement is never searched. The
+/// replacement may be shorter or longer than the pattern.
+///
+/// # Arguments
+///
+/// * `source` - The source string to search for the pattern.
+/// * `config` - The configuration for the BNDM search, which includes the pattern and the
+///   bitmasks.
+/// * `replacement` - The bytes that replace every occurrence.
+///
+/// # Returns
+///
+/// * `Vec<u8>` - Returns a copy of the source string in which all occurrences are replaced.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{BndmConfig, replace_all};
+///
+/// let source = b"The quick brown fox jumps over the lazy dog";
+/// let config = BndmConfig::new(b"?he", Some(b'?'));
+/// assert_eq!(replace_all(source, &config, b"a"), b"a quick brown fox jumps over a lazy dog");
+/// ```
+pub fn replace_all(source: &[u8], config: &BndmConfig, replacement: &[u8]) -> Vec<u8> {
+    replace_with(source, config, |_| replacement)
+}
+
+/// Replaces all occurrences of the pattern in the source string with the bytes returned by the
+/// closure.
+///
+/// The closure is called with the bytes of every occurrence, including the bytes matched by
+/// wildcards, so the replacement can depend on the occurrence. Returning the occurrence itself
+/// leaves it unchanged. Occurrences do not overlap, as with `replace_all`.
+///
+/// # Arguments
+///
+/// * `source` - The source string to search for the pattern.
+/// * `config` - The configuration for the BNDM search, which includes the pattern and the
+///   bitmasks.
+/// * `replacement` - The closure that is called with the bytes of every occurrence, in
+///   ascending order, and returns the bytes that replace it.
+///
+/// # Returns
+///
+/// * `Vec<u8>` - Returns a copy of the source string in which all occurrences are replaced.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{BndmConfig, replace_with};
+///
+/// // Replaces "LDA #$nn" by "LDA #$00" for values below $10 only.
+/// let source = b"\xa9\x05\xa9\x20\xa9\x0f";
+/// let config = BndmConfig::new(b"\xa9?", Some(b'?'));
+/// let patched = replace_with(source, &config, |found| {
+///     if found[1] < 0x10 { vec![0xa9, 0x00] } else { found.to_vec() }
+/// });
+/// assert_eq!(patched, b"\xa9\x00\xa9\x20\xa9\x00");
+/// ```
+pub fn replace_with<R: AsRef<[u8]>>(source: &[u8], config: &BndmConfig, mut replacement: impl FnMut(&[u8]) -> R) -> Vec<u8> {
+    let mut result = Vec::with_capacity(source.len());
+    let mut end = 0;
+
+    for index in find_indexes(source, config) {
+        result.extend_from_slice(&source[end..index]);
+        end = index + config.pattern.len();
+        result.extend_from_slice(replacement(&source[index..end]).as_ref());
+    }
+    result.extend_from_slice(&source[end..]);
+    result
+}
+
+#[cfg(test)]
+#[path = "./replace_test.rs"]
+mod replace_test;
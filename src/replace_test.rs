@@ -0,0 +1,116 @@
+use super::*;
+
+#[test]
+fn replace_all_no_match() {
+    let source = b"The quick brown fox";
+    let config = BndmConfig::new(b"cat", None);
+
+    assert_eq!(replace_all(source, &config, b"dog"), source);
+}
+
+#[test]
+fn replace_all_same_length() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let config = BndmConfig::new(b"o", None);
+
+    assert_eq!(replace_all(source, &config, b"0"), b"The quick br0wn f0x jumps 0ver the lazy d0g");
+}
+
+#[test]
+fn replace_all_shorter_replacement() {
+    let source = b"a--b--c";
+    let config = BndmConfig::new(b"--", None);
+
+    assert_eq!(replace_all(source, &config, b""), b"abc");
+}
+
+#[test]
+fn replace_all_longer_replacement() {
+    let source = b"a-b-c";
+    let config = BndmConfig::new(b"-", None);
+
+    assert_eq!(replace_all(source, &config, b" - "), b"a - b - c");
+}
+
+#[test]
+fn replace_all_wildcard() {
+    let source = b"\xa9\x01\x8d\x20\xd0\xa9\x02\x8d\x21\xd0";
+    let config = BndmConfig::new(b"\xa9?\x8d", Some(b'?'));
+
+    assert_eq!(replace_all(source, &config, b"\xea\xea\xea"), b"\xea\xea\xea\x20\xd0\xea\xea\xea\x21\xd0");
+}
+
+#[test]
+fn replace_all_non_overlapping() {
+    let source = b"aaaaa";
+    let config = BndmConfig::new(b"aa", None);
+
+    assert_eq!(replace_all(source, &config, b"b"), b"bba");
+}
+
+#[test]
+fn replace_all_replacement_not_searched() {
+    let source = b"ab";
+    let config = BndmConfig::new(b"a", None);
+
+    assert_eq!(replace_all(source, &config, b"aa"), b"aab");
+}
+
+#[test]
+fn replace_all_match_at_boundaries() {
+    let source = b"xyzxy";
+    let config = BndmConfig::new(b"?y", Some(b'?'));
+
+    assert_eq!(replace_all(source, &config, b"_"), b"_z_");
+}
+
+#[test]
+fn replace_all_empty_pattern() {
+    let source = b"abc";
+    let config = BndmConfig::new(b"", None);
+
+    assert_eq!(replace_all(source, &config, b"x"), b"abc");
+}
+
+#[test]
+fn replace_with_receives_matched_bytes() {
+    let source = b"a1b2c3";
+    let config = BndmConfig::new(b"??", Some(b'?'));
+    let mut found = Vec::new();
+    replace_with(source, &config, |bytes| {
+        found.push(bytes.to_vec());
+        b"".as_slice()
+    });
+
+    assert_eq!(found, vec![b"a1".to_vec(), b"b2".to_vec(), b"c3".to_vec()]);
+}
+
+#[test]
+fn replace_with_context_dependent() {
+    let source = b"x=1;x=7;x=3";
+    let config = BndmConfig::new(b"x=?", Some(b'?'));
+    let patched = replace_with(source, &config, |bytes| if bytes[2] > b'5' { b"x=5".to_vec() } else { bytes.to_vec() });
+
+    assert_eq!(patched, b"x=1;x=5;x=3");
+}
+
+#[test]
+fn replace_with_different_lengths() {
+    let source = b"<a><bb><c>";
+    let config = BndmConfig::new(b"<?>", Some(b'?'));
+    let patched = replace_with(source, &config, |bytes| bytes[1].to_string());
+
+    assert_eq!(patched, b"97<bb>99");
+}
+
+#[test]
+fn replace_with_long_pattern() {
+    let mut pattern = vec![b'a'; 70];
+    pattern[69] = b'?';
+    let mut source = vec![b'a'; 69];
+    source.push(b'!');
+    source.extend_from_slice(b"tail");
+    let config = BndmConfig::new(&pattern, Some(b'?'));
+
+    assert_eq!(replace_with(&source, &config, |bytes| [bytes[69]]), b"!tail");
+}
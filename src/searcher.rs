@@ -0,0 +1,108 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! An object-safe searcher interface, for swapping pattern engines at runtime.
+
+use crate::{BndmConfig, find_pattern};
+
+/// The `DynSearcher` trait is an object-safe interface to a pattern engine.
+///
+/// Applications can store any engine as a `Box<dyn DynSearcher + Send + Sync>` and search
+/// slices or streams of chunks through the same interface.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{BndmConfig, DynSearcher, StreamState};
+///
+/// let searcher: Box<dyn DynSearcher + Send + Sync> = Box::new(BndmConfig::new(b"fox", None));
+/// assert_eq!(searcher.find(b"The quick brown fox"), Some(16));
+///
+/// let mut state = StreamState::new();
+/// assert_eq!(searcher.feed(&mut state, b"The quick brown f"), vec![]);
+/// assert_eq!(searcher.feed(&mut state, b"ox jumps"), vec![16]);
+/// ```
+pub trait DynSearcher {
+    /// Returns the length of the pattern.
+    fn pattern_len(&self) -> usize;
+
+    /// Returns the index of the first occurrence of the pattern in the source string, or
+    /// `None` if the pattern is not found.
+    fn find(&self, source: &[u8]) -> Option<usize>;
+
+    /// Returns an iterator over the indexes of all occurrences of the pattern in the source
+    /// string in ascending order. Occurrences do not overlap.
+    fn find_iter<'a>(&'a self, source: &'a [u8]) -> Box<dyn Iterator<Item = usize> + 'a>;
+
+    /// Searches the next chunk of a stream and returns the indexes, relative to the start of
+    /// the stream, of all occurrences that end in the chunk. Occurrences that cross the
+    /// boundary between chunks are found as well, and the occurrences of all chunks together
+    /// are the same as the occurrences in the whole stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The state of the stream, which must only be used with this searcher.
+    /// * `chunk` - The next chunk of the stream.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<usize>` - Returns the indexes of the occurrences in ascending order.
+    fn feed(&self, state: &mut StreamState, chunk: &[u8]) -> Vec<usize> {
+        let mut window = std::mem::take(&mut state.tail);
+        window.extend_from_slice(chunk);
+
+        let start = (state.resume - state.offset).min(window.len());
+        let matches: Vec<usize> = self.find_iter(&window[start..]).map(|index| state.offset + start + index).collect();
+        if let Some(&last) = matches.last() {
+            state.resume = last + self.pattern_len();
+        }
+
+        let tail_len = self.pattern_len().saturating_sub(1).min(window.len());
+        state.offset += window.len() - tail_len;
+        state.resume = state.resume.max(state.offset);
+        window.drain(..window.len() - tail_len);
+        state.tail = window;
+
+        matches
+    }
+}
+
+/// The `StreamState` struct holds the position in a stream, the bytes at the end of the
+/// previous chunk that may be the start of an occurrence, and the index from which the next
+/// occurrence may start.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StreamState {
+    tail: Vec<u8>,
+    offset: usize,
+    resume: usize
+}
+
+impl StreamState {
+    /// Creates a new `StreamState` instance for the start of a stream.
+    pub fn new() -> StreamState {
+        StreamState::default()
+    }
+
+    /// Returns the number of bytes of the stream that have been fed.
+    pub fn position(&self) -> usize {
+        self.offset + self.tail.len()
+    }
+}
+
+impl DynSearcher for BndmConfig {
+    fn pattern_len(&self) -> usize {
+        self.pattern.len()
+    }
+
+    fn find(&self, source: &[u8]) -> Option<usize> {
+        find_pattern(source, self)
+    }
+
+    fn find_iter<'a>(&'a self, source: &'a [u8]) -> Box<dyn Iterator<Item = usize> + 'a> {
+        Box::new(BndmConfig::find_iter(self, source))
+    }
+}
+
+#[cfg(test)]
+#[path = "./searcher_test.rs"]
+mod searcher_test;
@@ -0,0 +1,76 @@
+use super::*;
+
+fn boxed(pattern: &[u8], wildcard: Option<u8>) -> Box<dyn DynSearcher + Send + Sync> {
+    Box::new(BndmConfig::new(pattern, wildcard))
+}
+
+#[test]
+fn find_through_trait_object() {
+    let searcher = boxed(b"ju??s", Some(b'?'));
+
+    assert_eq!(searcher.pattern_len(), 5);
+    assert_eq!(searcher.find(b"The quick brown fox jumps over the lazy dog"), Some(20));
+    assert_eq!(searcher.find(b"The quick brown fox"), None);
+}
+
+#[test]
+fn find_iter_through_trait_object() {
+    let searcher = boxed(b"aa", None);
+
+    assert_eq!(searcher.find_iter(b"aaabaa").collect::<Vec<_>>(), vec![0, 4]);
+}
+
+#[test]
+fn feed_across_chunks() {
+    let searcher = boxed(b"abcd", None);
+    let mut state = StreamState::new();
+
+    assert_eq!(searcher.feed(&mut state, b"xxab"), vec![]);
+    assert_eq!(searcher.feed(&mut state, b"c"), vec![]);
+    assert_eq!(searcher.feed(&mut state, b"dabcdab"), vec![2, 6]);
+    assert_eq!(searcher.feed(&mut state, b""), vec![]);
+    assert_eq!(searcher.feed(&mut state, b"cd"), vec![10]);
+    assert_eq!(state.position(), 14);
+}
+
+#[test]
+fn feed_single_char_pattern() {
+    let searcher = boxed(b"a", None);
+    let mut state = StreamState::new();
+
+    assert_eq!(searcher.feed(&mut state, b"ab"), vec![0]);
+    assert_eq!(searcher.feed(&mut state, b"ba"), vec![3]);
+}
+
+#[test]
+fn swap_searchers_at_runtime() {
+    let searchers = [boxed(b"fox", None), boxed(b"d?g", Some(b'?'))];
+    let source = b"The quick brown fox jumps over the lazy dog";
+
+    assert_eq!(searchers.iter().map(|searcher| searcher.find(source)).collect::<Vec<_>>(), vec![Some(16), Some(40)]);
+}
+
+#[test]
+fn feed_non_overlapping_across_chunks() {
+    let searcher = boxed(b"aa", None);
+    let mut state = StreamState::new();
+
+    assert_eq!(searcher.feed(&mut state, b"aaa"), vec![0]);
+    assert_eq!(searcher.feed(&mut state, b"a"), vec![2]);
+    assert_eq!(searcher.feed(&mut state, b"a"), vec![]);
+    assert_eq!(searcher.feed(&mut state, b"aa"), vec![4]);
+}
+
+#[test]
+fn feed_equals_whole_stream() {
+    let source = b"abababababcabababab";
+    let searcher = boxed(b"?bab", Some(b'?'));
+    let expected: Vec<usize> = searcher.find_iter(source).collect();
+
+    for chunk_size in 1..source.len() {
+        let mut state = StreamState::new();
+        let found: Vec<usize> = source.chunks(chunk_size).flat_map(|chunk| searcher.feed(&mut state, chunk)).collect();
+
+        assert_eq!(found, expected, "chunk size {chunk_size}");
+    }
+}
@@ -0,0 +1,71 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Serialization of configurations with serde, so that compiled bitmasks can be stored and
+//! loaded without compiling the pattern again.
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::masks::validate_view;
+use crate::{BndmConfig, ByteClass, Equivalence, MASKS_TABLE_SIZE, WORD_SIZE_IN_BITS};
+
+/// The serialized form of a `BndmConfig`. The word size is stored, as the bitmasks depend on
+/// it and can't be used on a target with a different word size.
+#[derive(Serialize, Deserialize)]
+struct SerializedConfig {
+    word_size: usize,
+    masks: Vec<usize>,
+    wildcard: Option<u8>,
+    pattern: Vec<u8>,
+    equivalence: Option<Vec<u8>>,
+    classes: Option<Vec<[u64; 4]>>
+}
+
+impl Serialize for BndmConfig {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializedConfig {
+            word_size: WORD_SIZE_IN_BITS,
+            masks: self.masks.to_vec(),
+            wildcard: self.wildcard,
+            pattern: self.pattern.clone(),
+            equivalence: self.equivalence.as_ref().map(|equivalence| (0..=u8::MAX).map(|byte| equivalence.fold(byte)).collect()),
+            classes: self.classes.as_ref().map(|classes| classes.iter().map(ByteClass::bits).collect())
+        }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for BndmConfig {
+    /// Deserializes a `BndmConfig` without compiling the pattern again.
+    ///
+    /// Configurations that were serialized on a target with a different word size are
+    /// rejected, as are bitmasks and equivalences that do not have an entry for every byte,
+    /// classes that do not have an entry for every position of the pattern, and bitmasks that
+    /// do not fit the pattern.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<BndmConfig, D::Error> {
+        let config = SerializedConfig::deserialize(deserializer)?;
+        if config.word_size != WORD_SIZE_IN_BITS {
+            return Err(D::Error::custom(format_args!("bitmasks of word size {} can't be used with word size {}",
+                config.word_size, WORD_SIZE_IN_BITS)));
+        }
+
+        let masks = <Box<[usize; MASKS_TABLE_SIZE]>>::try_from(config.masks)
+            .map_err(|masks: Vec<usize>| D::Error::invalid_length(masks.len(), &"a bitmask for every byte"))?;
+        let equivalence = config.equivalence.map(|classes| classes.try_into()
+            .map(Equivalence::from_classes)
+            .map_err(|classes: Vec<u8>| D::Error::invalid_length(classes.len(), &"a class for every byte")))
+            .transpose()?;
+        let classes = config.classes.map(|classes| match classes.len() == config.pattern.len() {
+            true => Ok(classes.into_iter().map(ByteClass::from_bits).collect()),
+            false => Err(D::Error::invalid_length(classes.len(), &"a class for every position of the pattern"))
+        }).transpose()?;
+
+        let config = BndmConfig { masks: masks.into(), wildcard: config.wildcard, pattern: config.pattern, equivalence, classes };
+        validate_view(&config.view()).map_err(|error| D::Error::custom(format_args!("invalid bitmasks: {error}")))?;
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+#[path = "./serialize_test.rs"]
+mod serialize_test;
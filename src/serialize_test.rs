@@ -0,0 +1,66 @@
+use super::*;
+use crate::find_pattern;
+
+fn round_trip(config: &BndmConfig) -> BndmConfig {
+    bincode::deserialize(&bincode::serialize(config).unwrap()).unwrap()
+}
+
+fn serialized(config: &BndmConfig) -> SerializedConfig {
+    bincode::deserialize(&bincode::serialize(config).unwrap()).unwrap()
+}
+
+fn deserialize(config: &SerializedConfig) -> Result<BndmConfig, bincode::Error> {
+    bincode::deserialize(&bincode::serialize(config).unwrap())
+}
+
+#[test]
+fn round_trip_configs() {
+    let configs = [
+        BndmConfig::new(b"jumps", None),
+        BndmConfig::new(b"ju??s", Some(b'?')),
+        BndmConfig::new_nocase(b"JUMPS", None),
+        BndmConfig::with_classes(&[ByteClass::byte(b'j'), ByteClass::range(b'a'..=b'z')]),
+        BndmConfig::new(&[b'x'; 70], None)
+    ];
+
+    for config in configs {
+        assert_eq!(round_trip(&config), config);
+    }
+    assert_eq!(find_pattern(b"The quick brown fox JUMPS", &round_trip(&BndmConfig::new_nocase(b"jumps", None))), Some(20));
+}
+
+#[test]
+fn reject_other_word_size() {
+    let mut config = serialized(&BndmConfig::new(b"fox", None));
+    config.word_size = WORD_SIZE_IN_BITS / 2;
+
+    let error = deserialize(&config).err().unwrap();
+    assert!(error.to_string().starts_with("bitmasks of word size"));
+}
+
+#[test]
+fn reject_invalid_lengths() {
+    let mut config = serialized(&BndmConfig::new_nocase(b"fox", None));
+    config.masks.pop();
+    assert!(deserialize(&config).is_err());
+
+    let mut config = serialized(&BndmConfig::new_nocase(b"fox", None));
+    config.equivalence.as_mut().unwrap().pop();
+    assert!(deserialize(&config).is_err());
+
+    let mut config = serialized(&BndmConfig::with_classes(&[ByteClass::any(); 2]));
+    config.classes.as_mut().unwrap().pop();
+    assert!(deserialize(&config).is_err());
+}
+
+#[test]
+fn reject_invalid_masks() {
+    let mut config = serialized(&BndmConfig::new(b"ab", None));
+    config.masks.iter_mut().for_each(|mask| *mask = !2);
+    let error = deserialize(&config).err().unwrap();
+    assert!(error.to_string().starts_with("invalid bitmasks"));
+
+    let mut config = serialized(&BndmConfig::new(b"ab", None));
+    config.pattern = b"ac".to_vec();
+    assert!(deserialize(&config).is_err());
+}
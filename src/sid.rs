@@ -0,0 +1,218 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Helpers for locating PSID/RSID files in memory dumps and mapping file offsets to C64
+//! addresses.
+
+use std::ops::Range;
+
+use crate::{BndmConfig, find_overlapping_indexes_unlogged, log_match};
+
+const HEADER_SIZE_V1: usize = 0x76;
+const HEADER_SIZE_V2: usize = 0x7c;
+
+/// The type of a SID file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SidType {
+    /// A PSID file, which can be played by most SID players.
+    Psid,
+
+    /// An RSID file, which requires a real C64 environment to be played.
+    Rsid
+}
+
+/// The header of a PSID or RSID file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SidHeader {
+    /// The index of the header in the source.
+    pub offset: usize,
+
+    /// The type of the SID file.
+    pub sid_type: SidType,
+
+    /// The version of the header, from 1 to 4.
+    pub version: u16,
+
+    /// The C64 address at which the data is loaded. If the header specifies a load address of
+    /// zero, this is the load address stored in the first two bytes of the data.
+    pub load_address: u16,
+
+    /// The C64 address of the init routine.
+    pub init_address: u16,
+
+    /// The C64 address of the play routine.
+    pub play_address: u16,
+
+    /// The number of songs.
+    pub songs: u16,
+
+    /// The song that is played by default, starting at 1.
+    pub start_song: u16,
+
+    /// The name of the tune, without trailing zero bytes.
+    pub name: Vec<u8>,
+
+    /// The author of the tune, without trailing zero bytes.
+    pub author: Vec<u8>,
+
+    /// The release information of the tune, without trailing zero bytes.
+    pub released: Vec<u8>,
+
+    /// The range of the C64 data in the source, excluding an embedded load address.
+    pub data: Range<usize>
+}
+
+/// A match inside the C64 data of a SID file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SidMatch {
+    /// The index of the match in the source.
+    pub file_offset: usize,
+
+    /// The C64 address of the match.
+    pub address: u16
+}
+
+impl SidHeader {
+    /// Returns the C64 address of an index in the source, or `None` if the index lies outside
+    /// the C64 data.
+    pub fn address_of(&self, file_offset: usize) -> Option<u16> {
+        self.data.contains(&file_offset)
+            .then(|| self.load_address as usize + file_offset - self.data.start)
+            .and_then(|address| u16::try_from(address).ok())
+    }
+
+    /// Returns the index in the source of a C64 address, or `None` if the address lies outside
+    /// the C64 data.
+    pub fn file_offset_of(&self, address: u16) -> Option<usize> {
+        (address >= self.load_address)
+            .then(|| self.data.start + (address - self.load_address) as usize)
+            .filter(|file_offset| self.data.contains(file_offset))
+    }
+
+    /// Searches for all occurrences of the pattern, e.g. a player signature, in the C64 data.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source that contains the SID file.
+    /// * `config` - The configuration for the BNDM search, which includes the pattern and the
+    ///   bitmasks.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<SidMatch>` - Returns the index in the source and the C64 address of every
+    ///   occurrence. Occurrences may overlap.
+    pub fn find_pattern(&self, source: &[u8], config: &BndmConfig) -> Vec<SidMatch> {
+        let data = &source[self.data.start.min(source.len())..self.data.end.min(source.len())];
+
+        find_overlapping_indexes_unlogged(data, config)
+            .filter_map(|index| {
+                let file_offset = self.data.start + index;
+                self.address_of(file_offset).map(|address| SidMatch { file_offset, address })
+            })
+            .inspect(|sid_match| log_match(config, sid_match.file_offset))
+            .collect()
+    }
+}
+
+/// Searches for PSID and RSID headers in the source, e.g. in a memory dump or a disk image.
+///
+/// Only headers with a valid version, data offset and song count are reported. The C64 data of
+/// a SID file is assumed to extend to the end of the source, or up to the next SID header.
+///
+/// # Arguments
+///
+/// * `source` - The source to search for SID headers.
+///
+/// # Returns
+///
+/// * `Vec<SidHeader>` - Returns the headers ordered by their offset.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{BndmConfig, find_sid_headers};
+///
+/// let mut source = b"PSID\x00\x02\x00\x7c\x10\x00\x10\x00\x10\x03\x00\x01\x00\x01".to_vec();
+/// source.resize(0x7c, 0);
+/// source.extend_from_slice(&[0x4c, 0x00, 0x10, 0xa9, 0x0f, 0x8d, 0x18, 0xd4]);
+///
+/// let headers = find_sid_headers(&source);
+/// assert_eq!(headers[0].load_address, 0x1000);
+///
+/// let config = BndmConfig::new(b"\x8d\x18\xd4", None);
+/// let matches = headers[0].find_pattern(&source, &config);
+/// assert_eq!(matches[0].file_offset, 0x81);
+/// assert_eq!(matches[0].address, 0x1005);
+/// ```
+pub fn find_sid_headers(source: &[u8]) -> Vec<SidHeader> {
+    let config = BndmConfig::new(b"?SID", Some(b'?'));
+    let mut headers: Vec<SidHeader> = find_overlapping_indexes_unlogged(source, &config)
+        .filter_map(|offset| parse_header(source, offset))
+        .inspect(|header| log_match(&config, header.offset))
+        .collect();
+
+    for index in 1..headers.len() {
+        let next_offset = headers[index].offset;
+        let previous = &mut headers[index - 1];
+        previous.data.end = previous.data.end.min(next_offset).max(previous.data.start);
+    }
+    headers
+}
+
+fn parse_header(source: &[u8], offset: usize) -> Option<SidHeader> {
+    let sid_type = match source.get(offset) {
+        Some(b'P') => SidType::Psid,
+        Some(b'R') => SidType::Rsid,
+        _ => return None
+    };
+    let header = source.get(offset..offset + HEADER_SIZE_V1)?;
+    let read_u16 = |index: usize| u16::from_be_bytes([header[index], header[index + 1]]);
+
+    let version = read_u16(4);
+    let data_offset = read_u16(6) as usize;
+    let songs = read_u16(14);
+    let start_song = read_u16(16);
+    let is_valid = match version {
+        1 => sid_type == SidType::Psid && data_offset == HEADER_SIZE_V1,
+        2..=4 => data_offset == HEADER_SIZE_V2,
+        _ => false
+    };
+    if !is_valid || !(1..=256).contains(&songs) || start_song == 0 || start_song > songs {
+        return None;
+    }
+
+    let mut data_start = offset + data_offset;
+    let mut load_address = read_u16(8);
+    if load_address == 0 {
+        let bytes = source.get(data_start..data_start + 2)?;
+        load_address = u16::from_le_bytes([bytes[0], bytes[1]]);
+        data_start += 2;
+    }
+    if data_start > source.len() {
+        return None;
+    }
+
+    Some(SidHeader {
+        offset,
+        sid_type,
+        version,
+        load_address,
+        init_address: read_u16(10),
+        play_address: read_u16(12),
+        songs,
+        start_song,
+        name: read_string(&header[0x16..0x36]),
+        author: read_string(&header[0x36..0x56]),
+        released: read_string(&header[0x56..0x76]),
+        data: data_start..source.len()
+    })
+}
+
+fn read_string(field: &[u8]) -> Vec<u8> {
+    let len = field.iter().position(|&byte| byte == 0).unwrap_or(field.len());
+    field[..len].to_vec()
+}
+
+#[cfg(test)]
+#[path = "./sid_test.rs"]
+mod sid_test;
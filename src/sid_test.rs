@@ -0,0 +1,108 @@
+use super::*;
+
+fn sid_file(magic: &[u8], version: u16, load_address: u16, data: &[u8]) -> Vec<u8> {
+    let data_offset = if version == 1 { HEADER_SIZE_V1 } else { HEADER_SIZE_V2 };
+    let mut file = magic.to_vec();
+    file.extend_from_slice(&version.to_be_bytes());
+    file.extend_from_slice(&(data_offset as u16).to_be_bytes());
+    file.extend_from_slice(&load_address.to_be_bytes());
+    file.extend_from_slice(&0x1000u16.to_be_bytes());
+    file.extend_from_slice(&0x1003u16.to_be_bytes());
+    file.extend_from_slice(&3u16.to_be_bytes());
+    file.extend_from_slice(&1u16.to_be_bytes());
+    file.extend_from_slice(&[0; 4]);
+    file.resize(0x16, 0);
+    file.extend_from_slice(b"Commando");
+    file.resize(0x36, 0);
+    file.extend_from_slice(b"Rob Hubbard");
+    file.resize(0x56, 0);
+    file.extend_from_slice(b"1985 Elite");
+    file.resize(data_offset, 0);
+    file.extend_from_slice(data);
+    file
+}
+
+#[test]
+fn find_psid_header() {
+    let source = sid_file(b"PSID", 2, 0x1000, &[0x4c, 0x00, 0x10]);
+    let headers = find_sid_headers(&source);
+
+    assert_eq!(headers.len(), 1);
+    assert_eq!(headers[0].sid_type, SidType::Psid);
+    assert_eq!(headers[0].load_address, 0x1000);
+    assert_eq!(headers[0].init_address, 0x1000);
+    assert_eq!(headers[0].play_address, 0x1003);
+    assert_eq!(headers[0].songs, 3);
+    assert_eq!(headers[0].name, b"Commando");
+    assert_eq!(headers[0].author, b"Rob Hubbard");
+    assert_eq!(headers[0].released, b"1985 Elite");
+    assert_eq!(headers[0].data, 0x7c..0x7f);
+}
+
+#[test]
+fn find_rsid_header_with_embedded_load_address() {
+    let mut source = vec![0xea; 10];
+    source.extend(sid_file(b"RSID", 3, 0, &[0x00, 0x20, 0x78, 0x60]));
+    let headers = find_sid_headers(&source);
+
+    assert_eq!(headers[0].offset, 10);
+    assert_eq!(headers[0].sid_type, SidType::Rsid);
+    assert_eq!(headers[0].load_address, 0x2000);
+    assert_eq!(headers[0].data, 10 + 0x7e..10 + 0x80);
+}
+
+#[test]
+fn find_psid_v1_header() {
+    let source = sid_file(b"PSID", 1, 0xc000, &[0x60]);
+    let headers = find_sid_headers(&source);
+
+    assert_eq!(headers[0].version, 1);
+    assert_eq!(headers[0].data, 0x76..0x77);
+}
+
+#[test]
+fn find_invalid_headers() {
+    let mut source = b"PSID is not a header".to_vec();
+    source.extend(sid_file(b"RSID", 1, 0x1000, &[0x60]));
+    source.extend(sid_file(b"PSID", 5, 0x1000, &[0x60]));
+    let headers = find_sid_headers(&source);
+
+    assert_eq!(headers, vec![]);
+}
+
+#[test]
+fn find_multiple_headers_limit_data() {
+    let mut source = sid_file(b"PSID", 2, 0x1000, &[0x60, 0x60]);
+    let second_offset = source.len();
+    source.extend(sid_file(b"PSID", 2, 0x2000, &[0x60]));
+    let headers = find_sid_headers(&source);
+
+    assert_eq!(headers.len(), 2);
+    assert_eq!(headers[0].data, 0x7c..second_offset);
+    assert_eq!(headers[1].data, second_offset + 0x7c..source.len());
+}
+
+#[test]
+fn map_addresses() {
+    let source = sid_file(b"PSID", 2, 0x1000, &[0x4c, 0x00, 0x10]);
+    let headers = find_sid_headers(&source);
+
+    assert_eq!(headers[0].address_of(0x7d), Some(0x1001));
+    assert_eq!(headers[0].address_of(0x10), None);
+    assert_eq!(headers[0].file_offset_of(0x1002), Some(0x7e));
+    assert_eq!(headers[0].file_offset_of(0x1003), None);
+    assert_eq!(headers[0].file_offset_of(0x0fff), None);
+}
+
+#[test]
+fn find_player_signature() {
+    let source = sid_file(b"PSID", 2, 0x0ff6, &[0xa9, 0x0f, 0x8d, 0x18, 0xd4, 0x60, 0x8d, 0x18, 0xd4]);
+    let headers = find_sid_headers(&source);
+    let config = BndmConfig::new(b"\x8d?\xd4", Some(b'?'));
+    let matches = headers[0].find_pattern(&source, &config);
+
+    assert_eq!(matches, vec![
+        SidMatch { file_offset: 0x7e, address: 0x0ff8 },
+        SidMatch { file_offset: 0x82, address: 0x0ffc }
+    ]);
+}
@@ -0,0 +1,141 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Splitting of a source string on the occurrences of a pattern.
+
+use std::iter::FusedIterator;
+
+use crate::{BndmConfig, find_pattern_from};
+
+/// The `Split` struct is an iterator over the parts of a source string between the occurrences
+/// of a pattern.
+#[derive(Clone)]
+pub struct Split<'a> {
+    source: &'a [u8],
+    config: &'a BndmConfig,
+    position: usize,
+    finished: bool
+}
+
+impl<'a> Split<'a> {
+    fn remainder(&mut self) -> Option<&'a [u8]> {
+        if self.finished {
+            return None;
+        }
+        self.finished = true;
+        Some(&self.source[self.position..])
+    }
+}
+
+impl<'a> Iterator for Split<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<&'a [u8]> {
+        if self.finished {
+            return None;
+        }
+        match find_pattern_from(self.source, self.config, self.position) {
+            Some(index) => {
+                let part = &self.source[self.position..index];
+                self.position = index + self.config.pattern.len();
+                Some(part)
+            }
+            None => self.remainder()
+        }
+    }
+}
+
+impl FusedIterator for Split<'_> {}
+
+/// The `SplitN` struct is an iterator over at most a given number of parts of a source string
+/// between the occurrences of a pattern.
+#[derive(Clone)]
+pub struct SplitN<'a> {
+    split: Split<'a>,
+    count: usize
+}
+
+impl<'a> Iterator for SplitN<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<&'a [u8]> {
+        match self.count {
+            0 => None,
+            1 => {
+                self.count = 0;
+                self.split.remainder()
+            }
+            _ => {
+                self.count -= 1;
+                self.split.next()
+            }
+        }
+    }
+}
+
+impl FusedIterator for SplitN<'_> {}
+
+/// Splits the source string on the occurrences of the pattern.
+///
+/// The parts between the occurrences are yielded in order, like `str::split`. Occurrences do
+/// not overlap, and an occurrence at the start or the end of the source results in an empty
+/// part, so there is always one more part than there are occurrences.
+///
+/// # Arguments
+///
+/// * `source` - The source string to split.
+/// * `config` - The configuration for the BNDM search, which includes the pattern and the
+///   bitmasks.
+///
+/// # Returns
+///
+/// * `Split` - An iterator that yields the parts of the source string.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{BndmConfig, split};
+///
+/// let source = b"name\x1e\x01age\x1e\x02city";
+/// let config = BndmConfig::new(b"\x1e?", Some(b'?'));
+/// let parts: Vec<&[u8]> = split(source, &config).collect();
+/// assert_eq!(parts, vec![&b"name"[..], b"age", b"city"]);
+/// ```
+pub fn split<'a>(source: &'a [u8], config: &'a BndmConfig) -> Split<'a> {
+    Split { source, config, position: 0, finished: false }
+}
+
+/// Splits the source string on the occurrences of the pattern into at most the given number of
+/// parts.
+///
+/// Splitting stops after the given number of parts minus one, and the remainder of the source
+/// string is yielded as the last part, like `str::splitn`.
+///
+/// # Arguments
+///
+/// * `source` - The source string to split.
+/// * `config` - The configuration for the BNDM search, which includes the pattern and the
+///   bitmasks.
+/// * `n` - The maximum number of parts; no parts are yielded if it is zero.
+///
+/// # Returns
+///
+/// * `SplitN` - An iterator that yields the parts of the source string.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{BndmConfig, splitn};
+///
+/// let source = b"key=value=with=separators";
+/// let config = BndmConfig::new(b"=", None);
+/// let parts: Vec<&[u8]> = splitn(source, &config, 2).collect();
+/// assert_eq!(parts, vec![&b"key"[..], b"value=with=separators"]);
+/// ```
+pub fn splitn<'a>(source: &'a [u8], config: &'a BndmConfig, n: usize) -> SplitN<'a> {
+    SplitN { split: split(source, config), count: n }
+}
+
+#[cfg(test)]
+#[path = "./split_test.rs"]
+mod split_test;
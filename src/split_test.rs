@@ -0,0 +1,104 @@
+use super::*;
+
+fn parts<'a>(source: &'a [u8], config: &'a BndmConfig) -> Vec<&'a [u8]> {
+    split(source, config).collect()
+}
+
+#[test]
+fn split_no_match() {
+    let config = BndmConfig::new(b",", None);
+
+    assert_eq!(parts(b"abc", &config), vec![&b"abc"[..]]);
+}
+
+#[test]
+fn split_multiple_matches() {
+    let config = BndmConfig::new(b", ", None);
+
+    assert_eq!(parts(b"a, b, c", &config), vec![&b"a"[..], b"b", b"c"]);
+}
+
+#[test]
+fn split_match_at_boundaries() {
+    let config = BndmConfig::new(b"--", None);
+
+    assert_eq!(parts(b"--a----b--", &config), vec![&b""[..], b"a", b"", b"b", b""]);
+}
+
+#[test]
+fn split_wildcard_delimiter() {
+    let config = BndmConfig::new(b"\xff?\xff", Some(b'?'));
+
+    assert_eq!(parts(b"rec1\xff\x01\xffrec2\xff\x02\xffrec3", &config), vec![&b"rec1"[..], b"rec2", b"rec3"]);
+}
+
+#[test]
+fn split_non_overlapping() {
+    let config = BndmConfig::new(b"aa", None);
+
+    assert_eq!(parts(b"xaaay", &config), vec![&b"x"[..], b"ay"]);
+}
+
+#[test]
+fn split_empty_source() {
+    let config = BndmConfig::new(b",", None);
+
+    assert_eq!(parts(b"", &config), vec![&b""[..]]);
+}
+
+#[test]
+fn split_empty_pattern() {
+    let config = BndmConfig::new(b"", None);
+
+    assert_eq!(parts(b"abc", &config), vec![&b"abc"[..]]);
+}
+
+#[test]
+fn split_is_fused() {
+    let config = BndmConfig::new(b",", None);
+    let mut parts = split(b"a,b", &config);
+
+    assert_eq!(parts.next(), Some(&b"a"[..]));
+    assert_eq!(parts.next(), Some(&b"b"[..]));
+    assert_eq!(parts.next(), None);
+    assert_eq!(parts.next(), None);
+}
+
+#[test]
+fn splitn_limit() {
+    let config = BndmConfig::new(b"?,", Some(b'?'));
+    let parts: Vec<&[u8]> = splitn(b"a1,b2,c3,d", &config, 3).collect();
+
+    assert_eq!(parts, vec![&b"a"[..], b"b", b"c3,d"]);
+}
+
+#[test]
+fn splitn_zero() {
+    let config = BndmConfig::new(b",", None);
+
+    assert_eq!(splitn(b"a,b", &config, 0).count(), 0);
+}
+
+#[test]
+fn splitn_one() {
+    let config = BndmConfig::new(b",", None);
+    let parts: Vec<&[u8]> = splitn(b"a,b", &config, 1).collect();
+
+    assert_eq!(parts, vec![&b"a,b"[..]]);
+}
+
+#[test]
+fn splitn_fewer_matches_than_limit() {
+    let config = BndmConfig::new(b",", None);
+    let parts: Vec<&[u8]> = splitn(b"a,b", &config, 5).collect();
+
+    assert_eq!(parts, vec![&b"a"[..], b"b"]);
+}
+
+#[test]
+fn splitn_remainder_starts_after_match() {
+    let config = BndmConfig::new(b",", None);
+    let parts: Vec<&[u8]> = splitn(b"a,", &config, 2).collect();
+
+    assert_eq!(parts, vec![&b"a"[..], b""]);
+}
@@ -0,0 +1,153 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! A textual pattern language for configurations that come from configuration files and
+//! command line arguments, e.g. `GET /?[0-9]\x20`.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{BndmConfig, ByteClass, PatternBuilder};
+
+/// The error type for parsing patterns.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PatternSyntaxError {
+    /// The pattern has no bytes.
+    Empty,
+
+    /// The escape sequence at the given position is not valid.
+    InvalidEscape(usize),
+
+    /// The class at the given position is empty or has a range that ends before it starts.
+    InvalidClass(usize),
+
+    /// The pattern ended unexpectedly.
+    UnexpectedEnd
+}
+
+impl fmt::Display for PatternSyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PatternSyntaxError::Empty => write!(f, "pattern is empty"),
+            PatternSyntaxError::InvalidEscape(position) => write!(f, "invalid escape sequence at position {position}"),
+            PatternSyntaxError::InvalidClass(position) => write!(f, "invalid class at position {position}"),
+            PatternSyntaxError::UnexpectedEnd => write!(f, "unexpected end of pattern")
+        }
+    }
+}
+
+impl Error for PatternSyntaxError {}
+
+impl FromStr for BndmConfig {
+    type Err = PatternSyntaxError;
+
+    /// Parses a pattern in which every character matches itself, except for:
+    ///
+    /// * `?` - Matches any byte.
+    /// * `[...]` - Matches any byte of the class, e.g. `[a-z_]`, or any byte except the bytes
+    ///   of the class if it starts with `^`, e.g. `[^0-9]`.
+    /// * `\xNN` - Matches the byte of two hexadecimal digits.
+    /// * `\n`, `\r`, `\t` and `\0` - Match a newline, carriage return, tab and zero byte.
+    /// * `\` followed by any other character that is not a letter or digit - Matches that
+    ///   character, e.g. `\?`, `\[` and `\\`.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{BndmConfig, find_pattern};
+    ///
+    /// let config: BndmConfig = r"v[0-9].?\x00".parse().unwrap();
+    /// assert_eq!(find_pattern(b"vx.1\0 v2.0\0", &config), Some(6));
+    /// ```
+    fn from_str(pattern: &str) -> Result<BndmConfig, PatternSyntaxError> {
+        let bytes = pattern.as_bytes();
+        let mut builder = PatternBuilder::new();
+        let mut position = 0;
+
+        while position < bytes.len() {
+            let class = match bytes[position] {
+                b'?' => {
+                    position += 1;
+                    ByteClass::any()
+                }
+                b'[' => parse_class(bytes, &mut position)?,
+                _ => ByteClass::byte(parse_byte(bytes, &mut position)?)
+            };
+            builder = builder.class(class);
+        }
+
+        if builder.is_empty() {
+            return Err(PatternSyntaxError::Empty);
+        }
+        Ok(builder.build())
+    }
+}
+
+fn parse_class(bytes: &[u8], position: &mut usize) -> Result<ByteClass, PatternSyntaxError> {
+    let start = *position;
+    *position += 1;
+    let negated = bytes.get(*position) == Some(&b'^');
+    if negated {
+        *position += 1;
+    }
+
+    let mut class = ByteClass::empty();
+    loop {
+        match bytes.get(*position) {
+            None => return Err(PatternSyntaxError::UnexpectedEnd),
+            Some(b']') => break,
+            Some(_) => {
+                let first = parse_byte(bytes, position)?;
+                if bytes.get(*position) == Some(&b'-') && bytes.get(*position + 1).is_some_and(|&byte| byte != b']') {
+                    *position += 1;
+                    let last = parse_byte(bytes, position)?;
+                    if last < first {
+                        return Err(PatternSyntaxError::InvalidClass(start));
+                    }
+                    class = class.union(ByteClass::range(first..=last));
+                } else {
+                    class = class.with_byte(first);
+                }
+            }
+        }
+    }
+    *position += 1;
+
+    match (class.is_empty(), negated) {
+        (true, _) => Err(PatternSyntaxError::InvalidClass(start)),
+        (false, true) => Ok(class.complement()),
+        (false, false) => Ok(class)
+    }
+}
+
+fn parse_byte(bytes: &[u8], position: &mut usize) -> Result<u8, PatternSyntaxError> {
+    let start = *position;
+    if bytes[start] != b'\\' {
+        *position += 1;
+        return Ok(bytes[start]);
+    }
+
+    let escaped = *bytes.get(start + 1).ok_or(PatternSyntaxError::UnexpectedEnd)?;
+    *position += 2;
+    match escaped {
+        b'x' => {
+            let digits = bytes.get(start + 2..start + 4).ok_or(PatternSyntaxError::UnexpectedEnd)?;
+            *position += 2;
+            std::str::from_utf8(digits).ok()
+                .filter(|digits| digits.bytes().all(|digit| digit.is_ascii_hexdigit()))
+                .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+                .ok_or(PatternSyntaxError::InvalidEscape(start))
+        }
+        b'n' => Ok(b'\n'),
+        b'r' => Ok(b'\r'),
+        b't' => Ok(b'\t'),
+        b'0' => Ok(0),
+        _ if !escaped.is_ascii_alphanumeric() => Ok(escaped),
+        _ => Err(PatternSyntaxError::InvalidEscape(start))
+    }
+}
+
+#[cfg(test)]
+#[path = "./syntax_test.rs"]
+mod syntax_test;
@@ -0,0 +1,82 @@
+use super::*;
+use crate::find_pattern;
+
+fn parse(pattern: &str) -> BndmConfig {
+    pattern.parse().unwrap()
+}
+
+fn parse_error(pattern: &str) -> Option<PatternSyntaxError> {
+    pattern.parse::<BndmConfig>().err()
+}
+
+#[test]
+fn literal_pattern() {
+    let config = parse("fox");
+
+    assert!(config.is_pure_literal());
+    assert_eq!(find_pattern(b"The quick brown fox", &config), Some(16));
+}
+
+#[test]
+fn wildcards() {
+    let config = parse("j?mp?");
+
+    assert_eq!(config.wildcard_positions(), vec![1, 4]);
+    assert_eq!(find_pattern(b"The quick brown fox jumps", &config), Some(20));
+}
+
+#[test]
+fn escapes() {
+    let config = parse(r"\x4d\x5A\?\\\n\t\r\0");
+
+    assert_eq!(find_pattern(b"xMZ?\\\n\t\r\0", &config), Some(1));
+    assert_eq!(find_pattern(b"MZx\\\n\t\r\0", &config), None);
+}
+
+#[test]
+fn classes() {
+    let config = parse("[a-cx][^0-9][-_]");
+
+    assert_eq!(find_pattern(b"a1_ bx- c_-", &config), Some(4));
+    assert_eq!(find_pattern(b"d_-", &config), None);
+}
+
+#[test]
+fn escapes_in_classes() {
+    let config = parse(r"[\]\x00-\x02]");
+
+    assert_eq!(find_pattern(b"a\x03]", &config), Some(2));
+    assert_eq!(find_pattern(b"\x01", &config), Some(0));
+}
+
+#[test]
+fn utf8_literals() {
+    let config = parse("é?");
+
+    assert_eq!(find_pattern("caféx".as_bytes(), &config), Some(3));
+}
+
+#[test]
+fn long_pattern() {
+    let pattern = format!("{}[0-9]", "?".repeat(70));
+    let config = parse(&pattern);
+
+    let mut source = vec![b'x'; 100];
+    assert_eq!(find_pattern(&source, &config), None);
+    source[90] = b'7';
+    assert_eq!(find_pattern(&source, &config), Some(20));
+}
+
+#[test]
+fn invalid_patterns() {
+    assert_eq!(parse_error(""), Some(PatternSyntaxError::Empty));
+    assert_eq!(parse_error(r"ab\q"), Some(PatternSyntaxError::InvalidEscape(2)));
+    assert_eq!(parse_error(r"\xg0"), Some(PatternSyntaxError::InvalidEscape(0)));
+    assert_eq!(parse_error(r"\x+1"), Some(PatternSyntaxError::InvalidEscape(0)));
+    assert_eq!(parse_error(r"\x4"), Some(PatternSyntaxError::UnexpectedEnd));
+    assert_eq!(parse_error("ab\\"), Some(PatternSyntaxError::UnexpectedEnd));
+    assert_eq!(parse_error("a[bc"), Some(PatternSyntaxError::UnexpectedEnd));
+    assert_eq!(parse_error("a[]"), Some(PatternSyntaxError::InvalidClass(1)));
+    assert_eq!(parse_error("a[z-a]"), Some(PatternSyntaxError::InvalidClass(1)));
+    assert_eq!(PatternSyntaxError::InvalidClass(4).to_string(), "invalid class at position 4");
+}
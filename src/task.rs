@@ -0,0 +1,101 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! An interruptible search that performs a limited amount of work per call, for cooperative
+//! schedulers, game loops and GUI event loops.
+
+use std::task::Poll;
+
+use crate::{BndmConfig, find_overlapping_indexes_unlogged, log_match};
+
+/// The `ScanTask` struct searches a source string in steps of at most a given number of
+/// bytes.
+pub struct ScanTask<'a> {
+    source: &'a [u8],
+    config: &'a BndmConfig,
+    budget: usize,
+    position: usize,
+    matches: Vec<usize>
+}
+
+impl<'a> ScanTask<'a> {
+    /// Creates a new `ScanTask` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source string to search for the pattern.
+    /// * `config` - The configuration for the BNDM search, which includes the pattern and the
+    ///   bitmasks.
+    /// * `budget` - The maximum number of positions of the source to scan per call of
+    ///   `ScanTask::poll`; a budget of zero is treated as one.
+    ///
+    /// # Returns
+    ///
+    /// * `ScanTask` - A new `ScanTask` instance.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use std::task::Poll;
+    /// use bndm::{BndmConfig, ScanTask};
+    ///
+    /// let source = b"The quick brown fox jumps over the lazy dog";
+    /// let config = BndmConfig::new(b"o", None);
+    /// let mut task = ScanTask::new(source, &config, 16);
+    ///
+    /// assert_eq!(task.poll(), Poll::Pending);
+    /// assert_eq!(task.poll(), Poll::Pending);
+    /// assert_eq!(task.poll(), Poll::Ready(vec![12, 17, 26, 41]));
+    /// ```
+    pub fn new(source: &'a [u8], config: &'a BndmConfig, budget: usize) -> ScanTask<'a> {
+        ScanTask {
+            source,
+            config,
+            budget: budget.max(1),
+            position: 0,
+            matches: Vec::new()
+        }
+    }
+
+    /// Scans the next part of the source string.
+    ///
+    /// # Returns
+    ///
+    /// * `Poll<Vec<usize>>` - Returns `Poll::Pending` if a part of the source remains to be
+    ///   scanned, or `Poll::Ready` with the indexes of all occurrences in ascending order once
+    ///   the whole source is scanned. Occurrences may overlap. Polling again after the task is
+    ///   ready returns an empty list.
+    pub fn poll(&mut self) -> Poll<Vec<usize>> {
+        if !self.is_finished() {
+            let end = (self.position + self.budget).min(self.source.len());
+            let scan_end = (end + self.config.pattern.len().saturating_sub(1)).min(self.source.len());
+            let start = self.position;
+
+            self.matches.extend(find_overlapping_indexes_unlogged(&self.source[start..scan_end], self.config)
+                .map(|index| start + index)
+                .take_while(|&index| index < end)
+                .inspect(|&index| log_match(self.config, index)));
+            self.position = end;
+        }
+
+        if self.is_finished() {
+            Poll::Ready(std::mem::take(&mut self.matches))
+        } else {
+            Poll::Pending
+        }
+    }
+
+    /// Returns the number of positions of the source string that have been scanned.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns whether the whole source string has been scanned.
+    pub fn is_finished(&self) -> bool {
+        self.position >= self.source.len()
+    }
+}
+
+#[cfg(test)]
+#[path = "./task_test.rs"]
+mod task_test;
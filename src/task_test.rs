@@ -0,0 +1,56 @@
+use super::*;
+
+fn run_to_completion(task: &mut ScanTask) -> (usize, Vec<usize>) {
+    let mut polls = 1;
+    loop {
+        match task.poll() {
+            Poll::Ready(matches) => return (polls, matches),
+            Poll::Pending => polls += 1
+        }
+    }
+}
+
+#[test]
+fn poll_empty_source() {
+    let config = BndmConfig::new(b"fox", None);
+    let mut task = ScanTask::new(b"", &config, 10);
+
+    assert_eq!(task.poll(), Poll::Ready(vec![]));
+}
+
+#[test]
+fn poll_finds_occurrences_across_steps() {
+    let source = b"abcabcabcabc";
+    let config = BndmConfig::new(b"cab", None);
+    let mut task = ScanTask::new(source, &config, 3);
+
+    assert_eq!(run_to_completion(&mut task), (4, vec![2, 5, 8]));
+}
+
+#[test]
+fn poll_finds_overlapping_occurrences() {
+    let config = BndmConfig::new(b"aa", None);
+    let mut task = ScanTask::new(b"aaaa", &config, 1);
+
+    assert_eq!(run_to_completion(&mut task), (4, vec![0, 1, 2]));
+}
+
+#[test]
+fn poll_zero_budget() {
+    let config = BndmConfig::new(b"b", None);
+    let mut task = ScanTask::new(b"ab", &config, 0);
+
+    assert_eq!(task.poll(), Poll::Pending);
+    assert_eq!(task.position(), 1);
+    assert_eq!(task.poll(), Poll::Ready(vec![1]));
+}
+
+#[test]
+fn poll_after_ready() {
+    let config = BndmConfig::new(b"b", None);
+    let mut task = ScanTask::new(b"ab", &config, 10);
+
+    assert_eq!(task.poll(), Poll::Ready(vec![1]));
+    assert!(task.is_finished());
+    assert_eq!(task.poll(), Poll::Ready(vec![]));
+}
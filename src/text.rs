@@ -0,0 +1,87 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Search in UTF-8 text with results on character boundaries.
+
+use crate::{BndmConfig, find_pattern_verified};
+
+impl BndmConfig {
+    /// Creates a new `BndmConfig` instance for a text pattern.
+    ///
+    /// # Arguments
+    ///
+    /// * `search_pattern` - The text to search for.
+    /// * `wildcard` - An optional ASCII wildcard character. If provided, this character in the
+    ///   pattern can match any byte in the text, so a multi-byte character in the text is
+    ///   matched by one wildcard per byte.
+    ///
+    /// # Returns
+    ///
+    /// * `BndmConfig` - A new `BndmConfig` instance.
+    pub fn new_str(search_pattern: &str, wildcard: Option<u8>) -> BndmConfig {
+        BndmConfig::new(search_pattern.as_bytes(), wildcard)
+    }
+}
+
+/// Searches for the first occurrence of the pattern in the text that starts and ends on a
+/// character boundary.
+///
+/// Occurrences that start or end within a multi-byte character, which wildcards can match,
+/// are skipped, so the returned index can always be used to slice the text.
+///
+/// # Arguments
+///
+/// * `haystack` - The text to search for the pattern.
+/// * `config` - The configuration for the BNDM search, which includes the pattern and the
+///   bitmasks.
+///
+/// # Returns
+///
+/// * `Option<usize>` - Returns the byte index of the first occurrence of the pattern, or `None`
+///   if the pattern is not found.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{BndmConfig, find_pattern_str};
+///
+/// let haystack = "Größe: 42 cm";
+/// let config = BndmConfig::new_str("?? cm", Some(b'?'));
+/// let index = find_pattern_str(haystack, &config).unwrap();
+/// assert_eq!(&haystack[index..], "42 cm");
+/// ```
+pub fn find_pattern_str(haystack: &str, config: &BndmConfig) -> Option<usize> {
+    find_pattern_verified(haystack.as_bytes(), config, |_, index| {
+        haystack.is_char_boundary(index) && haystack.is_char_boundary(index + config.pattern.len())
+    })
+}
+
+/// Searches for the first occurrence of the pattern in the text that starts and ends on a
+/// character boundary, and returns its character index.
+///
+/// # Arguments
+///
+/// * `haystack` - The text to search for the pattern.
+/// * `config` - The configuration for the BNDM search, which includes the pattern and the
+///   bitmasks.
+///
+/// # Returns
+///
+/// * `Option<usize>` - Returns the number of characters before the first occurrence of the
+///   pattern, or `None` if the pattern is not found.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{BndmConfig, find_pattern_char_index};
+///
+/// let config = BndmConfig::new_str("42", None);
+/// assert_eq!(find_pattern_char_index("Größe: 42 cm", &config), Some(7));
+/// ```
+pub fn find_pattern_char_index(haystack: &str, config: &BndmConfig) -> Option<usize> {
+    find_pattern_str(haystack, config).map(|index| haystack[..index].chars().count())
+}
+
+#[cfg(test)]
+#[path = "./text_test.rs"]
+mod text_test;
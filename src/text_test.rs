@@ -0,0 +1,47 @@
+use super::*;
+
+#[test]
+fn find_pattern_str_ascii() {
+    let config = BndmConfig::new_str("jumps", None);
+
+    assert_eq!(find_pattern_str("The quick brown fox jumps over the lazy dog", &config), Some(20));
+}
+
+#[test]
+fn find_pattern_str_multi_byte_pattern() {
+    let config = BndmConfig::new_str("café", None);
+
+    assert_eq!(find_pattern_str("Un café crème", &config), Some(3));
+    assert_eq!(find_pattern_char_index("Un café crème", &config), Some(3));
+    assert_eq!(find_pattern_char_index("日本の café", &config), Some(4));
+}
+
+#[test]
+fn find_pattern_str_skips_match_within_character() {
+    // "ñ" is encoded as C3 B1, so "?b" matches B1 62 within the text, which starts within "ñ".
+    let config = BndmConfig::new_str("?b", Some(b'?'));
+
+    assert_eq!(find_pattern_str("ñb ab", &config), Some(4));
+}
+
+#[test]
+fn find_pattern_str_skips_match_ending_within_character() {
+    let config = BndmConfig::new_str("a?", Some(b'?'));
+
+    assert_eq!(find_pattern_str("añ az", &config), Some(4));
+}
+
+#[test]
+fn find_pattern_str_wildcards_spanning_character() {
+    let config = BndmConfig::new_str("a??b", Some(b'?'));
+
+    assert_eq!(find_pattern_str("añb", &config), Some(0));
+}
+
+#[test]
+fn find_pattern_str_no_match() {
+    let config = BndmConfig::new_str("?z", Some(b'?'));
+
+    assert_eq!(find_pattern_str("ñ", &config), None);
+    assert_eq!(find_pattern_char_index("abc", &config), None);
+}
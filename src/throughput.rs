@@ -0,0 +1,136 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Estimation of the scan throughput of a pattern, for detecting poorly selective patterns
+//! before scanning large sources.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::{BndmConfig, find_overlapping_indexes_unlogged, get_mask, get_pattern_length_within_cpu_word, scan_bndm_with_shifts};
+
+const MIN_DURATION: Duration = Duration::from_millis(10);
+const MAX_ROUNDS: usize = 1000;
+
+/// The `ThroughputEstimate` struct holds the result of a timed scan of a sample.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThroughputEstimate {
+    /// The estimated throughput in megabytes (10^6 bytes) per second.
+    pub megabytes_per_second: f64,
+
+    /// The number of windows of the sample that were inspected in a single scan.
+    pub windows: usize,
+
+    /// The average number of bytes the window shifted after every inspection.
+    pub average_shift: f64,
+
+    /// The smallest shift of the window.
+    pub min_shift: usize,
+
+    /// The largest shift of the window.
+    pub max_shift: usize,
+
+    /// The largest shift that is possible for the pattern. The window shifts at most one byte
+    /// less than the length of the part of the pattern that is processed by BNDM.
+    pub optimal_shift: usize
+}
+
+impl ThroughputEstimate {
+    /// Returns the average shift relative to the largest possible shift, between 0.0 and 1.0.
+    ///
+    /// A low value indicates a poorly selective pattern, e.g. a pattern with a wildcard prefix
+    /// or with bytes that are common in the sample, for which the scan advances slowly.
+    pub fn selectivity(&self) -> f64 {
+        self.average_shift / self.optimal_shift as f64
+    }
+}
+
+impl BndmConfig {
+    /// Estimates the throughput of scanning for the pattern by running a short timed scan of
+    /// the sample.
+    ///
+    /// The sample is scanned repeatedly for about 10 milliseconds, so the sample should be
+    /// representative for the sources to scan, but small enough to be scanned several times.
+    ///
+    /// # Arguments
+    ///
+    /// * `sample` - A sample of the sources that will be scanned for the pattern.
+    ///
+    /// # Returns
+    ///
+    /// * `ThroughputEstimate` - The estimated throughput and the statistics of the shifts of
+    ///   the scan window.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::BndmConfig;
+    ///
+    /// let sample = b"The quick brown fox jumps over the lazy dog".repeat(100);
+    /// let selective = BndmConfig::new(b"lazy cat", None).estimate_throughput(&sample);
+    /// let unselective = BndmConfig::new(b"???????t", Some(b'?')).estimate_throughput(&sample);
+    ///
+    /// assert!(selective.selectivity() > unselective.selectivity());
+    /// ```
+    pub fn estimate_throughput(&self, sample: &[u8]) -> ThroughputEstimate {
+        let mut rounds = 0;
+        let start = Instant::now();
+        while rounds < MAX_ROUNDS && (rounds == 0 || start.elapsed() < MIN_DURATION) {
+            black_box(find_overlapping_indexes_unlogged(black_box(sample), self).count());
+            rounds += 1;
+        }
+        let seconds = start.elapsed().as_secs_f64();
+
+        let shifts = ShiftStatistics::of(sample, self);
+
+        ThroughputEstimate {
+            megabytes_per_second: if seconds > 0.0 { (sample.len() * rounds) as f64 / seconds / 1_000_000.0 } else { f64::INFINITY },
+            windows: shifts.windows,
+            average_shift: if shifts.windows == 0 { 0.0 } else { shifts.total as f64 / shifts.windows as f64 },
+            min_shift: shifts.min,
+            max_shift: shifts.max,
+            optimal_shift: get_pattern_length_within_cpu_word(self.pattern.len()).saturating_sub(1).max(1)
+        }
+    }
+}
+
+#[derive(Default)]
+struct ShiftStatistics {
+    windows: usize,
+    total: usize,
+    min: usize,
+    max: usize
+}
+
+impl ShiftStatistics {
+    /// Returns the statistics of the shifts of the BNDM window over the source, continuing
+    /// after every match.
+    fn of(source: &[u8], config: &BndmConfig) -> ShiftStatistics {
+        let mut statistics = ShiftStatistics::default();
+        if config.pattern.is_empty() || config.pattern.len() > source.len() {
+            return statistics;
+        }
+
+        if config.pattern.len() == 1 {
+            let windows = source.len();
+            return ShiftStatistics { windows, total: windows, min: 1, max: 1 };
+        }
+
+        scan_bndm_with_shifts(source.len(), config.pattern.len(),
+            |index| get_mask(source, &config.masks, index),
+            |_| false,
+            |shift| statistics.add(shift));
+        statistics
+    }
+
+    fn add(&mut self, shift: usize) {
+        self.min = if self.windows == 0 { shift } else { self.min.min(shift) };
+        self.max = self.max.max(shift);
+        self.total += shift;
+        self.windows += 1;
+    }
+}
+
+#[cfg(test)]
+#[path = "./throughput_test.rs"]
+mod throughput_test;
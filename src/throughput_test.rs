@@ -0,0 +1,63 @@
+use super::*;
+
+#[test]
+fn estimate_throughput_empty_sample() {
+    let estimate = BndmConfig::new(b"fox", None).estimate_throughput(b"");
+
+    assert_eq!(estimate.windows, 0);
+    assert_eq!(estimate.average_shift, 0.0);
+    assert_eq!(estimate.min_shift, 0);
+    assert_eq!(estimate.max_shift, 0);
+    assert_eq!(estimate.optimal_shift, 2);
+}
+
+#[test]
+fn estimate_throughput_empty_pattern() {
+    let estimate = BndmConfig::new(b"", None).estimate_throughput(b"abc");
+
+    assert_eq!(estimate.windows, 0);
+    assert_eq!(estimate.selectivity(), 0.0);
+}
+
+#[test]
+fn estimate_throughput_absent_bytes() {
+    let estimate = BndmConfig::new(b"xyz", None).estimate_throughput(b"aaaaaaaaaaaa");
+
+    assert_eq!(estimate.windows, 5);
+    assert_eq!(estimate.average_shift, 2.0);
+    assert_eq!(estimate.selectivity(), 1.0);
+    assert!(estimate.megabytes_per_second > 0.0);
+}
+
+#[test]
+fn estimate_throughput_single_char() {
+    let estimate = BndmConfig::new(b"a", None).estimate_throughput(b"abcd");
+
+    assert_eq!(estimate.windows, 4);
+    assert_eq!(estimate.min_shift, 1);
+    assert_eq!(estimate.max_shift, 1);
+}
+
+#[test]
+fn estimate_throughput_wildcard_prefix() {
+    let sample = b"The quick brown fox jumps over the lazy dog".repeat(10);
+    let estimate = BndmConfig::new(b"????x", Some(b'?')).estimate_throughput(&sample);
+
+    assert_eq!(estimate.max_shift, 1);
+    assert_eq!(estimate.windows, sample.len() - 4);
+    assert_eq!(estimate.selectivity(), 0.25);
+}
+
+#[test]
+fn shift_statistics_continue_after_match() {
+    let shifts = ShiftStatistics::of(b"abababab", &BndmConfig::new(b"ab", None));
+
+    assert_eq!((shifts.windows, shifts.total, shifts.min, shifts.max), (7, 7, 1, 1));
+}
+
+#[test]
+fn shift_statistics_of_mixed_shifts() {
+    let shifts = ShiftStatistics::of(b"xxxxabcdxxab", &BndmConfig::new(b"abcd", None));
+
+    assert_eq!((shifts.windows, shifts.total, shifts.min, shifts.max), (5, 10, 1, 3));
+}
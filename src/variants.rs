@@ -0,0 +1,210 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Searching for a pattern together with transformed variants of it, reporting which variant
+//! matched.
+
+use crate::{BndmConfig, find_indexes, find_pattern};
+
+/// A variant of a pattern.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Variant {
+    /// The pattern as given.
+    Original,
+
+    /// The pattern with the bytes of every 16-bit word swapped.
+    Swapped16,
+
+    /// The pattern with the bytes of every 32-bit word swapped.
+    Swapped32,
+
+    /// The pattern with all bytes in reverse order.
+    Reversed,
+
+    /// The pattern with all ASCII letters in lowercase.
+    Lowercase,
+
+    /// The pattern with all ASCII letters in uppercase.
+    Uppercase,
+
+    /// The pattern with the first ASCII letter of every word in uppercase and the other ASCII
+    /// letters in lowercase.
+    TitleCase
+}
+
+/// A match of a variant of a pattern.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VariantMatch {
+    /// The index of the match in the source.
+    pub offset: usize,
+
+    /// The variant that matched.
+    pub variant: Variant
+}
+
+/// The `VariantSet` struct stores a pattern and variants of it, each compiled into its own
+/// configuration.
+///
+/// Variants that are identical to a variant already in the set are not added, so a match is
+/// reported with the first variant that produces the pattern.
+pub struct VariantSet {
+    pattern: Vec<u8>,
+    wildcard: Option<u8>,
+    variants: Vec<(Variant, BndmConfig)>
+}
+
+impl VariantSet {
+    /// Creates a new `VariantSet` instance that contains the original pattern only.
+    ///
+    /// # Arguments
+    ///
+    /// * `search_pattern` - The pattern to search for in the text.
+    /// * `wildcard` - An optional wildcard character. If provided, this character in the pattern
+    ///   can match any character in the text.
+    ///
+    /// # Returns
+    ///
+    /// * `VariantSet` - A new `VariantSet` instance.
+    pub fn new(search_pattern: &[u8], wildcard: Option<u8>) -> VariantSet {
+        VariantSet {
+            pattern: search_pattern.to_owned(),
+            wildcard,
+            variants: vec![(Variant::Original, BndmConfig::new(search_pattern, wildcard))]
+        }
+    }
+
+    /// Adds the 16-bit and 32-bit byte-swapped variants of the pattern, for searching data of
+    /// unknown endianness.
+    ///
+    /// A byte-swapped variant is only added if the length of the pattern is a multiple of the
+    /// word size.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{Variant, VariantMatch, VariantSet};
+    ///
+    /// let source = b"\x00\x00\xef\xbe\xad\xde\x00\x00\xad\xde\xef\xbe";
+    /// let set = VariantSet::new(b"\xde\xad\xbe\xef", None).with_byte_swapped();
+    /// assert_eq!(set.find_all(source), vec![
+    ///     VariantMatch { offset: 2, variant: Variant::Swapped32 },
+    ///     VariantMatch { offset: 8, variant: Variant::Swapped16 }
+    /// ]);
+    /// ```
+    pub fn with_byte_swapped(mut self) -> VariantSet {
+        for (variant, word_size) in [(Variant::Swapped16, 2), (Variant::Swapped32, 4)] {
+            if self.pattern.len().is_multiple_of(word_size) {
+                let swapped: Vec<u8> = self.pattern.chunks(word_size).flat_map(|word| word.iter().rev()).copied().collect();
+                self.add(variant, &swapped);
+            }
+        }
+        self
+    }
+
+    /// Adds the byte-reversed variant of the pattern, e.g. for palindromic motifs in DNA or for
+    /// data that is stored back-to-front.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{Variant, VariantMatch, VariantSet};
+    ///
+    /// let source = b"GATTACA..ACATTAG";
+    /// let set = VariantSet::new(b"GATTACA", None).with_reversed();
+    /// assert_eq!(set.find_all(source), vec![
+    ///     VariantMatch { offset: 0, variant: Variant::Original },
+    ///     VariantMatch { offset: 9, variant: Variant::Reversed }
+    /// ]);
+    /// ```
+    pub fn with_reversed(mut self) -> VariantSet {
+        let reversed: Vec<u8> = self.pattern.iter().rev().copied().collect();
+        self.add(Variant::Reversed, &reversed);
+        self
+    }
+
+    /// Adds the lowercase, uppercase and title case variants of the pattern, so hits of
+    /// different case variants are distinguished instead of folded together. A wildcard
+    /// character in the pattern is kept as is.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{Variant, VariantMatch, VariantSet};
+    ///
+    /// let source = b"ERROR: disk error in Error Handler";
+    /// let set = VariantSet::new(b"error handler", None).with_case_variants();
+    /// assert_eq!(set.find(source), Some(VariantMatch { offset: 21, variant: Variant::TitleCase }));
+    /// ```
+    pub fn with_case_variants(mut self) -> VariantSet {
+        let wildcard = self.wildcard;
+        let convert = |convert_byte: &dyn Fn(usize, u8) -> u8| -> Vec<u8> {
+            self.pattern.iter().enumerate()
+                .map(|(index, &byte)| if wildcard == Some(byte) { byte } else { convert_byte(index, byte) })
+                .collect()
+        };
+
+        let lowercase = convert(&|_, byte| byte.to_ascii_lowercase());
+        let uppercase = convert(&|_, byte| byte.to_ascii_uppercase());
+        let title_case = convert(&|index, byte| {
+            let is_word_start = index == 0 || !self.pattern[index - 1].is_ascii_alphabetic();
+            if is_word_start { byte.to_ascii_uppercase() } else { byte.to_ascii_lowercase() }
+        });
+
+        self.add(Variant::Lowercase, &lowercase);
+        self.add(Variant::Uppercase, &uppercase);
+        self.add(Variant::TitleCase, &title_case);
+        self
+    }
+
+    /// Returns the variants in the set with their configurations.
+    pub fn variants(&self) -> &[(Variant, BndmConfig)] {
+        &self.variants
+    }
+
+    /// Searches for the first occurrence of any variant of the pattern.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source string to search for the variants.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<VariantMatch>` - Returns the first match, or `None` if no variant is found. If
+    ///   several variants match at the same index, the first variant in the set is reported.
+    pub fn find(&self, source: &[u8]) -> Option<VariantMatch> {
+        self.variants.iter()
+            .filter_map(|(variant, config)| find_pattern(source, config).map(|offset| VariantMatch { offset, variant: *variant }))
+            .min_by_key(|variant_match| variant_match.offset)
+    }
+
+    /// Searches for all occurrences of all variants of the pattern.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source string to search for the variants.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<VariantMatch>` - Returns the matches ordered by their index. Occurrences of the
+    ///   same variant do not overlap. If several variants match at the same index, the first
+    ///   variant in the set is reported.
+    pub fn find_all(&self, source: &[u8]) -> Vec<VariantMatch> {
+        let mut matches: Vec<VariantMatch> = self.variants.iter()
+            .flat_map(|(variant, config)| find_indexes(source, config).map(|offset| VariantMatch { offset, variant: *variant }))
+            .collect();
+
+        matches.sort_by_key(|variant_match| variant_match.offset);
+        matches.dedup_by_key(|variant_match| variant_match.offset);
+        matches
+    }
+
+    fn add(&mut self, variant: Variant, pattern: &[u8]) {
+        if self.variants.iter().all(|(_, config)| config.pattern != pattern) {
+            self.variants.push((variant, BndmConfig::new(pattern, self.wildcard)));
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "./variants_test.rs"]
+mod variants_test;
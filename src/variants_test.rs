@@ -0,0 +1,134 @@
+use super::*;
+
+#[test]
+fn find_original_only() {
+    let source = b"\xef\xbe\xad\xde";
+    let set = VariantSet::new(b"\xde\xad\xbe\xef", None);
+
+    assert_eq!(set.find(source), None);
+    assert_eq!(set.variants().len(), 1);
+}
+
+#[test]
+fn find_byte_swapped_16() {
+    let source = b"....\xad\xde\xef\xbe";
+    let set = VariantSet::new(b"\xde\xad\xbe\xef", None).with_byte_swapped();
+
+    assert_eq!(set.find(source), Some(VariantMatch { offset: 4, variant: Variant::Swapped16 }));
+}
+
+#[test]
+fn find_byte_swapped_32() {
+    let source = b"\xde\xad\xbe\xef\xef\xbe\xad\xde";
+    let set = VariantSet::new(b"\xde\xad\xbe\xef", None).with_byte_swapped();
+
+    assert_eq!(set.find_all(source), vec![
+        VariantMatch { offset: 0, variant: Variant::Original },
+        VariantMatch { offset: 4, variant: Variant::Swapped32 }
+    ]);
+}
+
+#[test]
+fn find_byte_swapped_with_wildcard() {
+    let source = b"\x34\x12\x00\x00";
+    let set = VariantSet::new(b"\x12\x34??", Some(b'?')).with_byte_swapped();
+
+    assert_eq!(set.find(source), Some(VariantMatch { offset: 0, variant: Variant::Swapped16 }));
+}
+
+#[test]
+fn byte_swapped_odd_length() {
+    let set = VariantSet::new(b"abc", None).with_byte_swapped();
+
+    assert_eq!(set.variants().len(), 1);
+}
+
+#[test]
+fn byte_swapped_length_of_16_bit_word() {
+    let set = VariantSet::new(b"abcdef", None).with_byte_swapped();
+    let variants: Vec<Variant> = set.variants().iter().map(|(variant, _)| *variant).collect();
+
+    assert_eq!(variants, vec![Variant::Original, Variant::Swapped16]);
+}
+
+#[test]
+fn byte_swapped_identical_variant() {
+    let set = VariantSet::new(b"aaaa", None).with_byte_swapped();
+
+    assert_eq!(set.find_all(b"aaaa"), vec![VariantMatch { offset: 0, variant: Variant::Original }]);
+    assert_eq!(set.variants().len(), 1);
+}
+
+#[test]
+fn find_reversed() {
+    let source = b"The quick brown fox jumps over the lazy dog";
+    let set = VariantSet::new(b"god", None).with_reversed();
+
+    assert_eq!(set.find(source), Some(VariantMatch { offset: 40, variant: Variant::Reversed }));
+}
+
+#[test]
+fn find_reversed_with_wildcard() {
+    let source = b"..ab?..cxba";
+    let set = VariantSet::new(b"ab?", Some(b'?')).with_reversed();
+
+    assert_eq!(set.find_all(source), vec![
+        VariantMatch { offset: 2, variant: Variant::Original },
+        VariantMatch { offset: 8, variant: Variant::Reversed }
+    ]);
+}
+
+#[test]
+fn find_reversed_palindrome() {
+    let set = VariantSet::new(b"abba", None).with_reversed();
+
+    assert_eq!(set.variants().len(), 1);
+    assert_eq!(set.find(b"xabba"), Some(VariantMatch { offset: 1, variant: Variant::Original }));
+}
+
+#[test]
+fn find_reversed_and_byte_swapped() {
+    let source = b"dcba..badc";
+    let set = VariantSet::new(b"abcd", None).with_byte_swapped().with_reversed();
+
+    assert_eq!(set.find_all(source), vec![
+        VariantMatch { offset: 0, variant: Variant::Swapped32 },
+        VariantMatch { offset: 6, variant: Variant::Swapped16 }
+    ]);
+}
+
+#[test]
+fn find_case_variants() {
+    let source = b"warning: WARNING Warning";
+    let set = VariantSet::new(b"wArning", None).with_case_variants();
+
+    assert_eq!(set.find_all(source), vec![
+        VariantMatch { offset: 0, variant: Variant::Lowercase },
+        VariantMatch { offset: 9, variant: Variant::Uppercase },
+        VariantMatch { offset: 17, variant: Variant::TitleCase }
+    ]);
+}
+
+#[test]
+fn case_variants_skip_identical() {
+    let set = VariantSet::new(b"error", None).with_case_variants();
+    let variants: Vec<Variant> = set.variants().iter().map(|(variant, _)| *variant).collect();
+
+    assert_eq!(variants, vec![Variant::Original, Variant::Uppercase, Variant::TitleCase]);
+}
+
+#[test]
+fn case_variants_title_case_words() {
+    let set = VariantSet::new(b"end-of-file reached", None).with_case_variants();
+    let (_, config) = set.variants().iter().find(|(variant, _)| *variant == Variant::TitleCase).unwrap();
+
+    assert_eq!(config.pattern, b"End-Of-File Reached");
+}
+
+#[test]
+fn case_variants_keep_wildcard() {
+    let source = b"FOO1BAR";
+    let set = VariantSet::new(b"fooxbar", Some(b'x')).with_case_variants();
+
+    assert_eq!(set.find(source), Some(VariantMatch { offset: 0, variant: Variant::Uppercase }));
+}
@@ -0,0 +1,230 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Search in texts of elements other than bytes, e.g. UTF-16 memory dumps, token streams and
+//! opcode streams.
+
+use crate::{MASKS_TABLE_SIZE, get_pattern_length_within_cpu_word, scan_bndm};
+
+/// An element of a wide text that can be searched with BNDM.
+///
+/// Every element is folded to one of the 256 entries of the mask table. Elements that fold to
+/// the same entry are indistinguishable for the scan, so every candidate occurrence is
+/// verified against the elements of the pattern.
+pub trait WideElement: Copy + Eq {
+    /// Returns the index of the entry of the mask table for the element.
+    fn mask_index(self) -> u8;
+}
+
+impl WideElement for u16 {
+    fn mask_index(self) -> u8 {
+        (self ^ (self >> 8)) as u8
+    }
+}
+
+impl WideElement for u32 {
+    fn mask_index(self) -> u8 {
+        self.to_le_bytes().iter().fold(0, |index, &byte| index ^ byte)
+    }
+}
+
+/// The `WideConfig` struct is used to store a pattern of wide elements and the bitmasks.
+#[derive(Clone, Debug)]
+pub struct WideConfig<T> {
+    masks: [usize; MASKS_TABLE_SIZE],
+    wildcard: Option<T>,
+    pattern: Vec<T>
+}
+
+impl<T: WideElement> WideConfig<T> {
+    /// Creates a new `WideConfig` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `search_pattern` - The pattern to search for in the text.
+    /// * `wildcard` - An optional wildcard element. If provided, this element in the pattern
+    ///   can match any element in the text.
+    ///
+    /// # Returns
+    ///
+    /// * `WideConfig` - A new `WideConfig` instance.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{WideConfig, find_pattern_wide};
+    ///
+    /// let source: Vec<u16> = "C:\\Windows\\System32".encode_utf16().collect();
+    /// let pattern: Vec<u16> = "Sys?em".encode_utf16().collect();
+    /// let config = WideConfig::new(&pattern, Some(b'?' as u16));
+    /// assert_eq!(find_pattern_wide(&source, &config), Some(11));
+    /// ```
+    pub fn new(search_pattern: &[T], wildcard: Option<T>) -> WideConfig<T> {
+        WideConfig {
+            masks: generate_element_masks(search_pattern, wildcard, |element| element.mask_index()),
+            wildcard,
+            pattern: search_pattern.to_vec()
+        }
+    }
+
+    /// Returns the pattern.
+    pub fn pattern(&self) -> &[T] {
+        &self.pattern
+    }
+
+    /// Returns the wildcard element.
+    pub fn wildcard(&self) -> Option<T> {
+        self.wildcard
+    }
+}
+
+/// Searches for the pattern in a text of wide elements using the BNDM algorithm.
+///
+/// # Arguments
+///
+/// * `source` - The text to search for the pattern.
+/// * `config` - The configuration for the BNDM search, which includes the pattern and the
+///   bitmasks.
+///
+/// # Returns
+///
+/// * `Option<usize>` - Returns the index, in elements, of the first occurrence of the pattern
+///   in the text, or `None` if the pattern is not found.
+pub fn find_pattern_wide<T: WideElement>(source: &[T], config: &WideConfig<T>) -> Option<usize> {
+    find_elements(source, &config.masks, &config.pattern, config.wildcard, |element| element.mask_index())
+}
+
+/// An alphabet that maps the elements of a text to the entries of the mask table, so texts of
+/// custom elements, e.g. opcodes or tokens, can be searched with BNDM.
+///
+/// Elements that map to the same entry are indistinguishable for the scan, so every candidate
+/// occurrence is verified against the elements of the pattern. An alphabet with at most 256
+/// distinct elements that maps every element to its own entry avoids false candidates.
+pub trait Alphabet {
+    /// The type of the elements of the text.
+    type Element: Copy + Eq;
+
+    /// Returns the index of the entry of the mask table for the element.
+    fn mask_index(&self, element: Self::Element) -> u8;
+}
+
+/// The `AlphabetConfig` struct is used to store an alphabet, a pattern of elements of the
+/// alphabet and the bitmasks.
+#[derive(Clone, Debug)]
+pub struct AlphabetConfig<A: Alphabet> {
+    alphabet: A,
+    masks: [usize; MASKS_TABLE_SIZE],
+    wildcard: Option<A::Element>,
+    pattern: Vec<A::Element>
+}
+
+impl<A: Alphabet> AlphabetConfig<A> {
+    /// Creates a new `AlphabetConfig` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `alphabet` - The alphabet of the elements.
+    /// * `search_pattern` - The pattern to search for in the text.
+    /// * `wildcard` - An optional wildcard element. If provided, this element in the pattern
+    ///   can match any element in the text.
+    ///
+    /// # Returns
+    ///
+    /// * `AlphabetConfig` - A new `AlphabetConfig` instance.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::{Alphabet, AlphabetConfig};
+    ///
+    /// #[derive(Clone, Copy, PartialEq, Eq)]
+    /// enum Op { Push(u32), Pop, Call(&'static str), Ret }
+    ///
+    /// struct Opcodes;
+    ///
+    /// impl Alphabet for Opcodes {
+    ///     type Element = Op;
+    ///
+    ///     fn mask_index(&self, op: Op) -> u8 {
+    ///         match op {
+    ///             Op::Push(value) => value as u8,
+    ///             Op::Pop => 0xfd,
+    ///             Op::Call(name) => name.len() as u8,
+    ///             Op::Ret => 0xff
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let code = [Op::Push(1), Op::Call("open"), Op::Pop, Op::Push(2), Op::Call("read"), Op::Ret];
+    /// let config = AlphabetConfig::new(Opcodes, &[Op::Push(2), Op::Call("read")], None);
+    /// assert_eq!(config.find(&code), Some(3));
+    /// ```
+    pub fn new(alphabet: A, search_pattern: &[A::Element], wildcard: Option<A::Element>) -> AlphabetConfig<A> {
+        AlphabetConfig {
+            masks: generate_element_masks(search_pattern, wildcard, |element| alphabet.mask_index(element)),
+            alphabet,
+            wildcard,
+            pattern: search_pattern.to_vec()
+        }
+    }
+
+    /// Returns the alphabet.
+    pub fn alphabet(&self) -> &A {
+        &self.alphabet
+    }
+
+    /// Returns the pattern.
+    pub fn pattern(&self) -> &[A::Element] {
+        &self.pattern
+    }
+
+    /// Returns the wildcard element.
+    pub fn wildcard(&self) -> Option<A::Element> {
+        self.wildcard
+    }
+
+    /// Searches for the pattern in a text of elements of the alphabet using the BNDM algorithm.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The text to search for the pattern.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<usize>` - Returns the index, in elements, of the first occurrence of the
+    ///   pattern in the text, or `None` if the pattern is not found.
+    pub fn find(&self, source: &[A::Element]) -> Option<usize> {
+        find_elements(source, &self.masks, &self.pattern, self.wildcard, |element| self.alphabet.mask_index(element))
+    }
+}
+
+fn generate_element_masks<E: Copy + Eq>(search_pattern: &[E], wildcard: Option<E>, mask_index: impl Fn(E) -> u8) -> [usize; MASKS_TABLE_SIZE] {
+    let filter_len = get_pattern_length_within_cpu_word(search_pattern.len());
+    let mut masks = [0; MASKS_TABLE_SIZE];
+    for (position, &element) in search_pattern[..filter_len].iter().enumerate() {
+        let bit = 1 << (filter_len - 1 - position);
+        if wildcard == Some(element) {
+            masks.iter_mut().for_each(|mask| *mask |= bit);
+        } else {
+            masks[mask_index(element) as usize] |= bit;
+        }
+    }
+    masks
+}
+
+fn find_elements<E: Copy + Eq>(source: &[E], masks: &[usize; MASKS_TABLE_SIZE], pattern: &[E], wildcard: Option<E>,
+                               mask_index: impl Fn(E) -> u8) -> Option<usize> {
+    let matches_at = |index: usize| pattern.iter().zip(&source[index..])
+        .all(|(&pattern_element, &element)| pattern_element == element || wildcard == Some(pattern_element));
+
+    match pattern.len() {
+        0 => None,
+        1 => (0..source.len()).find(|&index| matches_at(index)),
+        pattern_len if pattern_len > source.len() => None,
+        pattern_len => scan_bndm(source.len(), pattern_len, |index| masks[mask_index(source[index]) as usize], matches_at)
+    }
+}
+
+#[cfg(test)]
+#[path = "./wide_test.rs"]
+mod wide_test;
@@ -0,0 +1,139 @@
+use super::*;
+
+fn utf16(text: &str) -> Vec<u16> {
+    text.encode_utf16().collect()
+}
+
+#[test]
+fn find_pattern_wide_utf16() {
+    let source = utf16("The quick brown fox jumps over the lazy dog");
+    let config = WideConfig::new(&utf16("jumps"), None);
+
+    assert_eq!(find_pattern_wide(&source, &config), Some(20));
+}
+
+#[test]
+fn find_pattern_wide_utf16_wildcard() {
+    let source = utf16("Grüße aus Köln");
+    let config = WideConfig::new(&utf16("K?ln"), Some(b'?' as u16));
+
+    assert_eq!(find_pattern_wide(&source, &config), Some(10));
+}
+
+#[test]
+fn find_pattern_wide_folded_collision() {
+    // 0x0141 and 0x0040 fold to the same entry of the mask table.
+    let source = [0x0141, 0x0042, 0x0040, 0x0042];
+    let config = WideConfig::new(&[0x0040u16, 0x0042], None);
+
+    assert_eq!(find_pattern_wide(&source, &config), Some(2));
+}
+
+#[test]
+fn find_pattern_wide_u32_tokens() {
+    let source: Vec<u32> = vec![7, 100_000, 3, 42, 100_000, 3, 43];
+    let config = WideConfig::new(&[100_000u32, 3, 43], None);
+
+    assert_eq!(find_pattern_wide(&source, &config), Some(4));
+}
+
+#[test]
+fn find_pattern_wide_single_element() {
+    let config = WideConfig::new(&[0x1234u16], None);
+
+    assert_eq!(find_pattern_wide(&[0x3412, 0x1234], &config), Some(1));
+    assert_eq!(find_pattern_wide(&[0x3412], &config), None);
+}
+
+#[test]
+fn find_pattern_wide_empty_pattern() {
+    let config = WideConfig::<u16>::new(&[], None);
+
+    assert_eq!(find_pattern_wide(&[1, 2, 3], &config), None);
+}
+
+#[test]
+fn find_pattern_wide_pattern_longer_than_source() {
+    let config = WideConfig::new(&[1u32, 2, 3], None);
+
+    assert_eq!(find_pattern_wide(&[1, 2], &config), None);
+}
+
+#[test]
+fn find_pattern_wide_longer_than_word() {
+    let mut pattern = vec![0x3042u16; 70];
+    pattern[66] = 0xffff;
+    let mut source = vec![0x3042u16; 80];
+    source[68] = 0x0001;
+    let config = WideConfig::new(&pattern, Some(0xffff));
+
+    assert_eq!(find_pattern_wide(&source, &config), Some(2));
+
+    // 0x4230 folds to the same entry of the mask table as 0x3042.
+    source[71] = 0x4230;
+    assert_eq!(find_pattern_wide(&source, &config), None);
+}
+
+#[test]
+fn wide_config_accessors() {
+    let config = WideConfig::new(&[1u16, 0, 2], Some(0));
+
+    assert_eq!(config.pattern(), &[1, 0, 2]);
+    assert_eq!(config.wildcard(), Some(0));
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Token {
+    Word(&'static str),
+    Number(i64),
+    Comma
+}
+
+struct Tokens;
+
+impl Alphabet for Tokens {
+    type Element = Token;
+
+    fn mask_index(&self, token: Token) -> u8 {
+        match token {
+            Token::Word(word) => word.len() as u8,
+            Token::Number(_) => 0x80,
+            Token::Comma => 0x81
+        }
+    }
+}
+
+#[test]
+fn alphabet_config_find() {
+    let source = [Token::Word("let"), Token::Word("x"), Token::Number(1), Token::Comma, Token::Word("y"), Token::Number(2)];
+    let config = AlphabetConfig::new(Tokens, &[Token::Comma, Token::Word("y")], None);
+
+    assert_eq!(config.find(&source), Some(3));
+}
+
+#[test]
+fn alphabet_config_verifies_colliding_elements() {
+    let source = [Token::Word("ab"), Token::Number(1), Token::Word("cd"), Token::Number(2)];
+    let config = AlphabetConfig::new(Tokens, &[Token::Word("cd"), Token::Number(2)], None);
+
+    assert_eq!(config.find(&source), Some(2));
+}
+
+#[test]
+fn alphabet_config_wildcard() {
+    let source = [Token::Number(1), Token::Comma, Token::Number(2), Token::Word("end")];
+    let config = AlphabetConfig::new(Tokens, &[Token::Comma, Token::Word("*"), Token::Word("end")], Some(Token::Word("*")));
+
+    assert_eq!(config.find(&source), Some(1));
+    assert_eq!(config.wildcard(), Some(Token::Word("*")));
+    assert_eq!(config.pattern().len(), 3);
+}
+
+#[test]
+fn alphabet_config_no_match() {
+    let source = [Token::Number(1), Token::Comma];
+    let config = AlphabetConfig::new(Tokens, &[Token::Comma, Token::Comma], None);
+
+    assert_eq!(config.find(&source), None);
+    assert_eq!(AlphabetConfig::new(Tokens, &[], None).find(&source), None);
+}
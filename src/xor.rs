@@ -0,0 +1,158 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Searching for patterns in XOR-obfuscated data, by applying the XOR key to the pattern
+//! instead of to the text.
+
+use crate::{BndmConfig, find_overlapping_indexes};
+
+/// The `XorPattern` struct stores a pattern as it appears after XOR with a repeating key.
+///
+/// The key repeats from the start of the source string, starting at the given phase, so the
+/// byte at index `n` of the source is XOR-ed with `key[(phase + n) % key.len()]`. For a key of
+/// `k` bytes, the pattern is compiled once for every position in the key, and every occurrence
+/// is verified against the pattern, so a XOR-ed byte that equals the wildcard character does
+/// not act as a wildcard.
+pub struct XorPattern {
+    pattern: Vec<u8>,
+    wildcard: Option<u8>,
+    key: Vec<u8>,
+    phase: usize,
+    configs: Vec<BndmConfig>
+}
+
+/// A match of a pattern that is XOR-ed with a single-byte key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct XorMatch {
+    /// The index of the match in the source.
+    pub offset: usize,
+
+    /// The key the pattern is XOR-ed with.
+    pub key: u8
+}
+
+impl XorPattern {
+    /// Creates a new `XorPattern` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `search_pattern` - The pattern to search for, before XOR.
+    /// * `wildcard` - An optional wildcard character. If provided, this character in the pattern
+    ///   can match any character in the text.
+    /// * `key` - The XOR key; an empty key leaves the pattern unchanged.
+    /// * `phase` - The index in the key of the byte that is XOR-ed with the first byte of the
+    ///   source string.
+    ///
+    /// # Returns
+    ///
+    /// * `XorPattern` - A new `XorPattern` instance.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::XorPattern;
+    ///
+    /// let key = b"\x13\x37";
+    /// let source: Vec<u8> = b"..http://example.com".iter().enumerate()
+    ///     .map(|(index, byte)| byte ^ key[(index + 1) % 2])
+    ///     .collect();
+    ///
+    /// let pattern = XorPattern::new(b"http://", None, key, 1);
+    /// assert_eq!(pattern.find(&source), Some(2));
+    /// ```
+    pub fn new(search_pattern: &[u8], wildcard: Option<u8>, key: &[u8], phase: usize) -> XorPattern {
+        let key = if key.is_empty() { vec![0] } else { key.to_owned() };
+        let configs = (0..key.len()).map(|key_index| {
+            let encoded: Vec<u8> = search_pattern.iter().enumerate()
+                .map(|(index, &byte)| if wildcard == Some(byte) { byte } else { byte ^ key[(key_index + index) % key.len()] })
+                .collect();
+            BndmConfig::new(&encoded, wildcard)
+        }).collect();
+
+        XorPattern {
+            pattern: search_pattern.to_owned(),
+            wildcard,
+            phase: phase % key.len(),
+            key,
+            configs
+        }
+    }
+
+    /// Returns the index of the first occurrence of the XOR-ed pattern in the source string,
+    /// or `None` if the pattern is not found.
+    pub fn find(&self, source: &[u8]) -> Option<usize> {
+        (0..self.configs.len())
+            .filter_map(|key_index| self.find_indexes(source, key_index).next())
+            .min()
+    }
+
+    /// Returns the indexes of all occurrences of the XOR-ed pattern in the source string in
+    /// ascending order. Occurrences may overlap.
+    pub fn find_all(&self, source: &[u8]) -> Vec<usize> {
+        let mut indexes: Vec<usize> = (0..self.configs.len())
+            .flat_map(|key_index| self.find_indexes(source, key_index))
+            .collect();
+        indexes.sort_unstable();
+        indexes
+    }
+
+    fn find_indexes<'a>(&'a self, source: &'a [u8], key_index: usize) -> impl Iterator<Item = usize> + 'a {
+        find_overlapping_indexes(source, &self.configs[key_index])
+            .filter(move |&index| (self.phase + index) % self.key.len() == key_index)
+            .filter(move |&index| self.is_match(source, index))
+    }
+
+    fn is_match(&self, source: &[u8], index: usize) -> bool {
+        self.pattern.iter().enumerate().all(|(offset, &pattern_byte)| {
+            self.wildcard == Some(pattern_byte) ||
+                source[index + offset] ^ self.key[(self.phase + index + offset) % self.key.len()] == pattern_byte
+        })
+    }
+}
+
+/// Searches for the pattern XOR-ed with every single-byte key from `0x01` to `0xff` at once.
+///
+/// Instead of scanning the source once for every key, the key is derived at every position
+/// from the first literal byte of the pattern and the remaining bytes are verified with that
+/// key, so all 255 keys are tested in a single pass over the source.
+///
+/// # Arguments
+///
+/// * `source` - The source string to search for the pattern.
+/// * `search_pattern` - The pattern to search for, before XOR.
+/// * `wildcard` - An optional wildcard character. If provided, this character in the pattern
+///   can match any character in the text.
+///
+/// # Returns
+///
+/// * `Vec<XorMatch>` - Returns the matches with their keys, ordered by their index.
+///   Occurrences may overlap. A pattern without literal bytes matches with every key, so no
+///   matches are reported for it.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::{XorMatch, find_xor_keys};
+///
+/// let source: Vec<u8> = b"..This program cannot be run in DOS mode".iter().map(|byte| byte ^ 0x7a).collect();
+/// assert_eq!(find_xor_keys(&source, b"DOS mode", None), vec![XorMatch { offset: 32, key: 0x7a }]);
+/// ```
+pub fn find_xor_keys(source: &[u8], search_pattern: &[u8], wildcard: Option<u8>) -> Vec<XorMatch> {
+    let Some(first_literal) = search_pattern.iter().position(|&byte| wildcard != Some(byte)) else {
+        return Vec::new();
+    };
+    let Some(end) = source.len().checked_sub(search_pattern.len()) else {
+        return Vec::new();
+    };
+
+    (0..=end).filter_map(|offset| {
+        let key = source[offset + first_literal] ^ search_pattern[first_literal];
+        let is_match = key != 0 && search_pattern.iter().zip(&source[offset..])
+            .all(|(&pattern_byte, &byte)| wildcard == Some(pattern_byte) || byte ^ key == pattern_byte);
+        is_match.then_some(XorMatch { offset, key })
+    }).collect()
+}
+
+#[cfg(test)]
+#[path = "./xor_test.rs"]
+mod xor_test;
@@ -0,0 +1,91 @@
+use super::*;
+
+fn xor(source: &[u8], key: &[u8], phase: usize) -> Vec<u8> {
+    source.iter().enumerate().map(|(index, byte)| byte ^ key[(phase + index) % key.len()]).collect()
+}
+
+#[test]
+fn find_single_byte_key() {
+    let source = xor(b"The quick brown fox jumps over the lazy dog", b"\x5a", 0);
+    let pattern = XorPattern::new(b"fox", None, b"\x5a", 0);
+
+    assert_eq!(pattern.find(&source), Some(16));
+    assert_eq!(pattern.find(b"The quick brown fox"), None);
+}
+
+#[test]
+fn find_empty_key() {
+    let pattern = XorPattern::new(b"fox", None, b"", 3);
+
+    assert_eq!(pattern.find(b"The quick brown fox"), Some(16));
+}
+
+#[test]
+fn find_all_multi_byte_key_at_every_phase() {
+    let plain = b"abc.abc..abc...abc";
+    let key = b"\x01\x02\x03";
+
+    for phase in 0..3 {
+        let source = xor(plain, key, phase);
+        let pattern = XorPattern::new(b"abc", None, key, phase);
+
+        assert_eq!(pattern.find_all(&source), vec![0, 4, 9, 15]);
+    }
+}
+
+#[test]
+fn find_rejects_wrong_key_alignment() {
+    let key = b"\x01\x02";
+    let mut source = xor(b"xxabcx", key, 0);
+    source[2..5].copy_from_slice(&xor(b"abc", key, 1));
+
+    assert_eq!(XorPattern::new(b"abc", None, key, 0).find(&source), None);
+}
+
+#[test]
+fn find_with_wildcard() {
+    let key = b"\x10\x20";
+    let source = xor(b"The quick brown fox", key, 0);
+    let pattern = XorPattern::new(b"b?o?n", Some(b'?'), key, 0);
+
+    assert_eq!(pattern.find(&source), Some(10));
+}
+
+#[test]
+fn find_encoded_byte_equal_to_wildcard_is_literal() {
+    let source = b"?b";
+    let pattern = XorPattern::new(b"ab", Some(b'?'), &[b'a' ^ b'?'], 0);
+
+    assert_eq!(pattern.find(source), None);
+    assert_eq!(pattern.find(&xor(b"ab", &[b'a' ^ b'?'], 0)), Some(0));
+}
+
+#[test]
+fn find_xor_keys_reports_keys() {
+    let mut source = xor(b"..http..", b"\x01", 0);
+    source.extend(xor(b"http", b"\xff", 0));
+    source.extend(b"http");
+
+    assert_eq!(find_xor_keys(&source, b"http", None), vec![
+        XorMatch { offset: 2, key: 0x01 },
+        XorMatch { offset: 8, key: 0xff }
+    ]);
+}
+
+#[test]
+fn find_xor_keys_with_wildcard_prefix() {
+    let source = xor(b"The quick brown fox", b"\x42", 0);
+
+    assert_eq!(find_xor_keys(&source, b"?ox", Some(b'?')), vec![XorMatch { offset: 16, key: 0x42 }]);
+}
+
+#[test]
+fn find_xor_keys_without_literals() {
+    assert_eq!(find_xor_keys(b"abc", b"??", Some(b'?')), vec![]);
+    assert_eq!(find_xor_keys(b"abc", b"", None), vec![]);
+}
+
+#[test]
+fn find_xor_keys_pattern_longer_than_source() {
+    assert_eq!(find_xor_keys(b"ab", b"abc", None), vec![]);
+}
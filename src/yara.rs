@@ -0,0 +1,410 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! A subset of YARA hex strings with jumps and alternatives, e.g.
+//! `{ 4D 5A [2-4] (50 45 | 4E 45) ?? }`.
+
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+use crate::hex::parse_hex_byte;
+use crate::{BndmConfig, ByteClass, find_pattern_from};
+
+/// The error type for parsing YARA hex strings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum YaraHexError {
+    /// The hex string has no bytes.
+    Empty,
+
+    /// A character at the given position is not valid in a hex string.
+    InvalidCharacter(usize),
+
+    /// The jump at the given position is malformed, has a minimum larger than its maximum, is
+    /// at the start or end of a sequence, or makes the length of the hex string overflow.
+    InvalidJump(usize),
+
+    /// The hex string ended unexpectedly.
+    UnexpectedEnd
+}
+
+impl fmt::Display for YaraHexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            YaraHexError::Empty => write!(f, "hex string is empty"),
+            YaraHexError::InvalidCharacter(position) => write!(f, "invalid character at position {position}"),
+            YaraHexError::InvalidJump(position) => write!(f, "invalid jump at position {position}"),
+            YaraHexError::UnexpectedEnd => write!(f, "unexpected end of hex string")
+        }
+    }
+}
+
+impl Error for YaraHexError {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Token {
+    Class(ByteClass),
+    Jump(usize, Option<usize>),
+    Alternatives(Vec<Vec<Token>>)
+}
+
+impl Token {
+    /// Returns the minimum and the optional maximum number of bytes the token matches, or
+    /// `None` if a bound does not fit in a `usize`.
+    pub(crate) fn len_bounds(&self) -> Option<(usize, Option<usize>)> {
+        match self {
+            Token::Class(_) => Some((1, Some(1))),
+            Token::Jump(min, max) => Some((*min, *max)),
+            Token::Alternatives(alternatives) => alternatives.iter()
+                .map(|tokens| sequence_len_bounds(tokens))
+                .collect::<Option<Vec<_>>>()?
+                .into_iter()
+                .reduce(|(min_a, max_a), (min_b, max_b)| (min_a.min(min_b), max_a.zip(max_b).map(|(a, b)| a.max(b))))
+                .or(Some((0, Some(0))))
+        }
+    }
+}
+
+/// The `YaraHexString` struct holds a YARA hex string with jumps and alternatives.
+///
+/// The longest run of bytes outside of jumps and alternatives is searched for with BNDM, and
+/// the complete hex string is verified at the start positions that the run allows.
+pub struct YaraHexString {
+    search: TokenSearch
+}
+
+/// A sequence of tokens that is searched for by its longest run of bytes, after which the
+/// complete sequence is verified.
+pub(crate) struct TokenSearch {
+    program: Vec<Instruction>,
+    start: usize,
+    anchor: Option<Anchor>
+}
+
+/// A token compiled into a step of a program, which refers to the step that follows it, so
+/// that a step and a position in the text fully determine the rest of the match.
+enum Instruction {
+    Match,
+    Class(ByteClass, usize),
+    Jump(usize, Option<usize>, usize),
+    Split(Vec<usize>)
+}
+
+struct Anchor {
+    config: BndmConfig,
+    min_offset: usize,
+    max_offset: Option<usize>
+}
+
+impl FromStr for YaraHexString {
+    type Err = YaraHexError;
+
+    /// Parses a YARA hex string. The surrounding braces are optional.
+    ///
+    /// Bytes are written as two hexadecimal digits, either of which can be `?`, and `~` before
+    /// a byte negates it. Jumps are written as `[4]`, `[2-4]`, `[2-]` or `[-]`, and
+    /// alternatives as `(AA | BB CC)`.
+    fn from_str(hex_string: &str) -> Result<YaraHexString, YaraHexError> {
+        let trimmed = hex_string.trim();
+        let (body, offset) = match trimmed.strip_prefix('{').and_then(|body| body.strip_suffix('}')) {
+            Some(body) => (body, hex_string.len() - hex_string.trim_start().len() + 1),
+            None => (hex_string, 0)
+        };
+
+        let mut parser = Parser { bytes: body.as_bytes(), position: 0 };
+        let tokens = parser.parse_sequence(false).map_err(|error| match error {
+            YaraHexError::InvalidCharacter(position) => YaraHexError::InvalidCharacter(position + offset),
+            YaraHexError::InvalidJump(position) => YaraHexError::InvalidJump(position + offset),
+            error => error
+        })?;
+
+        Ok(YaraHexString { search: TokenSearch::new(tokens) })
+    }
+}
+
+impl YaraHexString {
+    /// Returns the range of the first occurrence of the hex string in the source, or `None`
+    /// if the hex string is not found.
+    ///
+    /// Jumps match as few bytes as possible and alternatives are tried from left to right.
+    ///
+    /// # Usage
+    ///
+    /// ```rust
+    /// use bndm::YaraHexString;
+    ///
+    /// let hex_string: YaraHexString = "{ 4D 5A [2-4] (50 45 | 4E 45) }".parse().unwrap();
+    /// assert_eq!(hex_string.find(b"MZ..PE MZ...NE"), Some(0..6));
+    /// assert_eq!(hex_string.find(b"MZ.PE MZ...NE"), Some(6..13));
+    /// ```
+    pub fn find(&self, source: &[u8]) -> Option<Range<usize>> {
+        self.search.find_from(source, 0)
+    }
+
+    /// Returns the range of the first occurrence of the hex string in the source that starts
+    /// at or after the start index, or `None` if the hex string is not found.
+    pub fn find_from(&self, source: &[u8], start_index: usize) -> Option<Range<usize>> {
+        self.search.find_from(source, start_index)
+    }
+
+    /// Returns the ranges of all non-overlapping occurrences of the hex string in the source.
+    pub fn find_all(&self, source: &[u8]) -> Vec<Range<usize>> {
+        self.search.find_all(source)
+    }
+}
+
+impl TokenSearch {
+    pub(crate) fn new(tokens: Vec<Token>) -> TokenSearch {
+        let anchor = find_anchor(&tokens);
+        let mut program = vec![Instruction::Match];
+        let start = compile(&tokens, 0, &mut program);
+        TokenSearch { program, start, anchor }
+    }
+
+    pub(crate) fn find_from(&self, source: &[u8], start_index: usize) -> Option<Range<usize>> {
+        let mut failed = HashSet::new();
+        let mut match_at = |start| self.run(source, self.start, start, &mut failed).map(|end| start..end);
+
+        let Some(anchor) = &self.anchor else {
+            return (start_index..source.len()).find_map(match_at);
+        };
+
+        let mut next_start = start_index;
+        let mut anchor_start = start_index.saturating_add(anchor.min_offset);
+        while let Some(index) = find_pattern_from(source, &anchor.config, anchor_start) {
+            let first = anchor.max_offset.map_or(next_start, |max_offset| next_start.max(index.saturating_sub(max_offset)));
+            let last = index - anchor.min_offset;
+            if let Some(range) = (first..=last).find_map(&mut match_at) {
+                return Some(range);
+            }
+            next_start = next_start.max(last + 1);
+            anchor_start = index + 1;
+        }
+        None
+    }
+
+    pub(crate) fn find_all(&self, source: &[u8]) -> Vec<Range<usize>> {
+        let mut ranges = Vec::new();
+        let mut start_index = 0;
+        while let Some(range) = self.find_from(source, start_index) {
+            start_index = range.end;
+            ranges.push(range);
+        }
+        ranges
+    }
+
+    /// Returns the end of the first match of the program from the step at the position, with
+    /// jumps that match as few bytes as possible and alternatives that are tried from left to
+    /// right. The steps and positions that failed to match are remembered, so every pair is
+    /// only tried once per search, even across different start positions.
+    fn run(&self, source: &[u8], step: usize, position: usize, failed: &mut HashSet<(usize, usize)>) -> Option<usize> {
+        if failed.contains(&(step, position)) {
+            return None;
+        }
+
+        let end = match &self.program[step] {
+            Instruction::Match => return Some(position),
+            Instruction::Class(class, next) => source.get(position)
+                .filter(|&&byte| class.contains(byte))
+                .and_then(|_| self.run(source, *next, position + 1, failed)),
+            Instruction::Jump(min, max, next) => {
+                let available = source.len().saturating_sub(position);
+                let max = max.map_or(available, |max| max.min(available));
+                (*min..=max).find_map(|skip| self.run(source, *next, position + skip, failed))
+            }
+            Instruction::Split(starts) => starts.iter().find_map(|&start| self.run(source, start, position, failed))
+        };
+        if end.is_none() {
+            failed.insert((step, position));
+        }
+        end
+    }
+}
+
+/// Compiles the tokens into steps of the program that continue with the given step, and
+/// returns the first step.
+fn compile(tokens: &[Token], mut next: usize, program: &mut Vec<Instruction>) -> usize {
+    for token in tokens.iter().rev() {
+        let instruction = match token {
+            Token::Class(class) => Instruction::Class(*class, next),
+            Token::Jump(min, max) => Instruction::Jump(*min, *max, next),
+            Token::Alternatives(alternatives) => Instruction::Split(alternatives.iter()
+                .map(|alternative| compile(alternative, next, program))
+                .collect())
+        };
+        program.push(instruction);
+        next = program.len() - 1;
+    }
+    next
+}
+
+fn sequence_len_bounds(tokens: &[Token]) -> Option<(usize, Option<usize>)> {
+    tokens.iter().try_fold((0, Some(0)), |bounds, token| add_len_bounds(bounds, token.len_bounds()?))
+}
+
+/// Adds the length bounds of a token to those of the tokens before it, or returns `None` if
+/// a bound does not fit in a `usize`.
+pub(crate) fn add_len_bounds((min, max): (usize, Option<usize>), (token_min, token_max): (usize, Option<usize>)) -> Option<(usize, Option<usize>)> {
+    let max = match (max, token_max) {
+        (Some(max), Some(token_max)) => Some(max.checked_add(token_max)?),
+        _ => None
+    };
+    Some((min.checked_add(token_min)?, max))
+}
+
+fn find_anchor(tokens: &[Token]) -> Option<Anchor> {
+    let mut longest: Option<Range<usize>> = None;
+    let mut index = 0;
+
+    while index < tokens.len() {
+        let start = index;
+        while matches!(tokens.get(index), Some(Token::Class(_))) {
+            index += 1;
+        }
+        if index > start && longest.as_ref().is_none_or(|longest| index - start > longest.len()) {
+            longest = Some(start..index);
+        }
+        index += 1;
+    }
+
+    let run = longest?;
+    let classes: Vec<ByteClass> = tokens[run.clone()].iter()
+        .filter_map(|token| match token {
+            Token::Class(class) => Some(*class),
+            _ => None
+        })
+        .collect();
+    let (min_offset, max_offset) = sequence_len_bounds(&tokens[..run.start])?;
+
+    Some(Anchor { config: BndmConfig::with_classes(&classes), min_offset, max_offset })
+}
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    position: usize
+}
+
+impl Parser<'_> {
+    fn parse_sequence(&mut self, nested: bool) -> Result<Vec<Token>, YaraHexError> {
+        let mut tokens = Vec::new();
+        let mut last_jump = None;
+        let mut bounds = (0, Some(0));
+
+        loop {
+            self.skip_whitespace();
+            let position = self.position;
+            let token = match self.bytes.get(position) {
+                None if nested => return Err(YaraHexError::UnexpectedEnd),
+                None => break,
+                Some(b'|' | b')') if nested => break,
+                Some(b'[') => {
+                    let jump = self.parse_jump()?;
+                    if tokens.is_empty() {
+                        return Err(YaraHexError::InvalidJump(position));
+                    }
+                    last_jump = Some(position);
+                    jump
+                }
+                Some(b'(') => self.parse_alternatives()?,
+                Some(b'~') => {
+                    self.position += 1;
+                    match self.parse_byte()? {
+                        Token::Class(class) => Token::Class(class.complement()),
+                        token => token
+                    }
+                }
+                Some(_) => self.parse_byte()?
+            };
+            if !matches!(token, Token::Jump(..)) {
+                last_jump = None;
+            }
+            bounds = token.len_bounds().and_then(|token_bounds| add_len_bounds(bounds, token_bounds))
+                .ok_or(YaraHexError::InvalidJump(position))?;
+            tokens.push(token);
+        }
+
+        match (tokens.is_empty(), last_jump) {
+            (_, Some(position)) => Err(YaraHexError::InvalidJump(position)),
+            (true, _) if nested => Err(YaraHexError::InvalidCharacter(self.position)),
+            (true, _) => Err(YaraHexError::Empty),
+            _ => Ok(tokens)
+        }
+    }
+
+    fn parse_alternatives(&mut self) -> Result<Token, YaraHexError> {
+        let mut alternatives = Vec::new();
+        loop {
+            self.position += 1;
+            alternatives.push(self.parse_sequence(true)?);
+            match self.bytes.get(self.position) {
+                Some(b'|') => continue,
+                Some(b')') => break,
+                _ => return Err(YaraHexError::UnexpectedEnd)
+            }
+        }
+        self.position += 1;
+
+        if alternatives.len() < 2 {
+            return Err(YaraHexError::InvalidCharacter(self.position - 1));
+        }
+        Ok(Token::Alternatives(alternatives))
+    }
+
+    fn parse_jump(&mut self) -> Result<Token, YaraHexError> {
+        let start = self.position;
+        let close = self.bytes[start..].iter().position(|&byte| byte == b']')
+            .ok_or(YaraHexError::UnexpectedEnd)? + start;
+        self.position = close + 1;
+
+        let (min, max) = std::str::from_utf8(&self.bytes[start + 1..close]).ok()
+            .and_then(parse_jump_bounds)
+            .ok_or(YaraHexError::InvalidJump(start))?;
+        Ok(Token::Jump(min, max))
+    }
+
+    fn parse_byte(&mut self) -> Result<Token, YaraHexError> {
+        let start = self.position;
+        let digits = self.bytes.get(start..start + 2).ok_or(YaraHexError::UnexpectedEnd)?;
+        let (value, mask) = std::str::from_utf8(digits).ok()
+            .and_then(parse_hex_byte)
+            .ok_or(YaraHexError::InvalidCharacter(start))?;
+        self.position += 2;
+        Ok(Token::Class(ByteClass::masked(value, mask)))
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.bytes.get(self.position).is_some_and(|byte| byte.is_ascii_whitespace()) {
+            self.position += 1;
+        }
+    }
+}
+
+/// Parses the bounds of a jump, e.g. `4`, `2-4`, `2-`, `-4` or `-`, into a minimum and an
+/// optional maximum.
+pub(crate) fn parse_jump_bounds(bounds: &str) -> Option<(usize, Option<usize>)> {
+    if bounds.is_empty() {
+        return None;
+    }
+
+    let (min, max) = bounds.split_once('-').unwrap_or((bounds, bounds));
+    let min = parse_jump_bound(min, Some(0))?;
+    let max = match max {
+        "" => None,
+        max => Some(parse_jump_bound(max, None)?)
+    };
+    max.is_none_or(|max| min <= max).then_some((min, max))
+}
+
+fn parse_jump_bound(digits: &str, empty: Option<usize>) -> Option<usize> {
+    match digits {
+        "" => empty,
+        _ if digits.bytes().all(|digit| digit.is_ascii_digit()) => digits.parse().ok(),
+        _ => None
+    }
+}
+
+#[cfg(test)]
+#[path = "./yara_test.rs"]
+mod yara_test;
@@ -0,0 +1,142 @@
+use super::*;
+
+fn parse(hex_string: &str) -> YaraHexString {
+    hex_string.parse().unwrap()
+}
+
+fn parse_error(hex_string: &str) -> Option<YaraHexError> {
+    hex_string.parse::<YaraHexString>().err()
+}
+
+#[test]
+fn plain_bytes() {
+    let hex_string = parse("{ 4D 5A 9? }");
+
+    assert_eq!(hex_string.find(b"xMZ\x90 MZ\x9f"), Some(1..4));
+    assert_eq!(hex_string.find(b"MZ\xa0"), None);
+}
+
+#[test]
+fn braces_and_whitespace_are_optional() {
+    let hex_string = parse("4D5A[1]00");
+
+    assert_eq!(hex_string.find(b"MZ\x01\x00"), Some(0..4));
+}
+
+#[test]
+fn fixed_and_ranged_jumps() {
+    let hex_string = parse("{ AA [2] BB [1-3] CC }");
+
+    assert_eq!(hex_string.find(b"\xaa..\xbb.\xcc"), Some(0..6));
+    assert_eq!(hex_string.find(b"\xaa..\xbb...\xcc"), Some(0..8));
+    assert_eq!(hex_string.find(b"\xaa..\xbb....\xcc"), None);
+    assert_eq!(hex_string.find(b"\xaa.\xbb.\xcc"), None);
+}
+
+#[test]
+fn unbounded_jumps() {
+    let hex_string = parse("{ AA [2-] BB }");
+
+    assert_eq!(hex_string.find(b"\xaa.\xbb"), None);
+    assert_eq!(hex_string.find(b"\xaa........\xbb"), Some(0..10));
+    assert_eq!(parse("{ AA [-] BB }").find(b"\xaa\xbb"), Some(0..2));
+}
+
+#[test]
+fn alternatives() {
+    let hex_string = parse("{ 50 (45 | 4B 03 | 4E) }");
+
+    assert_eq!(hex_string.find(b"P PK\x03"), Some(2..5));
+    assert_eq!(hex_string.find_all(b"PE PN PK\x04"), vec![0..2, 3..5]);
+}
+
+#[test]
+fn leading_alternatives_and_jumps() {
+    let hex_string = parse("{ (AA | BB [1-2] CC) DD EE }");
+
+    assert_eq!(hex_string.find(b"\xbb..\xcc\xdd\xee"), Some(0..6));
+    assert_eq!(hex_string.find(b"\xbb...\xcc\xdd\xee \xaa\xdd\xee"), Some(8..11));
+}
+
+#[test]
+fn nested_alternatives() {
+    let hex_string = parse("{ 01 (02 (03 | 04) | 05) 06 }");
+
+    assert_eq!(hex_string.find(b"\x01\x02\x04\x06"), Some(0..4));
+    assert_eq!(hex_string.find(b"\x01\x05\x06"), Some(0..3));
+    assert_eq!(hex_string.find(b"\x01\x02\x05\x06"), None);
+}
+
+#[test]
+fn backtracks_into_alternatives() {
+    let hex_string = parse("{ (41 | 41 42) 43 }");
+
+    assert_eq!(hex_string.find(b"ABC"), Some(0..3));
+}
+
+#[test]
+fn only_alternatives() {
+    let hex_string = parse("{ (41 | 42) }");
+
+    assert_eq!(hex_string.find_all(b"xAyB"), vec![1..2, 3..4]);
+    assert_eq!(hex_string.find_from(b"xAyB", 2), Some(3..4));
+}
+
+#[test]
+fn negated_bytes() {
+    let hex_string = parse("{ 41 ~42 }");
+
+    assert_eq!(hex_string.find(b"ABAC"), Some(2..4));
+}
+
+#[test]
+fn leftmost_start_with_overlapping_anchor_windows() {
+    let hex_string = parse("{ 01 [0-4] 02 03 }");
+
+    assert_eq!(hex_string.find(b"\x01\x01\x02\x03"), Some(0..4));
+    assert_eq!(hex_string.find(b"\x01.....\x01\x02\x03"), Some(6..9));
+}
+
+#[test]
+fn long_anchor() {
+    let hex = vec!["AB"; 70].join(" ");
+    let hex_string = parse(&format!("{{ 01 [1-2] {hex} }}"));
+
+    let mut source = vec![0xab; 80];
+    assert_eq!(hex_string.find(&source), None);
+    source[3] = 0x01;
+    assert_eq!(hex_string.find(&source), Some(3..75));
+}
+
+#[test]
+fn invalid_hex_strings() {
+    assert_eq!(parse_error("{ }"), Some(YaraHexError::Empty));
+    assert_eq!(parse_error(""), Some(YaraHexError::Empty));
+    assert_eq!(parse_error("{ 4G }"), Some(YaraHexError::InvalidCharacter(2)));
+    assert_eq!(parse_error("{ 4 }"), Some(YaraHexError::InvalidCharacter(2)));
+    assert_eq!(parse_error("4D 5"), Some(YaraHexError::UnexpectedEnd));
+    assert_eq!(parse_error("{ [2] AA }"), Some(YaraHexError::InvalidJump(2)));
+    assert_eq!(parse_error("{ AA [2] }"), Some(YaraHexError::InvalidJump(5)));
+    assert_eq!(parse_error("{ AA [4-2] BB }"), Some(YaraHexError::InvalidJump(5)));
+    assert_eq!(parse_error("{ AA [] BB }"), Some(YaraHexError::InvalidJump(5)));
+    assert_eq!(parse_error("{ AA [+2] BB }"), Some(YaraHexError::InvalidJump(5)));
+    assert_eq!(parse_error("AA [18446744073709551615] BB [5] CC DD"), Some(YaraHexError::InvalidJump(3)));
+    assert_eq!(parse_error("AA [0-18446744073709551615] BB [5] CC DD"), Some(YaraHexError::InvalidJump(3)));
+    assert_eq!(parse_error("AA (BB [18446744073709551615] CC | DD) EE"), Some(YaraHexError::InvalidJump(7)));
+    assert_eq!(parse_error("{ AA [2 BB }"), Some(YaraHexError::UnexpectedEnd));
+    assert_eq!(parse_error("{ AA (BB | ) }"), Some(YaraHexError::InvalidCharacter(11)));
+    assert_eq!(parse_error("{ AA (BB) }"), Some(YaraHexError::InvalidCharacter(8)));
+    assert_eq!(parse_error("{ AA (BB | CC }"), Some(YaraHexError::UnexpectedEnd));
+    assert_eq!(YaraHexError::InvalidJump(3).to_string(), "invalid jump at position 3");
+}
+
+
+#[test]
+fn many_ranged_jumps_without_match() {
+    let hex_string: YaraHexString = ("41 [0-30] ".repeat(8) + "42").parse().unwrap();
+    let mut source = vec![0x41; 2000];
+
+    assert_eq!(hex_string.find(&source), None);
+    source[1990] = 0x42;
+    assert_eq!(hex_string.find(&source), Some(1742..1991));
+}
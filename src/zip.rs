@@ -0,0 +1,133 @@
+// Copyright (C) 2019 - 2024 Wilfred Bos
+// Licensed under the MIT license. See the LICENSE file for the terms and conditions.
+
+//! Recovery of ZIP entries from their local file headers, without a central directory.
+
+use std::ops::Range;
+
+use crate::{BndmConfig, find_indexes, find_pattern_from};
+
+const LOCAL_HEADER_SIGNATURE: &[u8] = b"PK\x03\x04";
+const LOCAL_HEADER_SIZE: usize = 30;
+const DATA_DESCRIPTOR_FLAG: u16 = 1 << 3;
+
+/// A ZIP entry that has been recovered from its local file header.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ZipEntry {
+    /// The index of the local file header in the source.
+    pub header_offset: usize,
+
+    /// The general purpose bit flags.
+    pub flags: u16,
+
+    /// The compression method, e.g. 0 for stored and 8 for deflated.
+    pub compression_method: u16,
+
+    /// The CRC-32 of the uncompressed data, or 0 if it is stored in a data descriptor.
+    pub crc32: u32,
+
+    /// The compressed size, or 0 if it is stored in a data descriptor.
+    pub compressed_size: u32,
+
+    /// The uncompressed size, or 0 if it is stored in a data descriptor.
+    pub uncompressed_size: u32,
+
+    /// The range of the file name in the source.
+    pub name: Range<usize>,
+
+    /// The candidate range of the compressed data in the source. If the sizes are stored in a
+    /// data descriptor, the range ends at the next ZIP signature or at the end of the source.
+    pub data: Range<usize>
+}
+
+impl ZipEntry {
+    /// Returns whether the sizes and the CRC-32 of the entry are stored in a data descriptor
+    /// after the compressed data instead of in the local file header.
+    pub fn has_data_descriptor(&self) -> bool {
+        self.flags & DATA_DESCRIPTOR_FLAG != 0
+    }
+}
+
+/// Searches for ZIP local file headers and recovers the entries they describe.
+///
+/// This does not rely on the central directory, so entries can be recovered from truncated or
+/// damaged archives and from archives that are embedded in other data. Headers that are
+/// truncated or have an empty file name are skipped.
+///
+/// # Arguments
+///
+/// * `source` - The source to recover ZIP entries from.
+///
+/// # Returns
+///
+/// * `Vec<ZipEntry>` - Returns the recovered entries ordered by their offset.
+///
+/// # Usage
+///
+/// ```rust
+/// use bndm::recover_zip_entries;
+///
+/// let mut source = b"PK\x03\x04\x0a\x00\x00\x00\x00\x00\x00\x00\x00\x00".to_vec();
+/// source.extend_from_slice(b"\x00\x00\x00\x00\x05\x00\x00\x00\x05\x00\x00\x00\x09\x00\x00\x00");
+/// source.extend_from_slice(b"hello.txthello");
+///
+/// let entries = recover_zip_entries(&source);
+/// assert_eq!(&source[entries[0].name.clone()], b"hello.txt");
+/// assert_eq!(&source[entries[0].data.clone()], b"hello");
+/// ```
+pub fn recover_zip_entries(source: &[u8]) -> Vec<ZipEntry> {
+    let config = BndmConfig::new(LOCAL_HEADER_SIGNATURE, None);
+    find_indexes(source, &config)
+        .filter_map(|offset| parse_local_header(source, offset))
+        .collect()
+}
+
+fn parse_local_header(source: &[u8], offset: usize) -> Option<ZipEntry> {
+    let header = source.get(offset..offset + LOCAL_HEADER_SIZE)?;
+    let read_u16 = |index: usize| u16::from_le_bytes([header[index], header[index + 1]]);
+    let read_u32 = |index: usize| u32::from_le_bytes([header[index], header[index + 1], header[index + 2], header[index + 3]]);
+
+    let name_len = read_u16(26) as usize;
+    let extra_len = read_u16(28) as usize;
+    let name_start = offset + LOCAL_HEADER_SIZE;
+    let data_start = name_start + name_len + extra_len;
+    if name_len == 0 || data_start > source.len() {
+        return None;
+    }
+
+    let flags = read_u16(6);
+    let compressed_size = read_u32(18);
+    let data_end = if flags & DATA_DESCRIPTOR_FLAG != 0 {
+        find_next_signature(source, data_start)
+    } else {
+        data_start.saturating_add(compressed_size as usize).min(source.len())
+    };
+
+    Some(ZipEntry {
+        header_offset: offset,
+        flags,
+        compression_method: read_u16(8),
+        crc32: read_u32(14),
+        compressed_size,
+        uncompressed_size: read_u32(22),
+        name: name_start..name_start + name_len,
+        data: data_start..data_end
+    })
+}
+
+fn find_next_signature(source: &[u8], start_index: usize) -> usize {
+    let config = BndmConfig::new(b"PK??", Some(b'?'));
+    let mut index = start_index;
+
+    while let Some(found) = find_pattern_from(source, &config, index) {
+        if matches!(&source[found + 2..found + 4], b"\x03\x04" | b"\x01\x02" | b"\x07\x08" | b"\x05\x06") {
+            return found;
+        }
+        index = found + 1;
+    }
+    source.len()
+}
+
+#[cfg(test)]
+#[path = "./zip_test.rs"]
+mod zip_test;
@@ -0,0 +1,82 @@
+use super::*;
+
+fn local_header(flags: u16, compressed_size: u32, name: &[u8], extra: &[u8]) -> Vec<u8> {
+    let mut header = LOCAL_HEADER_SIGNATURE.to_vec();
+    header.extend_from_slice(&20u16.to_le_bytes());
+    header.extend_from_slice(&flags.to_le_bytes());
+    header.extend_from_slice(&8u16.to_le_bytes());
+    header.extend_from_slice(&[0; 4]);
+    header.extend_from_slice(&0x12345678u32.to_le_bytes());
+    header.extend_from_slice(&compressed_size.to_le_bytes());
+    header.extend_from_slice(&(compressed_size * 2).to_le_bytes());
+    header.extend_from_slice(&(name.len() as u16).to_le_bytes());
+    header.extend_from_slice(&(extra.len() as u16).to_le_bytes());
+    header.extend_from_slice(name);
+    header.extend_from_slice(extra);
+    header
+}
+
+#[test]
+fn recover_entries() {
+    let mut source = b"junk".to_vec();
+    source.extend(local_header(0, 4, b"a.txt", b""));
+    source.extend_from_slice(b"DATA");
+    source.extend(local_header(0, 2, b"dir/b.bin", b"\x01\x00\x00\x00"));
+    source.extend_from_slice(b"XY");
+    let entries = recover_zip_entries(&source);
+
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].header_offset, 4);
+    assert_eq!(&source[entries[0].name.clone()], b"a.txt");
+    assert_eq!(&source[entries[0].data.clone()], b"DATA");
+    assert_eq!(entries[0].compression_method, 8);
+    assert_eq!(entries[0].crc32, 0x12345678);
+    assert_eq!(entries[0].uncompressed_size, 8);
+    assert_eq!(&source[entries[1].name.clone()], b"dir/b.bin");
+    assert_eq!(&source[entries[1].data.clone()], b"XY");
+}
+
+#[test]
+fn recover_entry_with_data_descriptor() {
+    let mut source = local_header(DATA_DESCRIPTOR_FLAG, 0, b"stream", b"");
+    source.extend_from_slice(b"compressed");
+    source.extend_from_slice(b"PK\x07\x08");
+    let entries = recover_zip_entries(&source);
+
+    assert!(entries[0].has_data_descriptor());
+    assert_eq!(&source[entries[0].data.clone()], b"compressed");
+}
+
+#[test]
+fn recover_entry_with_data_descriptor_at_end() {
+    let mut source = local_header(DATA_DESCRIPTOR_FLAG, 0, b"stream", b"");
+    source.extend_from_slice(b"PK\x00\x00truncated");
+    let entries = recover_zip_entries(&source);
+
+    assert_eq!(&source[entries[0].data.clone()], b"PK\x00\x00truncated");
+}
+
+#[test]
+fn recover_truncated_data() {
+    let mut source = local_header(0, 100, b"big", b"");
+    source.extend_from_slice(b"partial");
+    let entries = recover_zip_entries(&source);
+
+    assert_eq!(&source[entries[0].data.clone()], b"partial");
+}
+
+#[test]
+fn recover_skip_truncated_header() {
+    let source = local_header(0, 0, b"name", b"");
+    let entries = recover_zip_entries(&source[..28]);
+
+    assert_eq!(entries, vec![]);
+}
+
+#[test]
+fn recover_skip_empty_name() {
+    let source = local_header(0, 0, b"", b"");
+    let entries = recover_zip_entries(&source);
+
+    assert_eq!(entries, vec![]);
+}